@@ -0,0 +1,158 @@
+// src/formats/aiken.rs
+
+use crate::models::{QuestionType, Quiz};
+use super::{QuizImport, SkippedQuestions};
+
+/// Aiken labels its options A, B, C, ... so a question can have at most 26.
+const MAX_OPTIONS: usize = 26;
+
+/// Option text if `line` is "X. text" or "X) text" with X the expected letter.
+fn option_text(line: &str, letter: char) -> Option<&str> {
+    let rest = line.strip_prefix(letter)?;
+    let rest = rest.strip_prefix(". ").or_else(|| rest.strip_prefix(") "))?;
+    Some(rest.trim())
+}
+
+/// A question being read: (line it started on, text lines, options).
+type Pending = (usize, Vec<String>, Vec<String>);
+
+/// Parse an Aiken file (multiple choice only) into a quiz called `title`.
+pub fn parse_aiken(text: &str, title: &str) -> QuizImport {
+    let mut out = QuizImport::default();
+    let mut quiz = Quiz::new(title.to_string());
+    let mut current: Option<Pending> = None;
+    for (i, raw) in text.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if line.get(..7).is_some_and(|h| h.eq_ignore_ascii_case("ANSWER:")) {
+            let letter = line[7..].trim().chars().next().map(|c| c.to_ascii_uppercase());
+            match current.take() {
+                Some((start, _, options)) if options.len() < 2 => {
+                    out.skipped.push((start, "needs at least two options".to_string()));
+                }
+                Some((start, prompt, options)) => {
+                    let index = letter.filter(char::is_ascii_uppercase).map(|c| (c as u8 - b'A') as usize);
+                    match index.and_then(|n| options.get(n)) {
+                        Some(answer) => {
+                            let answer = answer.clone();
+                            quiz.add_question(prompt.join("\n"), options, answer, QuestionType::MultipleChoice);
+                        }
+                        None => out.skipped.push((start, format!("ANSWER '{}' is not one of the options", line[7..].trim()))),
+                    }
+                }
+                None => out.skipped.push((i + 1, "ANSWER without a question".to_string())),
+            }
+            continue;
+        }
+        if let Some((_, prompt, options)) = current.as_mut() {
+            let letter = (b'A' + options.len().min(MAX_OPTIONS - 1) as u8) as char;
+            if options.len() < MAX_OPTIONS
+                && let Some(option) = option_text(line, letter)
+            {
+                options.push(option.to_string());
+                continue;
+            }
+            if options.is_empty() {
+                prompt.push(line.to_string());
+                continue;
+            }
+        }
+        // text after the options starts the next question
+        if let Some((start, ..)) = current.take() {
+            out.skipped.push((start, "no ANSWER line".to_string()));
+        }
+        current = Some((i + 1, vec![line.to_string()], Vec::new()));
+    }
+    if let Some((start, ..)) = current {
+        out.skipped.push((start, "no ANSWER line".to_string()));
+    }
+    out.quiz = quiz;
+    out
+}
+
+/// Write a quiz's multiple-choice questions as Aiken. Other questions are left
+/// out and reported as (question number, reason).
+pub fn quiz_to_aiken(quiz: &Quiz) -> (String, SkippedQuestions) {
+    let mut out = String::new();
+    let mut skipped = Vec::new();
+    let one_line = |text: &str| text.split_whitespace().collect::<Vec<_>>().join(" ");
+    for i in 0..quiz.question_count() {
+        let Some(q) = quiz.get_question_data(i) else { continue };
+        if q.question_type != QuestionType::MultipleChoice {
+            skipped.push((i + 1, "Aiken only holds multiple-choice questions".to_string()));
+            continue;
+        }
+        if q.options.len() > MAX_OPTIONS {
+            skipped.push((i + 1, format!("more than {} options", MAX_OPTIONS)));
+            continue;
+        }
+        let Some(answer) = q.options.iter().position(|o| o.trim() == q.answer.trim()) else {
+            skipped.push((i + 1, "answer is not one of the options".to_string()));
+            continue;
+        };
+        out.push_str(&one_line(&q.prompt));
+        out.push('\n');
+        for (n, option) in q.options.iter().enumerate() {
+            out.push_str(&format!("{}. {}\n", (b'A' + n as u8) as char, one_line(option)));
+        }
+        out.push_str(&format!("ANSWER: {}\n\n", (b'A' + answer as u8) as char));
+    }
+    (out, skipped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_questions_separated_by_blank_lines() {
+        let text = "What is the capital\nof France?\nA. Paris\nB) London\nC. Rome\nanswer: a\n\n\nLargest planet?\nA. Mars\nB. Jupiter\nANSWER: B\n";
+        let import = parse_aiken(text, "Geography");
+        assert!(import.skipped.is_empty(), "{:?}", import.skipped);
+        assert_eq!(import.quiz.title(), "Geography");
+        assert_eq!(import.quiz.question_count(), 2);
+        let q = import.quiz.get_question_data(0).unwrap();
+        assert_eq!(q.prompt, "What is the capital\nof France?");
+        assert_eq!(q.options, ["Paris", "London", "Rome"]);
+        assert_eq!(q.answer, "Paris");
+        assert_eq!(import.quiz.get_question_data(1).unwrap().answer, "Jupiter");
+    }
+
+    #[test]
+    fn export_then_import_keeps_multiple_choice_questions() {
+        let mut quiz = Quiz::new("Quiz".to_string());
+        quiz.add_question("2 + 2?".to_string(), vec!["3".to_string(), "4".to_string(), "5".to_string()], "4".to_string(), QuestionType::MultipleChoice);
+        quiz.add_question("Sky colour".to_string(), vec!["Blue".to_string(), "Green".to_string()], "Blue".to_string(), QuestionType::MultipleChoice);
+        let (text, skipped) = quiz_to_aiken(&quiz);
+        assert!(skipped.is_empty());
+        let import = parse_aiken(&text, "Quiz");
+        assert!(import.skipped.is_empty());
+        assert_eq!(import.quiz, quiz);
+    }
+
+    #[test]
+    fn export_leaves_out_what_aiken_cannot_hold() {
+        let mut quiz = Quiz::new("Quiz".to_string());
+        quiz.add_question("Name it".to_string(), Vec::new(), "x".to_string(), QuestionType::FillInTheBlank);
+        quiz.add_question("Pick".to_string(), vec!["a".to_string(), "b".to_string()], "c".to_string(), QuestionType::MultipleChoice);
+        let (text, skipped) = quiz_to_aiken(&quiz);
+        assert!(text.is_empty());
+        assert_eq!(skipped.iter().map(|(n, _)| *n).collect::<Vec<_>>(), [1, 2]);
+    }
+
+    #[test]
+    fn reports_malformed_questions_with_their_line() {
+        let text = "ANSWER: A\nOne option\nA. only\nANSWER: A\nBad letter\nA. x\nB. y\nANSWER: D\nNo answer line\nA. x\nB. y\n";
+        let import = parse_aiken(text, "Quiz");
+        assert_eq!(import.quiz.question_count(), 0);
+        let skipped: Vec<(usize, &str)> = import.skipped.iter().map(|(line, reason)| (*line, reason.as_str())).collect();
+        assert_eq!(skipped, [
+            (1, "ANSWER without a question"),
+            (2, "needs at least two options"),
+            (5, "ANSWER 'D' is not one of the options"),
+            (9, "no ANSWER line"),
+        ]);
+    }
+}
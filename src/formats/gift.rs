@@ -0,0 +1,380 @@
+// src/formats/gift.rs
+
+use crate::models::{QuestionType, Quiz};
+use super::{QuizImport, SkippedQuestions};
+
+/// Characters that carry meaning in GIFT and have to be written as `\x`.
+const SPECIAL: [char; 6] = ['~', '=', '#', '{', '}', ':'];
+
+/// Undo GIFT escapes (`\=`, `\{`, ..., and `\n` for a line break).
+fn unescape(text: &str) -> String {
+    let mut out = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some('n')) => {
+                out.push('\n');
+                chars.next();
+            }
+            ('\\', Some(next)) if SPECIAL.contains(&next) || next == '\\' => {
+                out.push(next);
+                chars.next();
+            }
+            _ => out.push(c),
+        }
+    }
+    out.trim().to_string()
+}
+
+fn escape(text: &str) -> String {
+    let mut out = String::new();
+    for c in text.trim().chars() {
+        match c {
+            '\n' => out.push_str("\\n"),
+            '\r' => {}
+            c if SPECIAL.contains(&c) || c == '\\' => {
+                out.push('\\');
+                out.push(c);
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Byte offset of the first `target` in `text` that is not escaped with a backslash.
+fn find_unescaped(text: &str, target: char) -> Option<usize> {
+    let mut escaped = false;
+    for (i, c) in text.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == target {
+            return Some(i);
+        }
+    }
+    None
+}
+
+/// Text before an unescaped `#` (answer feedback is dropped).
+fn without_feedback(text: &str) -> &str {
+    find_unescaped(text, '#').map_or(text, |i| &text[..i])
+}
+
+/// One `=` (right) or `~` (wrong or weighted) entry of an answer block.
+struct Choice {
+    right: bool,
+    /// Percentage from a `%50%` prefix; a plain `=` counts as 100.
+    weight: f64,
+    /// Still escaped; feedback removed.
+    text: String,
+}
+
+/// Split an answer block into its `=` / `~` entries.
+fn choices(block: &str) -> Vec<Choice> {
+    let mut raw: Vec<(char, String)> = Vec::new();
+    let mut escaped = false;
+    for c in block.chars() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == '=' || c == '~' {
+            raw.push((c, String::new()));
+            continue;
+        }
+        if let Some((_, text)) = raw.last_mut() {
+            text.push(c);
+        }
+    }
+    raw.into_iter()
+        .map(|(marker, text)| {
+            let mut text = without_feedback(&text).trim();
+            let mut weight = if marker == '=' { 100.0 } else { 0.0 };
+            if let Some(rest) = text.strip_prefix('%')
+                && let Some((number, rest)) = rest.split_once('%')
+            {
+                weight = number.trim().parse().unwrap_or(weight);
+                text = rest.trim();
+            }
+            Choice { right: marker == '=', weight, text: text.to_string() }
+        })
+        .collect()
+}
+
+/// Parse a numerical answer (`5`, `5:0.5` or `1..10`); the note explains what was lost.
+fn numerical_answer(value: &str) -> Option<(String, Option<String>)> {
+    let value = without_feedback(value).trim();
+    if let Some((low, high)) = value.split_once("..") {
+        let (low, high): (f64, f64) = (low.trim().parse().ok()?, high.trim().parse().ok()?);
+        let mid = (low + high) / 2.0;
+        return Some((mid.to_string(), Some(format!("range {}..{} kept as its midpoint {}", low, high, mid))));
+    }
+    if let Some((number, tolerance)) = value.split_once(':') {
+        let (number, tolerance): (f64, f64) = (number.trim().parse().ok()?, tolerance.trim().parse().ok()?);
+        let note = (tolerance != 0.0).then(|| format!("tolerance ±{} dropped; only {} is accepted", tolerance, number));
+        return Some((number.to_string(), note));
+    }
+    let number: f64 = value.parse().ok()?;
+    Some((number.to_string(), None))
+}
+
+/// Turn one question (the lines between blank lines) into quiz questions.
+fn parse_question(text: &str, line: usize, quiz: &mut Quiz, out: &mut QuizImport) {
+    let mut text = text.trim();
+    // "::title::" names the question in Moodle; there is no place for it here
+    if let Some(rest) = text.strip_prefix("::")
+        && let Some(end) = rest.find("::")
+    {
+        text = rest[end + 2..].trim_start();
+    }
+    for format in ["[html]", "[moodle]", "[plain]", "[markdown]"] {
+        if let Some(rest) = text.strip_prefix(format) {
+            text = rest.trim_start();
+        }
+    }
+    let Some(open) = find_unescaped(text, '{') else {
+        out.skipped.push((line, "no answer block; descriptions are not supported".to_string()));
+        return;
+    };
+    let Some(len) = find_unescaped(&text[open + 1..], '}') else {
+        out.skipped.push((line, "answer block is not closed".to_string()));
+        return;
+    };
+    let block = text[open + 1..open + 1 + len].trim();
+    let (head, tail) = (unescape(&text[..open]), unescape(&text[open + 2 + len..]));
+    // text after the block makes it a missing-word question
+    let prompt = if tail.is_empty() { head } else { format!("{} _____ {}", head, tail) };
+
+    if block.is_empty() {
+        out.skipped.push((line, "essay questions are not supported".to_string()));
+        return;
+    }
+    if let Some(numeric) = block.strip_prefix('#') {
+        let list = choices(numeric);
+        let value = if list.is_empty() {
+            numeric.to_string()
+        } else {
+            let best = list.iter().max_by(|a, b| a.weight.total_cmp(&b.weight)).expect("list is not empty");
+            if list.len() > 1 {
+                out.converted.push((line, "only the full-credit numerical answer is kept".to_string()));
+            }
+            best.text.clone()
+        };
+        match numerical_answer(&value) {
+            Some((answer, note)) => {
+                if let Some(note) = note {
+                    out.converted.push((line, note));
+                }
+                quiz.add_question(prompt, Vec::new(), answer, QuestionType::FillInTheBlank);
+            }
+            None => out.skipped.push((line, format!("cannot read numerical answer '{}'", value.trim()))),
+        }
+        return;
+    }
+    match without_feedback(block).trim().to_ascii_uppercase().as_str() {
+        "T" | "TRUE" | "F" | "FALSE" => {
+            let answer = if block.to_ascii_uppercase().starts_with('T') { "True" } else { "False" };
+            let options = vec!["True".to_string(), "False".to_string()];
+            quiz.add_question(prompt, options, answer.to_string(), QuestionType::MultipleChoice);
+            return;
+        }
+        _ => {}
+    }
+
+    let list = choices(block);
+    if list.is_empty() {
+        out.skipped.push((line, "unrecognized answer block".to_string()));
+    } else if list.iter().all(|c| c.right && c.text.contains("->")) {
+        // matching: one multiple-choice question per pair, offering every match
+        let pairs: Vec<(String, String)> = list.iter()
+            .filter_map(|c| c.text.split_once("->").map(|(l, r)| (unescape(l), unescape(r))))
+            .collect();
+        let mut options: Vec<String> = Vec::new();
+        for (_, right) in &pairs {
+            if !options.contains(right) {
+                options.push(right.clone());
+            }
+        }
+        for (left, right) in pairs.iter().filter(|(l, _)| !l.is_empty()) {
+            quiz.add_question(format!("{}\n{}", prompt, left), options.clone(), right.clone(), QuestionType::MultipleChoice);
+        }
+        out.converted.push((line, format!("matching question split into {} multiple-choice questions", pairs.len())));
+    } else if list.iter().all(|c| c.right) {
+        if list.len() > 1 {
+            out.converted.push((line, "alternative answers dropped; only the first is accepted".to_string()));
+        }
+        quiz.add_question(prompt, Vec::new(), unescape(&list[0].text), QuestionType::FillInTheBlank);
+    } else {
+        let options: Vec<String> = list.iter().map(|c| unescape(&c.text)).collect();
+        // rev() so that ties go to the first option
+        let best = list.iter().enumerate().rev()
+            .max_by(|(_, a), (_, b)| (a.right, a.weight).partial_cmp(&(b.right, b.weight)).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|(i, _)| i)
+            .expect("list is not empty");
+        if !list[best].right && list[best].weight <= 0.0 {
+            out.skipped.push((line, "no answer is marked right".to_string()));
+            return;
+        }
+        if list.iter().filter(|c| c.weight > 0.0).count() > 1 {
+            out.converted.push((line, format!("several answers earn credit; only '{}' is kept", options[best])));
+        }
+        quiz.add_question(prompt, options.clone(), options[best].clone(), QuestionType::MultipleChoice);
+    }
+}
+
+/// Parse a Moodle GIFT file into a quiz. The last `$CATEGORY` segment, if any,
+/// becomes the title; otherwise `title` is used. Essays and descriptions are
+/// skipped, matching questions become one multiple-choice question per pair.
+pub fn parse_gift(text: &str, title: &str) -> QuizImport {
+    let mut quiz_title = title.to_string();
+    let mut out = QuizImport::default();
+    let mut quiz = Quiz::new(String::new());
+    let mut current = String::new();
+    let mut start = 0;
+    let mut depth = 0i32;
+    for (i, raw) in text.lines().chain(std::iter::once("")).enumerate() {
+        let line = raw.trim();
+        if line.starts_with("//") {
+            continue;
+        }
+        if let Some(category) = line.strip_prefix("$CATEGORY:") {
+            if let Some(last) = category.trim().rsplit('/').next().filter(|s| !s.trim().is_empty()) {
+                quiz_title = last.trim().to_string();
+            }
+            continue;
+        }
+        if line.is_empty() && depth <= 0 {
+            if !current.trim().is_empty() {
+                parse_question(&current, start, &mut quiz, &mut out);
+            }
+            current.clear();
+            depth = 0;
+            continue;
+        }
+        if current.is_empty() {
+            start = i + 1;
+        } else {
+            current.push('\n');
+        }
+        current.push_str(line);
+        let mut escaped = false;
+        for c in line.chars() {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                _ => {}
+            }
+        }
+    }
+    // an answer block still open at the end of the file
+    if !current.trim().is_empty() {
+        parse_question(&current, start, &mut quiz, &mut out);
+    }
+    quiz.set_title(quiz_title);
+    out.quiz = quiz;
+    out
+}
+
+/// Write a quiz as GIFT for upload to an LMS. Questions that GIFT cannot hold
+/// (no answer, or an answer that is not one of the options) are left out and
+/// reported as (question number, reason).
+pub fn quiz_to_gift(quiz: &Quiz) -> (String, SkippedQuestions) {
+    let mut out = format!("// {}\n$CATEGORY: {}\n\n", quiz.title().replace('\n', " "), quiz.title().replace(['\n', '/'], " "));
+    let mut skipped = Vec::new();
+    for i in 0..quiz.question_count() {
+        let Some(q) = quiz.get_question_data(i) else { continue };
+        let answer = q.answer.trim();
+        let block = match q.question_type {
+            QuestionType::FillInTheBlank if answer.is_empty() => {
+                skipped.push((i + 1, "no answer set".to_string()));
+                continue;
+            }
+            QuestionType::FillInTheBlank if answer.parse::<f64>().is_ok() => format!("{{#{}}}", answer),
+            QuestionType::FillInTheBlank => format!("{{={}}}", escape(answer)),
+            QuestionType::MultipleChoice if !q.options.iter().any(|o| o.trim() == answer) => {
+                skipped.push((i + 1, "answer is not one of the options".to_string()));
+                continue;
+            }
+            QuestionType::MultipleChoice if q.options.len() == 2
+                && q.options[0].trim().eq_ignore_ascii_case("true")
+                && q.options[1].trim().eq_ignore_ascii_case("false") =>
+            {
+                if answer.eq_ignore_ascii_case("true") { "{TRUE}".to_string() } else { "{FALSE}".to_string() }
+            }
+            QuestionType::MultipleChoice => {
+                let mut block = "{\n".to_string();
+                for option in &q.options {
+                    let marker = if option.trim() == answer { '=' } else { '~' };
+                    block.push_str(&format!("\t{}{}\n", marker, escape(option)));
+                }
+                block.push('}');
+                block
+            }
+        };
+        out.push_str(&format!("::Q{}:: {} {}\n\n", i + 1, escape(&q.prompt), block));
+    }
+    (out, skipped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_escapes_weights_comments_and_blank_line_separators() {
+        let text = "// a comment\n$CATEGORY: $course$/Biology/Cells\n\n\
+            ::Q1:: What is 1\\=1 \\# \\~? {=yes ~no}\n\n\
+            Which are organelles? {\n  ~%50%Nucleus\n  ~%100%Mitochondrion#right\n  ~%-50%Cell wall\n}\n\n\
+            The sky is blue. {T}\n";
+        let import = parse_gift(text, "fallback");
+        assert!(import.skipped.is_empty(), "{:?}", import.skipped);
+        assert_eq!(import.quiz.title(), "Cells");
+        assert_eq!(import.quiz.question_count(), 3);
+
+        let q = import.quiz.get_question_data(0).unwrap();
+        assert_eq!(q.prompt, "What is 1=1 # ~?");
+        assert_eq!(q.options, ["yes", "no"]);
+        assert_eq!(q.answer, "yes");
+
+        let q = import.quiz.get_question_data(1).unwrap();
+        assert_eq!(q.answer, "Mitochondrion");
+        assert_eq!(q.options.len(), 3);
+        assert!(import.converted.iter().any(|(line, _)| *line == 6));
+
+        let q = import.quiz.get_question_data(2).unwrap();
+        assert_eq!((q.answer.as_str(), q.question_type), ("True", QuestionType::MultipleChoice));
+    }
+
+    #[test]
+    fn export_then_import_keeps_the_quiz() {
+        let mut quiz = Quiz::new("Chemistry".to_string());
+        quiz.add_question("Symbol of gold {Au}?".to_string(), vec!["Au".to_string(), "Ag = silver".to_string(), "G~".to_string()], "Au".to_string(), QuestionType::MultipleChoice);
+        quiz.add_question("Water is H_2O: name it".to_string(), Vec::new(), "water #1".to_string(), QuestionType::FillInTheBlank);
+        quiz.add_question("Protons in carbon".to_string(), Vec::new(), "6".to_string(), QuestionType::FillInTheBlank);
+        quiz.add_question("Gold is a metal".to_string(), vec!["True".to_string(), "False".to_string()], "True".to_string(), QuestionType::MultipleChoice);
+        let (text, skipped) = quiz_to_gift(&quiz);
+        assert!(skipped.is_empty());
+        let import = parse_gift(&text, "unused");
+        assert!(import.skipped.is_empty() && import.converted.is_empty(), "{:?} {:?}", import.skipped, import.converted);
+        assert_eq!(import.quiz, quiz);
+    }
+
+    #[test]
+    fn reports_malformed_questions_with_their_line() {
+        // an unclosed block runs to the end of the file, so it comes last
+        let text = "No block here\n\nEssay {}\n\nNothing right {~a ~b}\n\nGood {=ok}\n\nOpen block {=a\n";
+        let import = parse_gift(text, "Quiz");
+        assert_eq!(import.quiz.question_count(), 1);
+        let skipped: Vec<(usize, &str)> = import.skipped.iter().map(|(line, reason)| (*line, reason.as_str())).collect();
+        assert_eq!(skipped, [
+            (1, "no answer block; descriptions are not supported"),
+            (3, "essay questions are not supported"),
+            (5, "no answer is marked right"),
+            (9, "answer block is not closed"),
+        ]);
+    }
+}
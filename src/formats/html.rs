@@ -0,0 +1,171 @@
+// src/formats/html.rs
+
+use std::io;
+use crate::models::StudySet;
+
+/// Page skeleton; `{{TITLE}}` and `{{DATA}}` are filled in by `set_to_html`.
+/// The script only ever assigns set text through `textContent`, so card and
+/// quiz text cannot inject markup.
+const TEMPLATE: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>{{TITLE}}</title>
+<style>
+  body { font-family: system-ui, sans-serif; margin: 0; background: #f4f5f7; color: #222; }
+  header { background: #2d3e50; color: #fff; padding: 12px 20px; }
+  header h1 { margin: 0; font-size: 1.4em; }
+  nav button { margin-right: 6px; }
+  main { max-width: 760px; margin: 20px auto; padding: 0 16px; }
+  button { font: inherit; padding: 6px 14px; border: 1px solid #8895a7; border-radius: 6px; background: #fff; cursor: pointer; }
+  button.active { background: #2d3e50; color: #fff; }
+  .card { perspective: 1000px; height: 260px; margin: 16px 0; cursor: pointer; }
+  .card-inner { position: relative; width: 100%; height: 100%; transition: transform 0.4s; transform-style: preserve-3d; }
+  .card.flipped .card-inner { transform: rotateY(180deg); }
+  .face { position: absolute; inset: 0; display: flex; align-items: center; justify-content: center; padding: 20px;
+          box-sizing: border-box; border-radius: 10px; background: #fff; box-shadow: 0 2px 8px rgba(0,0,0,0.15);
+          backface-visibility: hidden; font-size: 1.3em; text-align: center; white-space: pre-wrap; overflow: auto; }
+  .back { transform: rotateY(180deg); background: #eef6ee; }
+  .hints { color: #666; font-size: 0.9em; }
+  .controls { display: flex; gap: 8px; align-items: center; }
+  .question { background: #fff; border-radius: 8px; padding: 12px 16px; margin: 12px 0; box-shadow: 0 1px 4px rgba(0,0,0,0.1); }
+  .question.right { border-left: 6px solid #3a9d4a; }
+  .question.wrong { border-left: 6px solid #c94c4c; }
+  .question label { display: block; margin: 4px 0; }
+  .feedback { margin-top: 6px; font-size: 0.9em; }
+  .hidden { display: none; }
+</style>
+</head>
+<body>
+<header><h1>{{TITLE}}</h1><nav id="nav"></nav></header>
+<main id="main"></main>
+<script type="application/json" id="set-data">{{DATA}}</script>
+<script>
+(function () {
+  const set = JSON.parse(document.getElementById("set-data").textContent);
+  const nav = document.getElementById("nav");
+  const main = document.getElementById("main");
+
+  function el(tag, props, children) {
+    const node = document.createElement(tag);
+    Object.assign(node, props || {});
+    (children || []).forEach(function (c) { node.appendChild(typeof c === "string" ? document.createTextNode(c) : c); });
+    return node;
+  }
+
+  function showCards() {
+    main.replaceChildren();
+    const cards = set.flashcards.slice();
+    if (cards.length === 0) { main.appendChild(el("p", { textContent: "This set has no cards." })); return; }
+    let index = 0;
+    const front = el("div", { className: "face front" });
+    const back = el("div", { className: "face back" });
+    const card = el("div", { className: "card", title: "Click to flip" }, [el("div", { className: "card-inner" }, [front, back])]);
+    const counter = el("span");
+    function render() {
+      const c = cards[index];
+      card.classList.remove("flipped");
+      front.replaceChildren(c.question);
+      back.replaceChildren(c.answer);
+      if (c.hints.length > 0) {
+        front.appendChild(el("div", { className: "hints" }, ["\n\nHint: " + c.hints.join(" / ")]));
+      }
+      counter.textContent = (index + 1) + " / " + cards.length;
+    }
+    card.addEventListener("click", function () { card.classList.toggle("flipped"); });
+    const prev = el("button", { textContent: "Previous", onclick: function () { index = (index + cards.length - 1) % cards.length; render(); } });
+    const next = el("button", { textContent: "Next", onclick: function () { index = (index + 1) % cards.length; render(); } });
+    const shuffle = el("button", { textContent: "Shuffle", onclick: function () {
+      for (let i = cards.length - 1; i > 0; i--) { const j = Math.floor(Math.random() * (i + 1)); [cards[i], cards[j]] = [cards[j], cards[i]]; }
+      index = 0; render();
+    } });
+    document.onkeydown = function (e) {
+      if (e.key === "ArrowLeft") prev.onclick();
+      else if (e.key === "ArrowRight") next.onclick();
+      else if (e.key === " ") { e.preventDefault(); card.classList.toggle("flipped"); }
+    };
+    main.append(card, el("div", { className: "controls" }, [prev, next, shuffle, counter]));
+    render();
+  }
+
+  function isCorrect(q, given) {
+    if (q.question_type === "FillInTheBlank") return given.trim().toLowerCase() === q.answer.trim().toLowerCase();
+    return given === q.answer;
+  }
+
+  function showQuiz(quiz) {
+    document.onkeydown = null;
+    main.replaceChildren(el("h2", { textContent: quiz.title }));
+    const answers = quiz.questions.map(function (q, i) {
+      const box = el("div", { className: "question" }, [el("strong", { textContent: (i + 1) + ". " + q.prompt })]);
+      let read;
+      if (q.question_type === "FillInTheBlank") {
+        const input = el("input", { type: "text" });
+        box.appendChild(el("div", {}, [input]));
+        read = function () { return input.value; };
+      } else {
+        const name = "q" + i;
+        q.options.forEach(function (o) {
+          box.appendChild(el("label", {}, [el("input", { type: "radio", name: name, value: o }), " " + o]));
+        });
+        read = function () { const c = box.querySelector("input:checked"); return c ? c.value : ""; };
+      }
+      const feedback = el("div", { className: "feedback hidden" });
+      box.appendChild(feedback);
+      main.appendChild(box);
+      return { q: q, box: box, read: read, feedback: feedback };
+    });
+    const result = el("p");
+    main.append(el("button", { textContent: "Check answers", onclick: function () {
+      let right = 0;
+      answers.forEach(function (a) {
+        const ok = isCorrect(a.q, a.read());
+        if (ok) right++;
+        a.box.className = "question " + (ok ? "right" : "wrong");
+        a.feedback.textContent = ok ? "Correct" : "Answer: " + a.q.answer;
+        a.feedback.classList.remove("hidden");
+      });
+      result.textContent = "Score: " + right + " / " + answers.length;
+    } }), result);
+  }
+
+  function select(button, action) {
+    nav.querySelectorAll("button").forEach(function (b) { b.classList.remove("active"); });
+    button.classList.add("active");
+    action();
+  }
+
+  const cardsButton = el("button", { textContent: "Cards (" + set.flashcards.length + ")" });
+  cardsButton.onclick = function () { select(cardsButton, showCards); };
+  nav.appendChild(cardsButton);
+  set.quizzes.forEach(function (quiz) {
+    const b = el("button", { textContent: quiz.title });
+    b.onclick = function () { select(b, function () { showQuiz(quiz); }); };
+    nav.appendChild(b);
+  });
+  select(cardsButton, showCards);
+})();
+</script>
+</body>
+</html>
+"#;
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// A single self-contained HTML page (inline CSS and JS, no network access)
+/// with flip-card study for the set's cards and a self-grading form per quiz.
+/// Review history is left out.
+pub fn set_to_html(set: &StudySet) -> io::Result<String> {
+    let mut set = set.clone();
+    set.strip_review_history();
+    // "</script" or "<!--" in the text would confuse the HTML parser; "<" only occurs
+    // inside JSON strings, where "\u003c" means the same
+    let data = serde_json::to_string(&set).map_err(io::Error::other)?.replace('<', "\\u003c");
+    let title = escape_html(set.name());
+    // split first so text in the set can't be mistaken for a placeholder
+    let (head, tail) = TEMPLATE.split_once("{{DATA}}").expect("template has a data placeholder");
+    Ok(format!("{}{}{}", head.replace("{{TITLE}}", &title), data, tail.replace("{{TITLE}}", &title)))
+}
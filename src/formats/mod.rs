@@ -0,0 +1,74 @@
+// src/formats/mod.rs
+//! Conversions between study sets and files meant for other programs or for print.
+
+mod pdf;
+mod html;
+mod outline;
+mod gift;
+mod aiken;
+mod qti;
+mod quizlet;
+
+pub use pdf::*;
+pub use html::*;
+pub use outline::*;
+pub use gift::*;
+pub use aiken::*;
+pub use qti::*;
+pub use quizlet::*;
+
+use crate::models::Quiz;
+
+/// (1-based question number, reason) for questions an exporter had to leave out.
+pub type SkippedQuestions = Vec<(usize, String)>;
+
+/// A quiz read from another program's format, with what could not be kept.
+pub struct QuizImport {
+    pub quiz: Quiz,
+    /// (1-based line or item number, reason) for questions that were left out.
+    pub skipped: Vec<(usize, String)>,
+    /// (1-based line or item number, note) for questions that were changed to fit the quiz model.
+    pub converted: Vec<(usize, String)>,
+}
+
+impl Default for QuizImport {
+    fn default() -> Self {
+        QuizImport { quiz: Quiz::new(String::new()), skipped: Vec::new(), converted: Vec::new() }
+    }
+}
+
+/// Plain-text quiz formats used by learning management systems.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QuizTextFormat {
+    Gift,
+    Aiken,
+}
+
+impl QuizTextFormat {
+    pub fn label(self) -> &'static str {
+        match self {
+            QuizTextFormat::Gift => "Moodle GIFT",
+            QuizTextFormat::Aiken => "Aiken",
+        }
+    }
+
+    /// Guess the format of a file: Aiken has `ANSWER:` lines and no `{` answer blocks.
+    pub fn detect(text: &str) -> QuizTextFormat {
+        let answer_lines = text.lines().any(|l| l.trim_start().get(..7).is_some_and(|h| h.eq_ignore_ascii_case("ANSWER:")));
+        if answer_lines && !text.contains('{') { QuizTextFormat::Aiken } else { QuizTextFormat::Gift }
+    }
+
+    pub fn parse(self, text: &str, title: &str) -> QuizImport {
+        match self {
+            QuizTextFormat::Gift => parse_gift(text, title),
+            QuizTextFormat::Aiken => parse_aiken(text, title),
+        }
+    }
+
+    pub fn write(self, quiz: &Quiz) -> (String, SkippedQuestions) {
+        match self {
+            QuizTextFormat::Gift => quiz_to_gift(quiz),
+            QuizTextFormat::Aiken => quiz_to_aiken(quiz),
+        }
+    }
+}
@@ -0,0 +1,291 @@
+// src/formats/outline.rs
+
+use serde::{Serialize, Deserialize};
+use crate::models::Flashcard;
+
+/// How cards are written in a Markdown or plain-text outline.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+pub enum OutlineStyle {
+    /// One card per line: `term :: definition`, optionally followed by `:: hint` parts.
+    #[default]
+    Separator,
+    /// `Q:` starts a question, `A:` its answer and `H:` adds a hint; lines that
+    /// follow continue the part above them.
+    QaBlocks,
+    /// Every Markdown heading is a question; the text below it is the answer.
+    Headings,
+}
+
+impl OutlineStyle {
+    pub const ALL: [OutlineStyle; 3] = [OutlineStyle::Separator, OutlineStyle::QaBlocks, OutlineStyle::Headings];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            OutlineStyle::Separator => "term :: definition",
+            OutlineStyle::QaBlocks => "Q: / A: blocks",
+            OutlineStyle::Headings => "Heading = question",
+        }
+    }
+}
+
+/// Outline style and separator; kept in the config so the importer remembers them.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(default)]
+pub struct OutlineOptions {
+    pub style: OutlineStyle,
+    /// Separator between term, definition and hints for `OutlineStyle::Separator`.
+    pub separator: String,
+}
+
+impl Default for OutlineOptions {
+    fn default() -> Self {
+        OutlineOptions { style: OutlineStyle::Separator, separator: "::".to_string() }
+    }
+}
+
+/// Cards parsed from an outline or pasted text, plus the entries that could not be used.
+#[derive(Clone, Default)]
+pub struct OutlineParse {
+    pub cards: Vec<Flashcard>,
+    /// (1-based line or card number, reason) for entries that were skipped.
+    pub skipped: Vec<(usize, String)>,
+}
+
+/// Drop a Markdown list marker ("- ", "* ", "+ ", "1. ", "1) ") from the start of a line.
+fn strip_list_marker(line: &str) -> &str {
+    let line = line.trim();
+    for marker in ["- ", "* ", "+ "] {
+        if let Some(rest) = line.strip_prefix(marker) {
+            return rest.trim_start();
+        }
+    }
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits > 0
+        && let Some(rest) = line[digits..].strip_prefix(". ").or_else(|| line[digits..].strip_prefix(") "))
+    {
+        return rest.trim_start();
+    }
+    line
+}
+
+/// Text of a Markdown heading line ("## Title" -> "Title"), if it is one.
+fn heading_text(line: &str) -> Option<&str> {
+    let trimmed = line.trim_start();
+    let level = trimmed.chars().take_while(|c| *c == '#').count();
+    if (1..=6).contains(&level) && trimmed[level..].starts_with(' ') {
+        Some(trimmed[level..].trim().trim_end_matches('#').trim_end())
+    } else {
+        None
+    }
+}
+
+/// Text after whichever of `labels` (such as "Q:", compared case-insensitively) starts the line.
+fn strip_label<'a>(line: &'a str, labels: &[&str]) -> Option<&'a str> {
+    labels.iter().find_map(|label| {
+        let head = line.get(..label.len())?;
+        head.eq_ignore_ascii_case(label).then(|| line[label.len()..].trim())
+    })
+}
+
+fn parse_separator(text: &str, separator: &str, out: &mut OutlineParse) {
+    for (i, raw) in text.lines().enumerate() {
+        let line = strip_list_marker(raw);
+        if line.is_empty() || heading_text(line).is_some() {
+            continue;
+        }
+        let mut parts = line.split(separator).map(str::trim);
+        let question = parts.next().unwrap_or_default();
+        match parts.next() {
+            Some(answer) if !question.is_empty() && !answer.is_empty() => {
+                let hints = parts.filter(|h| !h.is_empty()).map(str::to_string).collect();
+                out.cards.push(Flashcard::new(question.to_string(), answer.to_string(), hints));
+            }
+            Some(_) => out.skipped.push((i + 1, "empty term or definition".to_string())),
+            None => out.skipped.push((i + 1, format!("no '{}' separator", separator))),
+        }
+    }
+}
+
+fn parse_qa_blocks(text: &str, out: &mut OutlineParse) {
+    enum Part { Question, Answer, Hint }
+    // (line of the Q:, question, answer, hints)
+    let mut current: Option<(usize, String, String, Vec<String>)> = None;
+    let mut part = Part::Question;
+    let finish = |card: Option<(usize, String, String, Vec<String>)>, out: &mut OutlineParse| {
+        if let Some((line, q, a, hints)) = card {
+            if q.trim().is_empty() || a.trim().is_empty() {
+                out.skipped.push((line, "question without an answer".to_string()));
+            } else {
+                out.cards.push(Flashcard::new(q.trim().to_string(), a.trim().to_string(), hints));
+            }
+        }
+    };
+    for (i, raw) in text.lines().enumerate() {
+        let line = raw.trim();
+        if let Some(q) = strip_label(line, &["Q:", "Question:"]) {
+            finish(current.take(), out);
+            current = Some((i + 1, q.to_string(), String::new(), Vec::new()));
+            part = Part::Question;
+        } else if let Some(a) = strip_label(line, &["A:", "Answer:"]) {
+            match current.as_mut() {
+                Some(card) => {
+                    card.2 = a.to_string();
+                    part = Part::Answer;
+                }
+                None => out.skipped.push((i + 1, "answer without a question".to_string())),
+            }
+        } else if let Some(h) = strip_label(line, &["H:", "Hint:"]) {
+            match current.as_mut() {
+                Some(card) if !h.is_empty() => {
+                    card.3.push(h.to_string());
+                    part = Part::Hint;
+                }
+                Some(_) => {}
+                None => out.skipped.push((i + 1, "hint without a question".to_string())),
+            }
+        } else if !line.is_empty() {
+            match current.as_mut() {
+                Some(card) => {
+                    let target = match part {
+                        Part::Question => &mut card.1,
+                        Part::Answer => &mut card.2,
+                        Part::Hint => card.3.last_mut().expect("a hint was started"),
+                    };
+                    target.push('\n');
+                    target.push_str(line);
+                }
+                None => out.skipped.push((i + 1, "text before the first Q:".to_string())),
+            }
+        }
+    }
+    finish(current, out);
+}
+
+fn parse_headings(text: &str, out: &mut OutlineParse) {
+    // (line of the heading, question, body lines)
+    let mut current: Option<(usize, String, Vec<&str>)> = None;
+    let finish = |card: Option<(usize, String, Vec<&str>)>, out: &mut OutlineParse| {
+        if let Some((line, q, body)) = card {
+            let answer = body.join("\n").trim().to_string();
+            if answer.is_empty() {
+                out.skipped.push((line, "heading without text below it".to_string()));
+            } else {
+                out.cards.push(Flashcard::new(q, answer, Vec::new()));
+            }
+        }
+    };
+    for (i, line) in text.lines().enumerate() {
+        match heading_text(line) {
+            Some(q) => {
+                finish(current.take(), out);
+                current = Some((i + 1, q.to_string(), Vec::new()));
+            }
+            None => match current.as_mut() {
+                Some(card) => card.2.push(line.trim_end()),
+                None if !line.trim().is_empty() => out.skipped.push((i + 1, "text before the first heading".to_string())),
+                None => {}
+            },
+        }
+    }
+    finish(current, out);
+}
+
+/// Parse cards out of a Markdown or plain-text outline.
+pub fn parse_outline(text: &str, options: &OutlineOptions) -> OutlineParse {
+    let mut out = OutlineParse::default();
+    match options.style {
+        OutlineStyle::Separator if options.separator.trim().is_empty() => {
+            out.skipped.push((0, "the separator is empty".to_string()));
+        }
+        OutlineStyle::Separator => parse_separator(text, options.separator.trim(), &mut out),
+        OutlineStyle::QaBlocks => parse_qa_blocks(text, &mut out),
+        OutlineStyle::Headings => parse_headings(text, &mut out),
+    }
+    out
+}
+
+/// Write cards as an outline that `parse_outline` reads back with the same options.
+/// The separator style is line based, so line breaks inside a card become spaces.
+pub fn cards_to_outline(cards: &[Flashcard], options: &OutlineOptions) -> String {
+    let mut out = String::new();
+    for card in cards {
+        match options.style {
+            OutlineStyle::Separator => {
+                let sep = format!(" {} ", options.separator.trim());
+                let mut parts = vec![card.question(), card.answer()];
+                parts.extend(card.get_hints().iter().map(String::as_str));
+                let line: Vec<String> = parts.iter().map(|p| p.split_whitespace().collect::<Vec<_>>().join(" ")).collect();
+                out.push_str(&line.join(&sep));
+                out.push('\n');
+            }
+            OutlineStyle::QaBlocks => {
+                out.push_str(&format!("Q: {}\nA: {}\n", card.question(), card.answer()));
+                for hint in card.get_hints() {
+                    out.push_str(&format!("H: {}\n", hint));
+                }
+                out.push('\n');
+            }
+            OutlineStyle::Headings => {
+                out.push_str(&format!("## {}\n\n{}\n\n", card.question().replace('\n', " "), card.answer()));
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(style: OutlineStyle, separator: &str) -> OutlineOptions {
+        OutlineOptions { style, separator: separator.to_string() }
+    }
+
+    fn pairs(parse: &OutlineParse) -> Vec<(&str, &str)> {
+        parse.cards.iter().map(|c| (c.question(), c.answer())).collect()
+    }
+
+    #[test]
+    fn separator_lines_at_any_list_level() {
+        let text = "# Chapter\n\n- Cell :: unit of life\n  - Nucleus :: holds the DNA :: control centre ::\n    1. Ribosome::makes proteins\n\n## Section\n* :: orphan\n+ no separator here\n";
+        let parse = parse_outline(text, &OutlineOptions::default());
+        assert_eq!(pairs(&parse), [("Cell", "unit of life"), ("Nucleus", "holds the DNA"), ("Ribosome", "makes proteins")]);
+        assert_eq!(parse.cards[1].get_hints(), &["control centre"]);
+        assert_eq!(parse.skipped, [(8, "empty term or definition".to_string()), (9, "no '::' separator".to_string())]);
+
+        let parse = parse_outline("one = 1\n\n1) two = 2 = hint\n", &options(OutlineStyle::Separator, " = "));
+        assert_eq!(pairs(&parse), [("one", "1"), ("two", "2")]);
+        assert_eq!(parse_outline("a b", &options(OutlineStyle::Separator, "  ")).skipped, [(0, "the separator is empty".to_string())]);
+    }
+
+    #[test]
+    fn qa_blocks_continue_over_lines() {
+        let text = "stray line\nQ: Capital of France?\nA: Paris\non the Seine\nH: starts with P\n\nquestion: Second\nAnswer:\n\nq: No answer\nA: orphan? no, answers the block above\n";
+        let parse = parse_outline(text, &options(OutlineStyle::QaBlocks, "::"));
+        assert_eq!(pairs(&parse), [("Capital of France?", "Paris\non the Seine"), ("No answer", "orphan? no, answers the block above")]);
+        assert_eq!(parse.cards[0].get_hints(), &["starts with P"]);
+        assert_eq!(parse.skipped, [(1, "text before the first Q:".to_string()), (7, "question without an answer".to_string())]);
+    }
+
+    #[test]
+    fn every_heading_level_is_a_question() {
+        let text = "intro\n# Biology\n## Cells\nThe unit of life.\n\nAlso in plants.\n### Nucleus ###\nHolds the DNA.\n####### not a heading\n";
+        let parse = parse_outline(text, &options(OutlineStyle::Headings, "::"));
+        assert_eq!(pairs(&parse), [("Cells", "The unit of life.\n\nAlso in plants."), ("Nucleus", "Holds the DNA.\n####### not a heading")]);
+        assert_eq!(parse.skipped, [(1, "text before the first heading".to_string()), (2, "heading without text below it".to_string())]);
+    }
+
+    #[test]
+    fn written_outlines_read_back() {
+        let cards = vec![
+            Flashcard::new("Cell".to_string(), "unit of life".to_string(), vec!["small".to_string()]),
+            Flashcard::new("Nucleus".to_string(), "holds the DNA".to_string(), Vec::new()),
+        ];
+        for style in OutlineStyle::ALL {
+            let options = options(style, "::");
+            let parse = parse_outline(&cards_to_outline(&cards, &options), &options);
+            assert!(parse.skipped.is_empty(), "{:?}: {:?}", style, parse.skipped);
+            assert_eq!(pairs(&parse), [("Cell", "unit of life"), ("Nucleus", "holds the DNA")], "{:?}", style);
+        }
+    }
+}
@@ -0,0 +1,301 @@
+// src/formats/pdf.rs
+
+use std::io;
+use printpdf::{BuiltinFont, IndirectFontRef, Line, LineDashPattern, Mm, PdfDocument, PdfDocumentReference, PdfLayerReference, Point};
+use serde::{Serialize, Deserialize};
+use crate::models::{Quiz, QuestionType, StudySet};
+
+/// Paper sizes offered for printing.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+pub enum PaperSize {
+    #[default]
+    A4,
+    Letter,
+}
+
+impl PaperSize {
+    pub const ALL: [PaperSize; 2] = [PaperSize::A4, PaperSize::Letter];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            PaperSize::A4 => "A4",
+            PaperSize::Letter => "US Letter",
+        }
+    }
+
+    /// Portrait width and height.
+    fn size(self) -> (Mm, Mm) {
+        match self {
+            PaperSize::A4 => (Mm(210.0), Mm(297.0)),
+            PaperSize::Letter => (Mm(215.9), Mm(279.4)),
+        }
+    }
+}
+
+/// Which edge the printer turns the sheet over for the second side.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+pub enum DuplexFlip {
+    /// The usual duplex setting for portrait pages: backs are mirrored left to right.
+    #[default]
+    LongEdge,
+    /// Backs are mirrored top to bottom.
+    ShortEdge,
+}
+
+impl DuplexFlip {
+    pub const ALL: [DuplexFlip; 2] = [DuplexFlip::LongEdge, DuplexFlip::ShortEdge];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            DuplexFlip::LongEdge => "Flip on long edge",
+            DuplexFlip::ShortEdge => "Flip on short edge",
+        }
+    }
+}
+
+/// Layout of printed flashcards; kept in the config so it survives restarts.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(default)]
+pub struct CardSheetOptions {
+    pub columns: u32,
+    pub rows: u32,
+    pub paper: PaperSize,
+    pub flip: DuplexFlip,
+    /// Dashed lines between the cards on the front pages.
+    pub cut_lines: bool,
+}
+
+impl Default for CardSheetOptions {
+    fn default() -> Self {
+        CardSheetOptions { columns: 2, rows: 4, paper: PaperSize::A4, flip: DuplexFlip::LongEdge, cut_lines: true }
+    }
+}
+
+/// Upper bound for `columns` and `rows`; smaller cards can't hold readable text.
+pub const MAX_CARDS_PER_SIDE: u32 = 6;
+
+const MARGIN_MM: f32 = 10.0;
+const CARD_PADDING_MM: f32 = 4.0;
+const PT_TO_MM: f32 = 0.3528;
+/// Average Helvetica glyph width as a fraction of the font size; used to wrap text.
+const AVG_CHAR_WIDTH: f32 = 0.5;
+
+fn pdf_err(e: printpdf::Error) -> io::Error {
+    io::Error::other(e.to_string())
+}
+
+/// Split `text` into lines of at most `width` (mm) at `size` (pt). Words that
+/// don't fit on a line of their own are broken.
+fn wrap_text(text: &str, width: f32, size: f32) -> Vec<String> {
+    let max_chars = ((width / (size * PT_TO_MM * AVG_CHAR_WIDTH)) as usize).max(1);
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            let mut word: Vec<char> = word.chars().collect();
+            while word.len() > max_chars {
+                if !line.is_empty() {
+                    lines.push(std::mem::take(&mut line));
+                }
+                lines.push(word.drain(..max_chars).collect());
+            }
+            let word: String = word.into_iter().collect();
+            if line.is_empty() {
+                line = word;
+            } else if line.chars().count() + 1 + word.chars().count() <= max_chars {
+                line.push(' ');
+                line.push_str(&word);
+            } else {
+                lines.push(std::mem::replace(&mut line, word));
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+fn draw_line(layer: &PdfLayerReference, from: (f32, f32), to: (f32, f32)) {
+    layer.add_line(Line {
+        points: vec![(Point::new(Mm(from.0), Mm(from.1)), false), (Point::new(Mm(to.0), Mm(to.1)), false)],
+        is_closed: false,
+    });
+}
+
+/// Write `text` centered in the box with bottom-left corner (x, y), shrinking
+/// the font until it fits; text that still doesn't fit at 6 pt is cut off.
+fn draw_centered_text(layer: &PdfLayerReference, font: &IndirectFontRef, text: &str, x: f32, y: f32, w: f32, h: f32) {
+    let (inner_w, inner_h) = (w - 2.0 * CARD_PADDING_MM, h - 2.0 * CARD_PADDING_MM);
+    let mut size = 14.0;
+    let mut lines = wrap_text(text, inner_w, size);
+    while size > 6.0 && lines.len() as f32 * size * PT_TO_MM * 1.2 > inner_h {
+        size -= 1.0;
+        lines = wrap_text(text, inner_w, size);
+    }
+    let line_h = size * PT_TO_MM * 1.2;
+    lines.truncate(((inner_h / line_h) as usize).max(1));
+    let block_h = lines.len() as f32 * line_h;
+    let mut baseline = y + h / 2.0 + block_h / 2.0 - size * PT_TO_MM;
+    for line in &lines {
+        let line_w = line.chars().count() as f32 * size * PT_TO_MM * AVG_CHAR_WIDTH;
+        layer.use_text(line.as_str(), size, Mm(x + (w - line_w).max(0.0) / 2.0), Mm(baseline), font);
+        baseline -= line_h;
+    }
+}
+
+/// Render the cards of `set` for double-sided printing: each front page (questions)
+/// is followed by a back page (answers) laid out so every answer lands behind its
+/// question when printed duplex with `options.flip`.
+pub fn flashcards_pdf(set: &StudySet, options: &CardSheetOptions) -> io::Result<Vec<u8>> {
+    let cards = set.get_all_flashcards();
+    if cards.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("set '{}' has no cards to print", set.name())));
+    }
+    let columns = options.columns.clamp(1, MAX_CARDS_PER_SIDE) as usize;
+    let rows = options.rows.clamp(1, MAX_CARDS_PER_SIDE) as usize;
+    let (page_w, page_h) = options.paper.size();
+    // the grid is centered, so mirroring it for the backs keeps the cards aligned
+    let card_w = (page_w.0 - 2.0 * MARGIN_MM) / columns as f32;
+    let card_h = (page_h.0 - 2.0 * MARGIN_MM) / rows as f32;
+    let left = (page_w.0 - card_w * columns as f32) / 2.0;
+    let top = page_h.0 - (page_h.0 - card_h * rows as f32) / 2.0;
+
+    let (doc, first_page, first_layer) = PdfDocument::new(set.name(), page_w, page_h, "Fronts 1");
+    let font = doc.add_builtin_font(BuiltinFont::Helvetica).map_err(pdf_err)?;
+    let per_page = columns * rows;
+    for (sheet, chunk) in cards.chunks(per_page).enumerate() {
+        let front = if sheet == 0 {
+            doc.get_page(first_page).get_layer(first_layer)
+        } else {
+            let (page, layer) = doc.add_page(page_w, page_h, format!("Fronts {}", sheet + 1));
+            doc.get_page(page).get_layer(layer)
+        };
+        let (page, layer) = doc.add_page(page_w, page_h, format!("Backs {}", sheet + 1));
+        let back = doc.get_page(page).get_layer(layer);
+
+        if options.cut_lines {
+            front.set_outline_thickness(0.3);
+            front.set_line_dash_pattern(LineDashPattern { dash_1: Some(3), ..Default::default() });
+            for c in 0..=columns {
+                let x = left + c as f32 * card_w;
+                draw_line(&front, (x, 0.0), (x, page_h.0));
+            }
+            for r in 0..=rows {
+                let y = top - r as f32 * card_h;
+                draw_line(&front, (0.0, y), (page_w.0, y));
+            }
+            front.set_line_dash_pattern(LineDashPattern::default());
+        }
+        for (i, card) in chunk.iter().enumerate() {
+            let (row, col) = (i / columns, i % columns);
+            let (back_row, back_col) = match options.flip {
+                DuplexFlip::LongEdge => (row, columns - 1 - col),
+                DuplexFlip::ShortEdge => (rows - 1 - row, col),
+            };
+            let cell = |r: usize, c: usize| (left + c as f32 * card_w, top - (r + 1) as f32 * card_h);
+            let (fx, fy) = cell(row, col);
+            draw_centered_text(&front, &font, card.question(), fx, fy, card_w, card_h);
+            let (bx, by) = cell(back_row, back_col);
+            draw_centered_text(&back, &font, card.answer(), bx, by, card_w, card_h);
+        }
+    }
+    doc.save_to_bytes().map_err(pdf_err)
+}
+
+/// Flows text lines down A4 pages, starting a new page when one is full.
+struct PageWriter {
+    doc: PdfDocumentReference,
+    layer: PdfLayerReference,
+    font: IndirectFontRef,
+    bold: IndirectFontRef,
+    y: f32,
+}
+
+impl PageWriter {
+    const WIDTH: f32 = 210.0;
+    const HEIGHT: f32 = 297.0;
+    const MARGIN: f32 = 20.0;
+
+    fn new(title: &str) -> io::Result<Self> {
+        let (doc, page, layer) = PdfDocument::new(title, Mm(Self::WIDTH), Mm(Self::HEIGHT), "Page 1");
+        let font = doc.add_builtin_font(BuiltinFont::Helvetica).map_err(pdf_err)?;
+        let bold = doc.add_builtin_font(BuiltinFont::HelveticaBold).map_err(pdf_err)?;
+        let layer = doc.get_page(page).get_layer(layer);
+        Ok(PageWriter { doc, layer, font, bold, y: Self::HEIGHT - Self::MARGIN })
+    }
+
+    /// Make sure `height` mm fit on the current page.
+    fn reserve(&mut self, height: f32) {
+        if self.y - height < Self::MARGIN {
+            let (page, layer) = self.doc.add_page(Mm(Self::WIDTH), Mm(Self::HEIGHT), "Page");
+            self.layer = self.doc.get_page(page).get_layer(layer);
+            self.y = Self::HEIGHT - Self::MARGIN;
+        }
+    }
+
+    /// Write wrapped `text` indented by `indent` mm.
+    fn paragraph(&mut self, text: &str, size: f32, bold: bool, indent: f32) {
+        let line_h = size * PT_TO_MM * 1.4;
+        for line in wrap_text(text, Self::WIDTH - 2.0 * Self::MARGIN - indent, size) {
+            self.reserve(line_h);
+            self.y -= line_h;
+            let font = if bold { &self.bold } else { &self.font };
+            self.layer.use_text(line, size, Mm(Self::MARGIN + indent), Mm(self.y), font);
+        }
+    }
+
+    /// A horizontal line for a written answer.
+    fn answer_line(&mut self, indent: f32) {
+        self.reserve(10.0);
+        self.y -= 10.0;
+        self.layer.set_outline_thickness(0.4);
+        draw_line(&self.layer, (Self::MARGIN + indent, self.y), (Self::WIDTH - Self::MARGIN, self.y));
+    }
+
+    fn space(&mut self, height: f32) {
+        self.y -= height;
+    }
+
+    fn finish(self) -> io::Result<Vec<u8>> {
+        self.doc.save_to_bytes().map_err(pdf_err)
+    }
+}
+
+/// Option letter for multiple-choice option `i` ("A", "B", ...).
+fn option_letter(i: usize) -> char {
+    (b'A' + (i % 26) as u8) as char
+}
+
+/// Render a quiz as a blank exam sheet, or (with `answer_key`) as the matching
+/// answer key with the same numbering.
+pub fn quiz_pdf(quiz: &Quiz, answer_key: bool) -> io::Result<Vec<u8>> {
+    let title = if answer_key { format!("{} - Answer Key", quiz.title()) } else { quiz.title().to_string() };
+    let mut page = PageWriter::new(&title)?;
+    page.paragraph(&title, 18.0, true, 0.0);
+    page.space(4.0);
+    if !answer_key {
+        page.paragraph("Name: ________________________________    Date: ______________", 11.0, false, 0.0);
+        page.space(6.0);
+    }
+    for i in 0..quiz.question_count() {
+        let Some(q) = quiz.get_question_data(i) else { continue };
+        page.paragraph(&format!("{}. {}", i + 1, q.prompt), 12.0, true, 0.0);
+        match (&q.question_type, answer_key) {
+            (QuestionType::MultipleChoice, false) => {
+                for (j, option) in q.options.iter().enumerate() {
+                    page.paragraph(&format!("( )  {}. {}", option_letter(j), option), 11.0, false, 6.0);
+                }
+            }
+            (QuestionType::FillInTheBlank, false) => page.answer_line(6.0),
+            (QuestionType::MultipleChoice, true) => {
+                let answer = match q.options.iter().position(|o| *o == q.answer) {
+                    Some(j) => format!("{}. {}", option_letter(j), q.answer),
+                    None => q.answer.clone(),
+                };
+                page.paragraph(&answer, 11.0, false, 6.0);
+            }
+            (QuestionType::FillInTheBlank, true) => page.paragraph(&q.answer, 11.0, false, 6.0),
+        }
+        page.space(4.0);
+    }
+    page.finish()
+}
@@ -0,0 +1,397 @@
+// src/formats/qti.rs
+
+use std::io::{self, Cursor, Read, Write};
+use std::path::Path;
+use roxmltree::{Document, Node, ParsingOptions};
+use zip::write::SimpleFileOptions;
+use crate::models::{QuestionData, QuestionType, Quiz};
+use super::{QuizImport, SkippedQuestions};
+
+/// QTI content packages are plain zip files.
+pub const QTI_EXTENSION: &str = "zip";
+
+const MANIFEST: &str = "imsmanifest.xml";
+const TEST_FILE: &str = "assessment.xml";
+const QTI_NS: &str = "http://www.imsglobal.org/xsd/imsqti_v2p1";
+const MATCH_CORRECT: &str = "http://www.imsglobal.org/question/qti_v2p1/rptemplates/match_correct";
+const MAP_RESPONSE: &str = "http://www.imsglobal.org/question/qti_v2p1/rptemplates/map_response";
+
+fn invalid(msg: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.into())
+}
+
+fn zip_err(e: zip::result::ZipError) -> io::Error {
+    io::Error::other(e.to_string())
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+fn parse_xml(text: &str) -> Result<Document<'_>, String> {
+    let options = ParsingOptions { allow_dtd: true, ..Default::default() };
+    Document::parse_with_options(text.trim_start_matches('\u{feff}'), options).map_err(|e| format!("not valid XML ({})", e))
+}
+
+fn child<'a, 'i>(node: Node<'a, 'i>, name: &str) -> Option<Node<'a, 'i>> {
+    node.children().find(|n| n.has_tag_name(name))
+}
+
+/// Resolve `href` against the folder of the package file `base` ("items/a.xml" + "../b.xml" -> "b.xml").
+fn resolve(base: &str, href: &str) -> String {
+    let mut parts: Vec<&str> = base.split('/').collect();
+    parts.pop();
+    for part in href.split('/') {
+        match part {
+            "." | "" => {}
+            ".." => {
+                parts.pop();
+            }
+            part => parts.push(part),
+        }
+    }
+    parts.join("/")
+}
+
+/// Append the readable text of an item body to `out`; blanks stand in for inline
+/// interactions and a choice interaction contributes only its prompt.
+fn collect_text(node: Node, out: &mut String) {
+    for n in node.children() {
+        if n.is_text() {
+            let text = n.text().unwrap_or_default();
+            let mut last_space = out.ends_with([' ', '\n']);
+            for c in text.chars() {
+                if c.is_whitespace() {
+                    if !last_space {
+                        out.push(' ');
+                    }
+                    last_space = true;
+                } else {
+                    out.push(c);
+                    last_space = false;
+                }
+            }
+            continue;
+        }
+        match n.tag_name().name() {
+            "choiceInteraction" => {
+                if let Some(prompt) = child(n, "prompt") {
+                    out.push('\n');
+                    collect_text(prompt, out);
+                }
+            }
+            "textEntryInteraction" | "inlineChoiceInteraction" => out.push_str(" _____ "),
+            "br" => out.push('\n'),
+            "img" => out.push_str(&format!("[{}]", n.attribute("alt").unwrap_or("image"))),
+            "p" | "div" | "li" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "blockquote" | "pre" | "table" | "tr" => {
+                out.push('\n');
+                collect_text(n, out);
+                out.push('\n');
+            }
+            _ => collect_text(n, out),
+        }
+    }
+}
+
+fn tidy(text: &str) -> String {
+    text.lines()
+        .map(|l| l.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|l| !l.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn node_text(node: Node) -> String {
+    let mut out = String::new();
+    collect_text(node, &mut out);
+    tidy(&out)
+}
+
+/// Interactions this converter understands; any other `*Interaction` is reported.
+const SUPPORTED: [&str; 3] = ["choiceInteraction", "inlineChoiceInteraction", "textEntryInteraction"];
+
+/// Convert one `assessmentItem`; the error says why the item cannot be used.
+fn parse_item(xml: &str) -> Result<(QuestionData, Option<String>), String> {
+    let doc = parse_xml(xml)?;
+    let item = doc.root_element();
+    if !item.has_tag_name("assessmentItem") {
+        return Err(format!("<{}> is not an assessmentItem", item.tag_name().name()));
+    }
+    let body = child(item, "itemBody").ok_or("has no itemBody")?;
+    let interactions: Vec<Node> = body.descendants().filter(|n| n.is_element() && n.tag_name().name().ends_with("Interaction")).collect();
+    let interaction = match interactions.as_slice() {
+        [] => return Err("has no interaction".to_string()),
+        [one] => *one,
+        more => return Err(format!("has {} interactions; only single-question items are supported", more.len())),
+    };
+    let kind = interaction.tag_name().name();
+    if !SUPPORTED.contains(&kind) {
+        return Err(format!("{} is not supported", kind));
+    }
+
+    let response_id = interaction.attribute("responseIdentifier").unwrap_or("RESPONSE");
+    let declaration = item.children()
+        .find(|n| n.has_tag_name("responseDeclaration") && n.attribute("identifier") == Some(response_id))
+        .ok_or("has no response declaration")?;
+    let mut correct: Vec<String> = child(declaration, "correctResponse")
+        .map(|c| c.children().filter(|n| n.has_tag_name("value")).map(|v| v.text().unwrap_or_default().trim().to_string()).collect())
+        .unwrap_or_default();
+    if correct.is_empty() {
+        // scored by a mapping only: take the best-scoring key
+        let best = child(declaration, "mapping").and_then(|m| {
+            m.children()
+                .filter(|n| n.has_tag_name("mapEntry"))
+                .filter_map(|e| Some((e.attribute("mapKey")?, e.attribute("mappedValue")?.parse::<f64>().ok()?)))
+                .filter(|(_, score)| *score > 0.0)
+                .max_by(|a, b| a.1.total_cmp(&b.1))
+        });
+        correct.extend(best.map(|(key, _)| key.to_string()));
+    }
+    let Some(first) = correct.first().cloned() else { return Err("has no correct response".to_string()) };
+    let prompt = node_text(body);
+
+    if kind == "textEntryInteraction" {
+        // an answer box after the question is not a gap in it
+        let prompt = prompt.strip_suffix("_____").map_or(prompt.as_str(), str::trim_end).to_string();
+        let note = (correct.len() > 1).then(|| "alternative answers dropped; only the first is accepted".to_string());
+        let data = QuestionData { prompt, options: Vec::new(), answer: first, question_type: QuestionType::FillInTheBlank };
+        return Ok((data, note));
+    }
+    let choice_tag = if kind == "choiceInteraction" { "simpleChoice" } else { "inlineChoice" };
+    let choices: Vec<(&str, String)> = interaction.children()
+        .filter(|n| n.has_tag_name(choice_tag))
+        .map(|n| (n.attribute("identifier").unwrap_or_default(), node_text(n)))
+        .collect();
+    let answer = choices.iter().find(|(id, _)| *id == first).map(|(_, text)| text.clone()).ok_or("correct response is not one of the choices")?;
+    let note = (correct.len() > 1).then(|| format!("several choices are correct; only '{}' is kept", answer));
+    let options = choices.into_iter().map(|(_, text)| text).collect();
+    Ok((QuestionData { prompt, options, answer, question_type: QuestionType::MultipleChoice }, note))
+}
+
+fn read_entry(archive: &mut zip::ZipArchive<Cursor<Vec<u8>>>, name: &str) -> io::Result<String> {
+    let mut entry = archive.by_name(name).map_err(|e| invalid(format!("{}: {}", name, e)))?;
+    let mut text = String::new();
+    entry.read_to_string(&mut text)?;
+    Ok(text)
+}
+
+/// Item files of a package in test order, and the test title if there is one.
+fn package_items(archive: &mut zip::ZipArchive<Cursor<Vec<u8>>>) -> io::Result<(Vec<String>, Option<String>)> {
+    let Ok(manifest) = read_entry(archive, MANIFEST) else {
+        // no manifest: every XML file, in name order
+        let mut names: Vec<String> = archive.file_names().filter(|n| n.to_ascii_lowercase().ends_with(".xml")).map(str::to_string).collect();
+        names.sort();
+        return Ok((names, None));
+    };
+    let doc = parse_xml(&manifest).map_err(|e| invalid(format!("{} is {}", MANIFEST, e)))?;
+    let resources: Vec<(&str, &str)> = doc.descendants()
+        .filter(|n| n.has_tag_name("resource"))
+        .filter_map(|n| Some((n.attribute("type")?, n.attribute("href")?)))
+        .collect();
+    let items: Vec<String> = resources.iter().filter(|(t, _)| t.starts_with("imsqti_item")).map(|(_, h)| resolve("", h)).collect();
+    if let Some((_, test_href)) = resources.iter().find(|(t, _)| t.starts_with("imsqti_test")) {
+        let test_href = resolve("", test_href);
+        let test = read_entry(archive, &test_href)?;
+        let doc = parse_xml(&test).map_err(|e| invalid(format!("{} is {}", test_href, e)))?;
+        let title = doc.root_element().attribute("title").map(str::to_string);
+        let refs: Vec<String> = doc.descendants()
+            .filter(|n| n.has_tag_name("assessmentItemRef"))
+            .filter_map(|n| n.attribute("href"))
+            .map(|h| resolve(&test_href, h))
+            .collect();
+        if !refs.is_empty() {
+            return Ok((refs, title));
+        }
+        return Ok((items, title));
+    }
+    Ok((items, None))
+}
+
+/// Read a QTI 2.1 content package (or a single item's XML file) into a quiz.
+/// Items that cannot be converted are reported as (item number, reason).
+pub fn read_qti(src: &Path, fallback_title: &str) -> io::Result<QuizImport> {
+    let bytes = std::fs::read(src)?;
+    let mut out = QuizImport::default();
+    let mut quiz = Quiz::new(fallback_title.to_string());
+    let mut add = |n: usize, name: &str, result: Result<(QuestionData, Option<String>), String>, out: &mut QuizImport| match result {
+        Ok((q, note)) => {
+            if let Some(note) = note {
+                out.converted.push((n, format!("{}: {}", name, note)));
+            }
+            quiz.add_question(q.prompt, q.options, q.answer, q.question_type);
+        }
+        Err(reason) => out.skipped.push((n, format!("{}: {}", name, reason))),
+    };
+    if !bytes.starts_with(b"PK") {
+        let text = String::from_utf8(bytes).map_err(|_| invalid("the file is neither a zip package nor XML text"))?;
+        add(1, fallback_title, parse_item(&text), &mut out);
+    } else {
+        let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).map_err(zip_err)?;
+        let (items, title) = package_items(&mut archive)?;
+        for (i, name) in items.iter().enumerate() {
+            let result = read_entry(&mut archive, name).map_err(|_| "cannot be read from the package".to_string()).and_then(|xml| parse_item(&xml));
+            add(i + 1, name, result, &mut out);
+        }
+        if let Some(title) = title.filter(|t| !t.trim().is_empty()) {
+            quiz.set_title(title.trim().to_string());
+        }
+    }
+    out.quiz = quiz;
+    Ok(out)
+}
+
+/// `text` as XML with line breaks kept as `<br/>`.
+fn xml_lines(text: &str) -> String {
+    text.trim().lines().map(escape_xml).collect::<Vec<_>>().join("<br/>")
+}
+
+fn item_xml(identifier: &str, q: &QuestionData) -> Option<String> {
+    let title: String = q.prompt.split_whitespace().collect::<Vec<_>>().join(" ").chars().take(60).collect();
+    let answer = q.answer.trim();
+    let (declaration, body, template) = match q.question_type {
+        QuestionType::MultipleChoice => {
+            let correct = q.options.iter().position(|o| o.trim() == answer)?;
+            let declaration = format!(
+                "  <responseDeclaration identifier=\"RESPONSE\" cardinality=\"single\" baseType=\"identifier\">\n    <correctResponse><value>choice{}</value></correctResponse>\n  </responseDeclaration>\n",
+                correct + 1,
+            );
+            let mut body = format!("    <choiceInteraction responseIdentifier=\"RESPONSE\" shuffle=\"false\" maxChoices=\"1\">\n      <prompt>{}</prompt>\n", xml_lines(&q.prompt));
+            for (i, option) in q.options.iter().enumerate() {
+                body.push_str(&format!("      <simpleChoice identifier=\"choice{}\">{}</simpleChoice>\n", i + 1, xml_lines(option)));
+            }
+            body.push_str("    </choiceInteraction>\n");
+            (declaration, body, MATCH_CORRECT)
+        }
+        QuestionType::FillInTheBlank if answer.is_empty() => return None,
+        QuestionType::FillInTheBlank => {
+            // our answers ignore case, so score through a case-insensitive mapping
+            let declaration = format!(
+                "  <responseDeclaration identifier=\"RESPONSE\" cardinality=\"single\" baseType=\"string\">\n    <correctResponse><value>{0}</value></correctResponse>\n    <mapping defaultValue=\"0\"><mapEntry mapKey=\"{0}\" mappedValue=\"1\" caseSensitive=\"false\"/></mapping>\n  </responseDeclaration>\n",
+                escape_xml(answer),
+            );
+            let body = format!(
+                "    <p>{}</p>\n    <p><textEntryInteraction responseIdentifier=\"RESPONSE\" expectedLength=\"{}\"/></p>\n",
+                xml_lines(&q.prompt), answer.chars().count().max(10),
+            );
+            (declaration, body, MAP_RESPONSE)
+        }
+    };
+    Some(format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<assessmentItem xmlns=\"{}\" identifier=\"{}\" title=\"{}\" adaptive=\"false\" timeDependent=\"false\">\n{}  <outcomeDeclaration identifier=\"SCORE\" cardinality=\"single\" baseType=\"float\"><defaultValue><value>0</value></defaultValue></outcomeDeclaration>\n  <itemBody>\n{}  </itemBody>\n  <responseProcessing template=\"{}\"/>\n</assessmentItem>\n",
+        QTI_NS, identifier, escape_xml(&title), declaration, body, template,
+    ))
+}
+
+/// Write a quiz as a QTI 2.1 content package (manifest, one test, one file per item).
+/// Questions without a usable answer are left out and reported as (question number, reason).
+pub fn quiz_to_qti_package(quiz: &Quiz) -> io::Result<(Vec<u8>, SkippedQuestions)> {
+    let mut items: Vec<(String, String)> = Vec::new();
+    let mut skipped = Vec::new();
+    for i in 0..quiz.question_count() {
+        let Some(q) = quiz.get_question_data(i) else { continue };
+        let identifier = format!("item{}", i + 1);
+        match item_xml(&identifier, &q) {
+            Some(xml) => items.push((identifier, xml)),
+            None if q.question_type == QuestionType::MultipleChoice => skipped.push((i + 1, "answer is not one of the options".to_string())),
+            None => skipped.push((i + 1, "no answer set".to_string())),
+        }
+    }
+    let title = escape_xml(quiz.title());
+    let mut test = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<assessmentTest xmlns=\"{}\" identifier=\"test\" title=\"{}\">\n  <testPart identifier=\"part1\" navigationMode=\"linear\" submissionMode=\"individual\">\n    <assessmentSection identifier=\"section1\" title=\"{}\" visible=\"true\">\n",
+        QTI_NS, title, title,
+    );
+    let mut manifest = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<manifest xmlns=\"http://www.imsglobal.org/xsd/imscp_v1p1\" identifier=\"manifest\">\n  <metadata><schema>QTIv2.1 Package</schema><schemaversion>1.0.0</schemaversion></metadata>\n  <organizations/>\n  <resources>\n    <resource identifier=\"test\" type=\"imsqti_test_xmlv2p1\" href=\"{0}\">\n      <file href=\"{0}\"/>\n",
+        TEST_FILE,
+    );
+    for (identifier, _) in &items {
+        test.push_str(&format!("      <assessmentItemRef identifier=\"{0}\" href=\"{0}.xml\"/>\n", identifier));
+        manifest.push_str(&format!("      <dependency identifierref=\"{}\"/>\n", identifier));
+    }
+    test.push_str("    </assessmentSection>\n  </testPart>\n</assessmentTest>\n");
+    manifest.push_str("    </resource>\n");
+    for (identifier, _) in &items {
+        manifest.push_str(&format!("    <resource identifier=\"{0}\" type=\"imsqti_item_xmlv2p1\" href=\"{0}.xml\">\n      <file href=\"{0}.xml\"/>\n    </resource>\n", identifier));
+    }
+    manifest.push_str("  </resources>\n</manifest>\n");
+
+    let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    zip.start_file(MANIFEST, options).map_err(zip_err)?;
+    zip.write_all(manifest.as_bytes())?;
+    zip.start_file(TEST_FILE, options).map_err(zip_err)?;
+    zip.write_all(test.as_bytes())?;
+    for (identifier, xml) in &items {
+        zip.start_file(format!("{}.xml", identifier), options).map_err(zip_err)?;
+        zip.write_all(xml.as_bytes())?;
+    }
+    let bytes = zip.finish().map_err(zip_err)?.into_inner();
+    Ok((bytes, skipped))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Write `bytes` to a temporary file and import it.
+    fn import(name: &str, bytes: &[u8]) -> QuizImport {
+        let path = std::env::temp_dir().join(format!("study_helper_qti_{}_{}.{}", name, std::process::id(), QTI_EXTENSION));
+        std::fs::write(&path, bytes).unwrap();
+        let import = read_qti(&path, "fallback").unwrap();
+        std::fs::remove_file(&path).unwrap();
+        import
+    }
+
+    fn package(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, text) in files {
+            zip.start_file(*name, SimpleFileOptions::default()).unwrap();
+            zip.write_all(text.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn export_then_import_keeps_choice_and_fill_in_questions() {
+        let mut quiz = Quiz::new("Rivers & <lakes>".to_string());
+        quiz.add_question("Longest river?\nPick one".to_string(), vec!["Nile".to_string(), "Amazon".to_string(), "Danube \"blue\"".to_string()], "Amazon".to_string(), QuestionType::MultipleChoice);
+        quiz.add_question("The Thames flows through".to_string(), Vec::new(), "London".to_string(), QuestionType::FillInTheBlank);
+        quiz.add_question("No answer".to_string(), Vec::new(), String::new(), QuestionType::FillInTheBlank);
+        let (bytes, skipped) = quiz_to_qti_package(&quiz).unwrap();
+        assert_eq!(skipped, [(3, "no answer set".to_string())]);
+
+        let import = import("round_trip", &bytes);
+        assert!(import.skipped.is_empty() && import.converted.is_empty(), "{:?} {:?}", import.skipped, import.converted);
+        let mut expected = quiz.clone();
+        expected.remove_question(2);
+        assert_eq!(import.quiz, expected);
+    }
+
+    #[test]
+    fn reports_missing_and_unsupported_items() {
+        let manifest = "<manifest><resources>\
+            <resource type=\"imsqti_item_xmlv2p1\" href=\"essay.xml\"/>\
+            <resource type=\"imsqti_item_xmlv2p1\" href=\"missing.xml\"/>\
+            <resource type=\"imsqti_item_xmlv2p1\" href=\"ok.xml\"/>\
+            </resources></manifest>";
+        let essay = "<assessmentItem identifier=\"essay\"><itemBody><extendedTextInteraction responseIdentifier=\"RESPONSE\"/></itemBody></assessmentItem>";
+        let ok = "<assessmentItem identifier=\"ok\">\
+            <responseDeclaration identifier=\"RESPONSE\"><correctResponse><value>b</value></correctResponse></responseDeclaration>\
+            <itemBody><choiceInteraction responseIdentifier=\"RESPONSE\"><prompt>Pick B</prompt>\
+            <simpleChoice identifier=\"a\">A</simpleChoice><simpleChoice identifier=\"b\">B</simpleChoice>\
+            </choiceInteraction></itemBody></assessmentItem>";
+        let bytes = package(&[(MANIFEST, manifest), ("essay.xml", essay), ("ok.xml", ok)]);
+
+        let import = import("unsupported", &bytes);
+        assert_eq!(import.skipped, [
+            (1, "essay.xml: extendedTextInteraction is not supported".to_string()),
+            (2, "missing.xml: cannot be read from the package".to_string()),
+        ]);
+        assert_eq!(import.quiz.title(), "fallback");
+        assert_eq!(import.quiz.question_count(), 1);
+        let q = import.quiz.get_question_data(0).unwrap();
+        assert_eq!((q.prompt.as_str(), q.answer.as_str()), ("Pick B", "B"));
+        assert_eq!(q.options, ["A", "B"]);
+    }
+}
@@ -0,0 +1,142 @@
+// src/formats/quizlet.rs
+
+use serde::{Serialize, Deserialize};
+use crate::models::Flashcard;
+use super::OutlineParse;
+
+/// A separator choice in Quizlet's export dialog.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub enum TextSeparator {
+    Tab,
+    Comma,
+    Semicolon,
+    NewLine,
+    Custom,
+}
+
+impl TextSeparator {
+    pub const ALL: [TextSeparator; 5] = [TextSeparator::Tab, TextSeparator::Comma, TextSeparator::Semicolon, TextSeparator::NewLine, TextSeparator::Custom];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TextSeparator::Tab => "Tab",
+            TextSeparator::Comma => "Comma",
+            TextSeparator::Semicolon => "Semicolon",
+            TextSeparator::NewLine => "New line",
+            TextSeparator::Custom => "Custom",
+        }
+    }
+
+    /// The separator text; `custom` is used for `TextSeparator::Custom`.
+    fn text(self, custom: &str) -> &str {
+        match self {
+            TextSeparator::Tab => "\t",
+            TextSeparator::Comma => ",",
+            TextSeparator::Semicolon => ";",
+            TextSeparator::NewLine => "\n",
+            TextSeparator::Custom => custom,
+        }
+    }
+}
+
+/// Separators of pasted Quizlet text; kept in the config so the dialog remembers them.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(default)]
+pub struct QuizletOptions {
+    /// Between a term and its definition.
+    pub term_separator: TextSeparator,
+    pub custom_term_separator: String,
+    /// Between one card and the next.
+    pub card_separator: TextSeparator,
+    pub custom_card_separator: String,
+}
+
+impl Default for QuizletOptions {
+    // Quizlet's own defaults
+    fn default() -> Self {
+        QuizletOptions {
+            term_separator: TextSeparator::Tab,
+            custom_term_separator: " - ".to_string(),
+            card_separator: TextSeparator::NewLine,
+            custom_card_separator: "\n\n".to_string(),
+        }
+    }
+}
+
+/// Parse text copied from Quizlet's export into cards. Skipped entries are
+/// reported with their 1-based card number.
+pub fn parse_quizlet(text: &str, options: &QuizletOptions) -> OutlineParse {
+    let mut out = OutlineParse::default();
+    let text = text.replace("\r\n", "\n");
+    let term_sep = options.term_separator.text(&options.custom_term_separator);
+    let card_sep = options.card_separator.text(&options.custom_card_separator);
+    if term_sep.is_empty() || card_sep.is_empty() {
+        out.skipped.push((0, "a custom separator is empty".to_string()));
+        return out;
+    }
+    if term_sep == card_sep {
+        out.skipped.push((0, "the term and card separators are the same".to_string()));
+        return out;
+    }
+    for (i, chunk) in text.split(card_sep).enumerate() {
+        if chunk.trim().is_empty() {
+            continue;
+        }
+        match chunk.split_once(term_sep) {
+            Some((term, definition)) if !term.trim().is_empty() && !definition.trim().is_empty() => {
+                out.cards.push(Flashcard::new(term.trim().to_string(), definition.trim().to_string(), Vec::new()));
+            }
+            Some(_) => out.skipped.push((i + 1, "empty term or definition".to_string())),
+            None => out.skipped.push((i + 1, "no term separator".to_string())),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pairs(parse: &OutlineParse) -> Vec<(&str, &str)> {
+        parse.cards.iter().map(|c| (c.question(), c.answer())).collect()
+    }
+
+    #[test]
+    fn parses_quizlet_defaults_and_skips_empty_lines() {
+        let text = "cat\tgato\r\n\n  dog \t perro\nbird\t\n\t\nfish pez\n";
+        let parse = parse_quizlet(text, &QuizletOptions::default());
+        assert_eq!(pairs(&parse), [("cat", "gato"), ("dog", "perro")]);
+        assert_eq!(parse.skipped, [
+            (4, "empty term or definition".to_string()),
+            (6, "no term separator".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn parses_custom_separators() {
+        let options = QuizletOptions {
+            term_separator: TextSeparator::Custom,
+            custom_term_separator: " - ".to_string(),
+            card_separator: TextSeparator::Custom,
+            custom_card_separator: "\n\n".to_string(),
+        };
+        let text = "H2O - water\nthe most common liquid\n\n\n\nNaCl - salt - table salt\n\nCO2 -";
+        let parse = parse_quizlet(text, &options);
+        assert_eq!(pairs(&parse), [("H2O", "water\nthe most common liquid"), ("NaCl", "salt - table salt")]);
+        assert_eq!(parse.skipped, [(4, "no term separator".to_string())]);
+
+        let options = QuizletOptions { term_separator: TextSeparator::Comma, card_separator: TextSeparator::Semicolon, ..QuizletOptions::default() };
+        assert_eq!(pairs(&parse_quizlet("a,1;b, 2 ;\n;c,3", &options)), [("a", "1"), ("b", "2"), ("c", "3")]);
+    }
+
+    #[test]
+    fn rejects_empty_or_equal_separators() {
+        let empty = QuizletOptions { term_separator: TextSeparator::Custom, custom_term_separator: String::new(), ..QuizletOptions::default() };
+        let parse = parse_quizlet("a\tb", &empty);
+        assert!(parse.cards.is_empty());
+        assert_eq!(parse.skipped, [(0, "a custom separator is empty".to_string())]);
+
+        let same = QuizletOptions { term_separator: TextSeparator::Custom, custom_term_separator: "\n".to_string(), ..QuizletOptions::default() };
+        assert_eq!(parse_quizlet("a\nb", &same).skipped, [(0, "the term and card separators are the same".to_string())]);
+    }
+}
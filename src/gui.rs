@@ -1,12 +1,36 @@
 // src/gui.rs
 use eframe::{egui, App, Frame};
 use egui::{TextStyle, FontId, RichText, Vec2};
-use crate::models::{Flashcard as AppFlashcard, StudySet as AppStudySet, Quiz as AppQuiz};
-use crate::storage::{load_config, save_config, list_class_folders};
+use crate::models::{Flashcard as AppFlashcard, StudySet as AppStudySet, Quiz as AppQuiz, ReviewAlgorithm};
+use crate::storage::{CardMatch, ClassSummary, Config, SetBackup, StorageBackend, TrashEntry, TrashedItem};
+use crate::history::{EditCommand, EditHistory};
+use crate::keymap::Action;
 use rfd::FileDialog;
 
+mod trash_view;
+mod autosave;
+mod storage_sync;
+mod storage_jobs;
+mod card_search;
+mod set_bundles;
+mod library_archive;
+mod print_export;
+mod outline_import;
+mod quiz_exchange;
+mod paste_import;
+mod shortcuts;
+mod quiz_session;
+mod appearance;
+mod settings_view;
+mod profiles;
+mod learn_view;
+mod leitner;
+mod match_view;
+
 pub struct StudyHelperApp {
     current_view: AppView,
+    // last loaded/saved configuration; the storage fields below are the live values
+    config: Config,
     // in-memory study sets (not yet persisted)
     study_sets: Vec<AppStudySet>,
 
@@ -17,21 +41,98 @@ pub struct StudyHelperApp {
     // class dropdown state
     available_classes: Vec<String>,
     selected_class: Option<usize>,
+    // class manager window state
+    show_manage_classes_popup: bool,
+    class_summaries: Vec<ClassSummary>,
+    new_class_name: String,
+    // class currently being renamed inline (old name) and the edit buffer for its new name
+    class_rename_target: Option<String>,
+    class_rename_input: String,
+    // class awaiting delete confirmation and whether it should go to the trash folder
+    class_delete_target: Option<String>,
+    class_delete_to_trash: bool,
+    // class / library archive path, the import awaiting confirmation and the policy for its conflicts
+    archive_path: String,
+    archive_preview: Option<library_archive::ArchivePreview>,
+    archive_default_policy: crate::storage::ConflictPolicy,
+    // rename / duplicate / move-copy popup for the selected set
+    set_action: Option<SetAction>,
+    set_action_name: String,
+    set_action_target_class: usize,
+    // backup browser for the selected set
+    show_backups_popup: bool,
+    set_backups: Vec<SetBackup>,
+    // delete awaiting confirmation (cards, quizzes and sets go to the class trash)
+    pending_delete: Option<PendingDelete>,
+    // trash contents of the current class, refreshed when the Trash view opens
+    trash_entries: Vec<TrashEntry>,
+    // card search in the Study Sets view (runs against the saved sets of the current class)
+    card_search_query: String,
+    card_search_results: Vec<CardMatch>,
+    // outline import popup: pasted text and the cards it parses to
+    show_outline_import: bool,
+    outline_text: String,
+    outline_preview: crate::formats::OutlineParse,
+    // Quizlet paste popup: pasted text, its preview and whether it becomes a new set
+    show_paste_import: bool,
+    paste_text: String,
+    paste_preview: crate::formats::OutlineParse,
+    paste_new_set: bool,
+    paste_set_name: String,
+    // (heading, lines) listing what the last quiz import skipped or changed
+    quiz_import_report: Option<(String, Vec<String>)>,
     // cache of last-known storage settings to avoid repeated reloads on every frame
     last_storage_base_path: String,
     last_storage_class_name: String,
     // storage/state for import/export
     storage_base_path: String,
     storage_class_name: String,
+    // text of the Settings base folder field, applied once the field loses focus
+    base_folder_edit: String,
     import_file_path: String,
     export_dest_path: String,
+    /// Keep the cards' review progress in exported bundles.
+    bundle_include_reviews: bool,
     status_message: String,
+    // settings that were invalid when the config was loaded, shown in the Settings view
+    config_problems: Vec<String>,
+    // people using this installation; `config` belongs to `profiles.active`
+    profiles: crate::storage::Profiles,
+    // "New profile" popup: the name being typed and whether to share the current study sets
+    new_profile: Option<(String, bool)>,
 
     // UI state for creating a flashcard
     new_question: String,
     new_answer: String,
     new_hint_input: String,
     new_hints: Vec<String>,
+    // index (in the selected set) of the card loaded into the form by "Edit"; None when adding
+    editing_card: Option<usize>,
+    // undo/redo stacks for card and quiz edits, plus the edit-history panel toggle
+    history: EditHistory,
+    show_history_panel: bool,
+    // names of sets with in-memory changes not yet on disk, and when the last edit happened
+    dirty_sets: std::collections::HashSet<String>,
+    dirty_class: String,
+    last_edit: Option<std::time::Instant>,
+    // focus state from the previous frame (autosave runs when the window loses focus)
+    window_focused: bool,
+    // unsaved-changes dialog on close; allow_close lets the next close request through
+    show_exit_prompt: bool,
+    allow_close: bool,
+    // watch on the storage base folder; external changes are reloaded set by set
+    storage_watcher: Option<crate::storage::StorageWatcher>,
+    watcher_requested_for: String,
+    // sets changed on disk while they had unsaved edits, waiting for the user to pick a version
+    set_conflicts: Vec<storage_sync::SetConflict>,
+    // background thread doing all filesystem work; results are applied in `poll_storage_jobs`
+    storage: crate::storage::StorageWorker<storage_jobs::StorageDone>,
+    // a class list request is in flight, so the selected class can't be resolved yet
+    class_list_pending: bool,
+    // last version of each set this app wrote, so the watcher can skip our own saves
+    last_saved: std::collections::HashMap<String, AppStudySet>,
+    // close the window as soon as the queued saves are done
+    quit_when_saved: bool,
     // flashcard viewing state
     current_card_index: usize,
     card_flipped: bool,
@@ -44,6 +145,20 @@ pub struct StudyHelperApp {
     show_create_quiz_popup: bool,
     // whether the focused quiz window is open (separate distraction-free window)
     show_quiz_window: bool,
+    quiz_session: quiz_session::QuizSession,
+    // Learn round in progress (set index and session) and the typed answer
+    learn_session: Option<(usize, crate::models::LearnSession)>,
+    learn_answer: String,
+    // Match game in progress
+    match_round: Option<match_view::MatchRound>,
+    // keyboard shortcut cheat sheet, and the action waiting for a new key while remapping
+    show_shortcut_sheet: bool,
+    shortcut_capture: Option<crate::keymap::Action>,
+    // the font files last sent to egui (loaded in the background)
+    fonts_requested: (String, String),
+    // move keyboard focus to the new-card question or the card search on the next frame
+    focus_new_question: bool,
+    focus_card_search: bool,
     // counts for placeholder question creation
     new_quiz_mc_count: usize,
     new_quiz_tf_count: usize,
@@ -59,62 +174,73 @@ pub struct StudyHelperApp {
 
 enum AppView {
     Flashcards,
+    Learn,
+    Match,
     StudySets,
+    Settings,
     Quiz,
+    Trash,
+}
+
+/// An item the user asked to delete, waiting for confirmation. Indexes refer to `study_sets`.
+#[derive(Clone, Copy)]
+enum PendingDelete {
+    Card { set_idx: usize, card_idx: usize },
+    Quiz { set_idx: usize, quiz_idx: usize },
+    Set { set_idx: usize },
+}
+
+/// Which operation the set action popup is performing on the selected set.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SetAction {
+    Rename,
+    Duplicate,
+    MoveOrCopy,
 }
 
 impl Default for StudyHelperApp {
     fn default() -> Self {
-        // Start with defaults, then try to load persisted configuration and sets.
-        let mut storage_base_path = String::new();
-        let mut storage_class_name = String::new();
-        let mut available_classes: Vec<String> = Vec::new();
-        let mut selected_class: Option<usize> = None;
-        let mut study_sets: Vec<AppStudySet> = Vec::new();
-        let mut selected_set: Option<usize> = None;
-        let status_message = String::new();
-
-        if let Ok(cfg) = load_config() {
-            storage_base_path = cfg.storage_base_path.clone();
-            storage_class_name = cfg.storage_class_name.clone();
-            if !storage_base_path.trim().is_empty() {
-                if let Ok(list) = list_class_folders(std::path::Path::new(&storage_base_path)) {
-                    available_classes = list;
-                        if !available_classes.is_empty() {
-                        // try to find index for configured class name
-                        if !storage_class_name.trim().is_empty() {
-                            if let Some(pos) = available_classes.iter().position(|s| s == &storage_class_name) {
-                                selected_class = Some(pos);
-                                if let Some(class_name) = available_classes.get(pos) {
-                                    if let Ok(sets) = crate::storage::load_sets_from_class_folder(std::path::Path::new(&storage_base_path), class_name) {
-                                        study_sets = sets;
-                                        if !study_sets.is_empty() { selected_set = Some(0); }
-                                    }
-                                }
-                            } else {
-                                // fallback to first class
-                                selected_class = Some(0);
-                            }
-                        } else {
-                            selected_class = Some(0);
-                        }
-                    }
-                }
-            }
+        // Start with defaults and the persisted configuration. Classes and sets are
+        // loaded by the storage worker once the first frame runs
+        // (see `ensure_storage_consistency`).
+        let profiles = crate::storage::load_profiles();
+        let (config, config_problems, upgrade_config) = profiles::load_validated_config(&profiles.active);
+        for problem in &config_problems {
+            log::warn!("Settings: {}", problem);
         }
-        // Prepare cached copies of the storage path values before moving them into the struct
-        let last_storage_base_path = storage_base_path.clone();
-        let last_storage_class_name = storage_class_name.clone();
-
-        Self {
-            current_view: AppView::Flashcards,
-            study_sets,
-            selected_set,
+        let storage_base_path = config.storage_base_path.clone();
+        let storage_class_name = config.storage_class_name.clone();
+
+        let mut app = Self {
+            // open the settings first when some of them had to be reset
+            current_view: if config_problems.is_empty() { AppView::Flashcards } else { AppView::Settings },
+            config,
+            config_problems,
+            profiles,
+            new_profile: None,
+            study_sets: Vec::new(),
+            selected_set: None,
             new_set_name: String::new(),
             new_question: String::new(),
             new_answer: String::new(),
             new_hint_input: String::new(),
             new_hints: Vec::new(),
+            editing_card: None,
+            history: EditHistory::default(),
+            show_history_panel: false,
+            dirty_sets: std::collections::HashSet::new(),
+            dirty_class: String::new(),
+            last_edit: None,
+            window_focused: true,
+            show_exit_prompt: false,
+            allow_close: false,
+            storage_watcher: None,
+            watcher_requested_for: String::new(),
+            set_conflicts: Vec::new(),
+            storage: crate::storage::StorageWorker::new(),
+            class_list_pending: false,
+            last_saved: std::collections::HashMap::new(),
+            quit_when_saved: false,
             current_card_index: 0,
             card_flipped: false,
             show_hint: false,
@@ -122,6 +248,15 @@ impl Default for StudyHelperApp {
             new_quiz_name: String::new(),
             show_create_quiz_popup: false,
             show_quiz_window: false,
+            quiz_session: Default::default(),
+            learn_session: None,
+            learn_answer: String::new(),
+            match_round: None,
+            show_shortcut_sheet: false,
+            shortcut_capture: None,
+            fonts_requested: Default::default(),
+            focus_new_question: false,
+            focus_card_search: false,
             new_quiz_mc_count: 0,
             new_quiz_tf_count: 0,
             new_quiz_sa_count: 0,
@@ -129,63 +264,129 @@ impl Default for StudyHelperApp {
             quiz_current_question_index: 0,
             storage_base_path,
             storage_class_name,
+            base_folder_edit: String::new(),
             import_file_path: String::new(),
             export_dest_path: String::new(),
-            status_message,
+            bundle_include_reviews: false,
+            status_message: String::new(),
             show_create_set_popup: false,
-            available_classes,
-            selected_class,
-            last_storage_base_path,
-            last_storage_class_name,
+            available_classes: Vec::new(),
+            selected_class: None,
+            show_manage_classes_popup: false,
+            class_summaries: Vec::new(),
+            new_class_name: String::new(),
+            class_rename_target: None,
+            class_rename_input: String::new(),
+            class_delete_target: None,
+            class_delete_to_trash: true,
+            archive_path: String::new(),
+            archive_preview: None,
+            archive_default_policy: Default::default(),
+            show_outline_import: false,
+            outline_text: String::new(),
+            outline_preview: Default::default(),
+            show_paste_import: false,
+            paste_text: String::new(),
+            paste_preview: Default::default(),
+            paste_new_set: false,
+            paste_set_name: String::new(),
+            quiz_import_report: None,
+            set_action: None,
+            set_action_name: String::new(),
+            set_action_target_class: 0,
+            show_backups_popup: false,
+            set_backups: Vec::new(),
+            pending_delete: None,
+            trash_entries: Vec::new(),
+            card_search_query: String::new(),
+            card_search_results: Vec::new(),
+            last_storage_base_path: String::new(),
+            last_storage_class_name: String::new(),
             quiz_edit_title: String::new(),
             quiz_edit_prompt: String::new(),
             quiz_edit_answer: String::new(),
             quiz_edit_opts_joined: String::new(),
             quiz_edit_qtype: crate::models::QuestionType::FillInTheBlank,
+        };
+
+        // Drop trash entries that outlived the retention period.
+        if !app.storage_base_path.trim().is_empty() {
+            let location = app.storage_location();
+            let retention_days = app.config.trash_retention_days;
+            app.run_storage("Purging expired trash", move || {
+                let classes = location.open().and_then(|store| store.list_classes()).unwrap_or_default();
+                for class_name in classes {
+                    if let Err(e) = crate::storage::purge_expired_trash(&location.base_dir, &class_name, retention_days) {
+                        log::warn!("Failed to purge trash of class '{}': {}", class_name, e);
+                    }
+                }
+            }, |_, ()| {});
+        }
+        if upgrade_config {
+            app.persist_config();
         }
+        app
     }
 }
 
 impl App for StudyHelperApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut Frame) {
-        // Validate storage paths and in-memory indexes against the filesystem
+        // Apply finished storage jobs, then queue whatever the current paths still need
+        self.poll_storage_jobs(ctx);
         self.ensure_storage_consistency();
-        // Compute a simple scale factor based on available width so UI scales on large displays.
-        let available = ctx.available_rect().size();
-        let base_width = 900.0_f32; // tweakable "design" width
-        let mut scale = (available.x / base_width).max(0.7).min(3.0);
-        if !scale.is_finite() || scale <= 0.0 {
-            scale = 1.0;
-        }
-
-        // Update style to scale text sizes and spacing.
-        // Some egui versions expose only immutable access, so clone, modify and set back.
-        let mut style = (*ctx.style()).clone();
-        style.text_styles.insert(TextStyle::Heading, FontId::proportional(28.0 * scale));
-        style.text_styles.insert(TextStyle::Body, FontId::proportional(16.0 * scale));
-        style.text_styles.insert(TextStyle::Button, FontId::proportional(18.0 * scale));
-        style.spacing.button_padding = Vec2::new((10.0 * scale).round(), (6.0 * scale).round());
-        style.spacing.item_spacing = Vec2::new((8.0 * scale).round(), (8.0 * scale).round());
-        ctx.set_style(style);
+        self.sync_storage_watcher(ctx);
+        self.history.sync_class(&self.storage_class_name);
+        self.autosave_tick(ctx);
+        self.handle_shortcuts(ctx);
+        let scale = self.apply_appearance(ctx);
 
         egui::CentralPanel::default().show(ctx, |ui| {
             ui.vertical_centered(|ui| {
                 ui.add_space((8.0 * scale).round());
                 ui.label(RichText::new("Welcome to Study Helper!").heading());
+                self.profile_switcher(ui);
                 ui.add_space((8.0 * scale).round());
 
                 let btn_size = Vec2::new((140.0 * scale).round(), (48.0 * scale).round());
 
                 ui.horizontal(|ui| {
-                    if ui.add_sized(btn_size, egui::Button::new(RichText::new("Flashcards").size(18.0 * scale))).clicked() {
+                    if ui.add_sized(btn_size, egui::Button::new("Flashcards")).clicked() {
                         self.current_view = AppView::Flashcards;
                     }
-                    if ui.add_sized(btn_size, egui::Button::new(RichText::new("Study Sets").size(18.0 * scale))).clicked() {
+                    if ui.add_sized(btn_size, egui::Button::new("Learn")).clicked() {
+                        self.current_view = AppView::Learn;
+                    }
+                    if ui.add_sized(btn_size, egui::Button::new("Match")).clicked() {
+                        self.current_view = AppView::Match;
+                    }
+                    if ui.add_sized(btn_size, egui::Button::new("Study Sets")).clicked() {
                         self.current_view = AppView::StudySets;
                     }
-                    if ui.add_sized(btn_size, egui::Button::new(RichText::new("Quiz").size(18.0 * scale))).clicked() {
+                    if ui.add_sized(btn_size, egui::Button::new("Quiz")).clicked() {
                         self.current_view = AppView::Quiz;
                     }
+                    if ui.add_sized(btn_size, egui::Button::new("Trash")).clicked() {
+                        self.current_view = AppView::Trash;
+                        self.refresh_trash();
+                    }
+                    if ui.add_sized(btn_size, egui::Button::new("Settings")).clicked() {
+                        self.current_view = AppView::Settings;
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    let (undo_key, redo_key) = (self.config.shortcuts.binding(Action::Undo), self.config.shortcuts.binding(Action::Redo));
+                    if ui.add_enabled(self.history.can_undo(), egui::Button::new("Undo")).on_hover_text(undo_key.to_string()).clicked() {
+                        self.undo_edit();
+                    }
+                    if ui.add_enabled(self.history.can_redo(), egui::Button::new("Redo")).on_hover_text(redo_key.to_string()).clicked() {
+                        self.redo_edit();
+                    }
+                    ui.toggle_value(&mut self.show_history_panel, "History");
+                    let sheet_hint = format!("Keyboard shortcuts ({})", self.config.shortcuts.binding(Action::ShowShortcuts));
+                    ui.toggle_value(&mut self.show_shortcut_sheet, "Shortcuts").on_hover_text(sheet_hint);
+                    // storage jobs run in the background; show what is being written or read
+                    self.storage_progress(ui);
                 });
 
                 ui.separator();
@@ -193,122 +394,386 @@ impl App for StudyHelperApp {
 
                 match self.current_view {
                     AppView::Flashcards => self.flashcards_view(ui, scale),
+                    AppView::Learn => self.learn_view(ui, scale),
+                    AppView::Match => self.match_view(ui, scale),
                     AppView::StudySets => self.study_sets_view(ui, scale),
                     AppView::Quiz => self.quiz_view(ui, scale),
+                    AppView::Trash => self.trash_view(ui, scale),
+                    AppView::Settings => self.settings_view(ui, scale),
                 }
             });
         });
+
+        if self.pending_delete.is_some() {
+            self.confirm_delete_window(ctx);
+        }
+        if self.show_history_panel {
+            self.history_panel(ctx);
+        }
+        if !self.set_conflicts.is_empty() {
+            self.conflicts_window(ctx);
+        }
+        if self.quiz_import_report.is_some() {
+            self.quiz_import_report_window(ctx, scale);
+        }
+        if self.show_shortcut_sheet {
+            self.shortcut_sheet_window(ctx, scale);
+        }
+        if self.new_profile.is_some() {
+            self.new_profile_window(ctx);
+        }
+        if self.show_exit_prompt {
+            self.exit_prompt_window(ctx);
+        }
     }
 }
 
 impl StudyHelperApp {
-    /// Ensure storage paths and indexes are consistent with the filesystem state.
-    /// If the configured base folder or class folder no longer exist, this will
-    /// clear or repair in-memory lists to avoid out-of-bounds indexing and crashes.
-    fn ensure_storage_consistency(&mut self) {
-        // If no base path configured, nothing to do.
-        if self.storage_base_path.trim().is_empty() {
-            self.available_classes.clear();
-            self.selected_class = None;
-            self.study_sets.clear();
-            self.selected_set = None;
-            return;
+    /// Current configuration with the live storage selection applied.
+    fn storage_config(&self) -> Config {
+        Config {
+            storage_base_path: self.storage_base_path.clone(),
+            storage_class_name: self.storage_class_name.clone(),
+            ..self.config.clone()
         }
+    }
 
-        let base = std::path::Path::new(&self.storage_base_path);
-        if !base.exists() {
-            // Base folder disappeared since last configured
-            self.available_classes.clear();
-            self.selected_class = None;
-            self.study_sets.clear();
-            self.selected_set = None;
-            self.status_message = "Configured base folder no longer exists".to_string();
-            return;
-        }
+    fn storage_configured(&self) -> bool {
+        !self.storage_base_path.trim().is_empty() && !self.storage_class_name.trim().is_empty()
+    }
 
-        // Only refresh the list of class folders when the base path actually changes.
-        if self.storage_base_path != self.last_storage_base_path {
-            match crate::storage::list_class_folders(base) {
-                Ok(list) => {
-                    self.available_classes = list;
-                    // update cache
-                    self.last_storage_base_path = self.storage_base_path.clone();
+    fn undo_edit(&mut self) {
+        let mut trash_slot = None;
+        let result = match self.history.undo(&mut self.study_sets) {
+            Ok(Some(command)) => {
+                if let EditCommand::DeleteCard { trash, .. } | EditCommand::DeleteQuiz { trash, .. } = command {
+                    trash_slot = Some(trash.clone());
                 }
-                Err(e) => {
-                    self.available_classes.clear();
-                    self.selected_class = None;
-                    self.study_sets.clear();
-                    self.selected_set = None;
-                    self.status_message = format!("Error listing classes: {}", e);
-                    return;
+                let edited = self.editing_card.is_some_and(|ci| command.affects_card(ci));
+                Ok((command.set_name().to_string(), format!("Undid: {}", command.description()), edited))
+            }
+            Ok(None) => return,
+            Err(e) => Err(e),
+        };
+        // an undone delete no longer belongs in the trash; the slot is read on the worker,
+        // after the job that filled it
+        if let Some(slot) = trash_slot {
+            self.run_storage("Updating trash", move || {
+                let path = slot.lock().ok()?.take()?;
+                std::fs::remove_file(&path).err().map(|e| (path, e))
+            }, |_, failed| {
+                if let Some((path, e)) = failed {
+                    log::warn!("Failed to remove trash entry {}: {}", path.display(), e);
                 }
+            });
+        }
+        self.finish_history_step(result);
+    }
+
+    fn redo_edit(&mut self) {
+        let mut trash_job = None;
+        let result = match self.history.redo(&mut self.study_sets) {
+            Ok(Some(command)) => {
+                // a redone delete goes back into the trash
+                let set_name = command.set_name().to_string();
+                match command {
+                    EditCommand::DeleteCard { card, trash, .. } => {
+                        trash_job = Some((set_name, TrashedItem::Flashcard(card.clone()), trash.clone()));
+                    }
+                    EditCommand::DeleteQuiz { quiz, trash, .. } => {
+                        trash_job = Some((set_name, TrashedItem::Quiz(quiz.clone()), trash.clone()));
+                    }
+                    _ => {}
+                }
+                let edited = self.editing_card.is_some_and(|ci| command.affects_card(ci));
+                Ok((command.set_name().to_string(), format!("Redid: {}", command.description()), edited))
             }
+            Ok(None) => return,
+            Err(e) => Err(e),
+        };
+        if let Some((set_name, item, slot)) = trash_job {
+            self.queue_move_to_trash(set_name, item, slot);
         }
+        self.finish_history_step(result);
+    }
 
-        if self.available_classes.is_empty() {
-            self.selected_class = None;
-            self.study_sets.clear();
-            self.selected_set = None;
+    /// Write a deleted card or quiz to the class trash in the background and remember
+    /// the entry in `slot` so undo can take it out again.
+    fn queue_move_to_trash(&mut self, set_name: String, item: TrashedItem, slot: crate::history::TrashSlot) {
+        if !self.storage_configured() {
             return;
         }
+        let base = std::path::PathBuf::from(&self.storage_base_path);
+        let class_name = self.storage_class_name.clone();
+        self.run_storage("Moving to trash", move || {
+            let result = crate::storage::move_to_trash(&base, &class_name, &set_name, item);
+            if let Ok(path) = &result
+                && let Ok(mut slot) = slot.lock()
+            {
+                *slot = Some(path.clone());
+            }
+            result
+        }, |app, result| {
+            if let Err(e) = result {
+                log::warn!("Failed to move item to trash: {}", e);
+                app.status_message = format!("Failed to move item to trash: {}", e);
+            }
+        });
+    }
 
-        // Try to resolve the configured class name to an index if possible.
-        if !self.storage_class_name.trim().is_empty() {
-            if let Some(pos) = self.available_classes.iter().position(|s| s == &self.storage_class_name) {
-                self.selected_class = Some(pos);
-            } else {
-                // Configured class no longer present; choose first class and clear sets.
-                self.selected_class = Some(0);
-                self.storage_class_name = self.available_classes[0].clone();
-                self.study_sets.clear();
-                self.selected_set = None;
-                self.status_message = "Configured class not found; switched to first available class".to_string();
+    /// Persist the set touched by an undo/redo step and clamp selection indexes.
+    /// `edited` says whether the step changed or moved the card open in the edit form.
+    fn finish_history_step(&mut self, result: Result<(String, String, bool), String>) {
+        match result {
+            Ok((set_name, message, edited)) => {
+                self.status_message = message;
+                if let Some(idx) = self.study_sets.iter().position(|s| s.name() == set_name) {
+                    // the form still holds the old text, which "Update Flashcard" would write back
+                    if edited && self.selected_set == Some(idx) {
+                        self.close_card_edit();
+                    }
+                    self.persist_set(idx);
+                }
             }
-        } else if let Some(idx) = self.selected_class {
-            // ensure index is still within range
-            if idx >= self.available_classes.len() {
-                self.selected_class = Some(0);
+            Err(e) => self.status_message = e,
+        }
+        if let Some(set) = self.selected_set.and_then(|i| self.study_sets.get(i)) {
+            let cards = set.get_all_flashcards().len();
+            if self.current_card_index >= cards {
+                self.current_card_index = cards.saturating_sub(1);
+            }
+            if self.editing_card.is_some_and(|i| i >= cards) {
+                self.editing_card = None;
+            }
+            let quizzes = set.get_all_quizzes().len();
+            if self.selected_quiz.is_some_and(|q| q >= quizzes) {
+                self.selected_quiz = if quizzes == 0 { None } else { Some(quizzes - 1) };
             }
-        } else {
-            // No selected class recorded; pick first available
-            self.selected_class = Some(0);
         }
+        self.populate_quiz_edit_buffers();
+    }
 
-        // If a class is selected, try loading sets (but handle errors gracefully)
-        // Only load sets from disk when the selected class changes or when the configured
-        // class name has changed since the last successful load. This avoids reloading
-        // on every UI frame (which was generating repeated log messages).
-        if let Some(idx) = self.selected_class {
-            if idx < self.available_classes.len() {
-                let class_name = &self.available_classes[idx];
-                if self.storage_class_name != self.last_storage_class_name || self.study_sets.is_empty() {
-                    match crate::storage::load_sets_from_class_folder(base, class_name) {
-                        Ok(sets) => {
-                            self.study_sets = sets;
-                            // update cache so we don't reload repeatedly
-                            self.last_storage_class_name = self.storage_class_name.clone();
-                            if self.study_sets.is_empty() {
-                                self.selected_set = None;
-                            } else {
-                                // clamp selected set index
-                                if let Some(sidx) = self.selected_set {
-                                    if sidx >= self.study_sets.len() { self.selected_set = Some(0); }
-                                } else {
-                                    self.selected_set = Some(0);
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            self.study_sets.clear();
-                            self.selected_set = None;
-                            self.status_message = format!("Error loading sets: {}", e);
-                        }
+    /// Leave the card edit form and clear its fields.
+    fn close_card_edit(&mut self) {
+        self.editing_card = None;
+        self.new_question.clear();
+        self.new_answer.clear();
+        self.new_hints.clear();
+        self.new_hint_input.clear();
+    }
+
+    /// Record a quiz change as an undoable step if the quiz actually changed.
+    /// The set stays dirty until it is saved.
+    fn record_quiz_edit(&mut self, set_idx: usize, quiz_idx: usize, before: AppQuiz, label: &str) {
+        let Some(set) = self.study_sets.get(set_idx) else { return };
+        if let Some(after) = set.get_all_quizzes().get(quiz_idx)
+            && *after != before
+        {
+            self.history.record(EditCommand::EditQuiz {
+                set: set.name().to_string(),
+                index: quiz_idx,
+                before,
+                after: after.clone(),
+                label: label.to_string(),
+            });
+            self.mark_dirty(set_idx);
+        }
+    }
+
+    /// Floating panel listing undoable and redoable edits.
+    fn history_panel(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_history_panel;
+        let mut undo_steps = 0;
+        let mut redo_steps = 0;
+        egui::Window::new("Edit History").open(&mut open).default_width(320.0).show(ctx, |ui_win| {
+            if !self.history.can_undo() && !self.history.can_redo() {
+                ui_win.label(RichText::new("(no edits yet)").italics());
+            }
+            egui::ScrollArea::vertical().max_height(300.0).show(ui_win, |ui_s| {
+                // redo entries are shown greyed above the current state, newest at the top
+                let redo: Vec<String> = self.history.redo_entries().map(|c| c.description()).collect();
+                for (i, d) in redo.iter().enumerate().rev() {
+                    if ui_s.selectable_label(false, RichText::new(d).weak()).on_hover_text("Click to redo up to here").clicked() {
+                        redo_steps = i + 1;
+                    }
+                }
+                ui_s.label(RichText::new("▶ current state").strong());
+                for (i, c) in self.history.undo_entries().enumerate() {
+                    if ui_s.selectable_label(false, c.description()).on_hover_text("Click to undo back to before this edit").clicked() {
+                        undo_steps = i + 1;
+                    }
+                }
+            });
+        });
+        self.show_history_panel = open;
+        for _ in 0..undo_steps {
+            self.undo_edit();
+        }
+        for _ in 0..redo_steps {
+            self.redo_edit();
+        }
+    }
+
+    /// Confirmation dialog for deleting a card, quiz or set.
+    fn confirm_delete_window(&mut self, ctx: &egui::Context) {
+        let Some(pending) = self.pending_delete else { return };
+        let description = match pending {
+            PendingDelete::Card { set_idx, card_idx } => self.study_sets.get(set_idx)
+                .and_then(|s| s.get_all_flashcards().get(card_idx))
+                .map(|c| format!("flashcard '{}'", c.question())),
+            PendingDelete::Quiz { set_idx, quiz_idx } => self.study_sets.get(set_idx)
+                .and_then(|s| s.get_all_quizzes().get(quiz_idx))
+                .map(|q| format!("quiz '{}'", q.title())),
+            PendingDelete::Set { set_idx } => self.study_sets.get(set_idx)
+                .map(|s| format!("set '{}' ({} cards)", s.name(), s.get_all_flashcards().len())),
+        };
+        let Some(description) = description else {
+            // selection changed underneath us
+            self.pending_delete = None;
+            return;
+        };
+        let mut confirmed = false;
+        let mut cancelled = false;
+        egui::Window::new("Confirm Delete").collapsible(false).resizable(false).order(egui::Order::Foreground).show(ctx, |ui_win| {
+            ui_win.label(format!("Delete {}?", description));
+            if self.storage_configured() {
+                ui_win.label(RichText::new("It will be moved to the class trash and can be restored from the Trash view.").small());
+            } else {
+                ui_win.label(RichText::new("Storage is not configured, so this cannot be undone.").small().color(ui_win.visuals().error_fg_color));
+            }
+            ui_win.horizontal(|ui_h| {
+                if ui_h.button("Delete").clicked() {
+                    confirmed = true;
+                }
+                if ui_h.button("Cancel").clicked() {
+                    cancelled = true;
+                }
+            });
+        });
+        if confirmed {
+            self.pending_delete = None;
+            self.perform_delete(pending);
+        } else if cancelled {
+            self.pending_delete = None;
+        }
+    }
+
+    /// Remove a confirmed item from memory, move it to the class trash and persist the set.
+    fn perform_delete(&mut self, pending: PendingDelete) {
+        match pending {
+            PendingDelete::Card { set_idx, card_idx } => {
+                let Some(removed) = self.study_sets.get_mut(set_idx).and_then(|s| s.remove_flashcard(card_idx)) else { return };
+                // clamp current card index for the currently selected set
+                if Some(set_idx) == self.selected_set {
+                    let remaining = self.study_sets[set_idx].get_all_flashcards().len();
+                    if remaining == 0 {
+                        self.current_card_index = 0;
+                    } else if self.current_card_index >= remaining {
+                        self.current_card_index = remaining - 1;
                     }
                 }
+                let set_name = self.study_sets[set_idx].name().to_string();
+                log::info!("Removed flashcard '{}' from set '{}'", removed.question(), set_name);
+                self.status_message = format!("Removed flashcard: '{}'", removed.question());
+                let trash = crate::history::TrashSlot::default();
+                if self.storage_configured() {
+                    self.queue_move_to_trash(set_name.clone(), TrashedItem::Flashcard(removed.clone()), trash.clone());
+                    self.persist_set(set_idx);
+                    self.status_message = format!("Moved flashcard '{}' to trash", removed.question());
+                } else {
+                    self.mark_dirty(set_idx);
+                }
+                self.history.record(EditCommand::DeleteCard { set: set_name, index: card_idx, card: removed, trash });
+            }
+            PendingDelete::Quiz { set_idx, quiz_idx } => {
+                let Some(removed) = self.study_sets.get_mut(set_idx).and_then(|s| s.remove_quiz(quiz_idx)) else { return };
+                let set_name = self.study_sets[set_idx].name().to_string();
+                self.status_message = format!("Deleted quiz '{}'", removed.title());
+                let trash = crate::history::TrashSlot::default();
+                if self.storage_configured() {
+                    self.queue_move_to_trash(set_name.clone(), TrashedItem::Quiz(removed.clone()), trash.clone());
+                    self.persist_set(set_idx);
+                    self.status_message = format!("Moved quiz '{}' to trash", removed.title());
+                } else {
+                    self.mark_dirty(set_idx);
+                }
+                self.history.record(EditCommand::DeleteQuiz { set: set_name, index: quiz_idx, quiz: removed, trash });
+                // adjust selection: if no remaining quizzes, clear selection
+                let remaining = self.study_sets[set_idx].get_all_quizzes().len();
+                if remaining == 0 {
+                    self.selected_quiz = None;
+                    self.quiz_current_question_index = 0;
+                } else if quiz_idx >= remaining {
+                    self.selected_quiz = Some(0);
+                    self.quiz_current_question_index = 0;
+                }
+                self.populate_quiz_edit_buffers();
+            }
+            PendingDelete::Set { set_idx } => {
+                if set_idx >= self.study_sets.len() { return; }
+                let removed = self.study_sets.remove(set_idx);
+                let set_name = removed.name().to_string();
+                self.dirty_sets.remove(&set_name);
+                self.status_message = format!("Deleted set '{}'", set_name);
+                if self.storage_configured() {
+                    let location = self.storage_location();
+                    let class_name = self.storage_class_name.clone();
+                    self.run_storage(format!("Deleting '{}'", set_name), move || {
+                        let result = crate::storage::move_to_trash(&location.base_dir, &class_name, &set_name, TrashedItem::Set(removed))
+                            .and_then(|_| location.open()?.delete_set(&class_name, &set_name));
+                        (set_name, result)
+                    }, |app, (set_name, result)| match result {
+                        Ok(()) => app.status_message = format!("Moved set '{}' to trash", set_name),
+                        Err(e) => app.status_message = format!("Delete set error: {}", e),
+                    });
+                }
+                self.selected_set = if self.study_sets.is_empty() { None } else { Some(set_idx.min(self.study_sets.len() - 1)) };
+                self.selected_quiz = None;
+                self.current_card_index = 0;
+                self.card_flipped = false;
+                self.show_hint = false;
             }
         }
     }
 
+    /// Keep the class list and loaded sets in step with the configured storage paths.
+    /// This runs every frame, so it only compares strings and queues jobs for the
+    /// storage worker; the results are applied by `apply_class_list` and
+    /// `apply_loaded_class`. External changes arrive through the storage watcher.
+    fn ensure_storage_consistency(&mut self) {
+        // If no base path configured, nothing to do.
+        if self.storage_base_path.trim().is_empty() {
+            if !self.last_storage_base_path.is_empty() {
+                self.last_storage_base_path.clear();
+                self.last_storage_class_name.clear();
+                self.available_classes.clear();
+                self.selected_class = None;
+                self.study_sets.clear();
+                self.selected_set = None;
+            }
+            return;
+        }
+
+        // New base folder (or first frame): list its classes before loading any sets.
+        if self.storage_base_path != self.last_storage_base_path {
+            self.last_storage_base_path = self.storage_base_path.clone();
+            self.last_storage_class_name.clear();
+            self.request_class_list();
+            return;
+        }
+        if self.class_list_pending {
+            return;
+        }
+
+        // Class changed: queue saves of pending edits, then the load of the new class.
+        if self.storage_class_name != self.last_storage_class_name && self.available_classes.contains(&self.storage_class_name) {
+            self.last_storage_class_name = self.storage_class_name.clone();
+            self.selected_class = self.available_classes.iter().position(|c| c == &self.storage_class_name);
+            self.flush_dirty_sets_before_reload();
+            self.load_current_class();
+        }
+    }
+
     /// Populate transient edit buffers from the currently selected quiz/question.
     fn populate_quiz_edit_buffers(&mut self) {
         if let Some(set_idx) = self.selected_set {
@@ -355,7 +820,7 @@ impl StudyHelperApp {
                 if self.study_sets.is_empty() {
                 ui.label(RichText::new("(no sets yet)").italics());
             } else {
-                let labels: Vec<String> = self.study_sets.iter().map(|s| s.name().to_string()).collect();
+                let labels: Vec<String> = (0..self.study_sets.len()).map(|i| self.set_label(i)).collect();
                 let mut selected = self.selected_set.unwrap_or(0);
                 // clamp selected index to valid range
                 if !labels.is_empty() {
@@ -404,7 +869,7 @@ impl StudyHelperApp {
                 let card_h = ((80.0 * scale).max(60.0)).round();
                 let (card_rect, _resp) = ui.allocate_exact_size(Vec2::new(card_w.round(), card_h.round()), egui::Sense::hover());
                 // draw a subtle border around the card using four line segments
-                let stroke = egui::Stroke::new(1.0, egui::Color32::from_gray(100));
+                let stroke = ui.visuals().widgets.noninteractive.bg_stroke;
                 let tl = card_rect.left_top();
                 let tr = card_rect.right_top();
                 let bl = card_rect.left_bottom();
@@ -416,34 +881,44 @@ impl StudyHelperApp {
 
                 // Center the question/answer text inside the rect using the painter to avoid child UI alignment issues
                 let text = if self.card_flipped { card.answer() } else { card.question() };
-                let font_id = FontId::proportional((24.0 * scale).round());
+                let font_id = FontId::proportional((TextStyle::Body.resolve(ui.style()).size * 1.5).round());
                 ui.painter().text(card_rect.center(), egui::Align2::CENTER_CENTER, text, font_id, ui.visuals().text_color());
 
-                // Buttons outside the card (below it)
+                // Buttons outside the card (below it); they run the same actions as the shortcuts
+                let keys = &self.config.shortcuts;
+                let mut action: Option<Action> = None;
                 ui.add_space((6.0 * scale).round());
                 ui.horizontal(|ui_h| {
-                    if ui_h.button("Prev").clicked() {
-                        if self.current_card_index == 0 {
-                            self.current_card_index = cards.len() - 1;
-                        } else {
-                            self.current_card_index -= 1;
-                        }
-                        self.card_flipped = false;
-                        self.show_hint = false;
+                    if ui_h.button("Prev").on_hover_text(keys.binding(Action::PreviousCard).to_string()).clicked() {
+                        action = Some(Action::PreviousCard);
                     }
-                    if ui_h.button(if self.card_flipped { "Show Question" } else { "Flip" }).clicked() {
-                        self.card_flipped = !self.card_flipped;
+                    if ui_h.button(if self.card_flipped { "Show Question" } else { "Flip" }).on_hover_text(keys.binding(Action::Flip).to_string()).clicked() {
+                        action = Some(Action::Flip);
                     }
-                    if ui_h.button("Next").clicked() {
-                        self.current_card_index = (self.current_card_index + 1) % cards.len();
-                        self.card_flipped = false;
-                        self.show_hint = false;
+                    if ui_h.button("Next").on_hover_text(keys.binding(Action::NextCard).to_string()).clicked() {
+                        action = Some(Action::NextCard);
                     }
                 });
+                // once the answer is showing, grade how well it was remembered
+                if self.card_flipped {
+                    // the Leitner system only knows right and wrong
+                    let grades: &[(Action, &str)] = match self.config.study.algorithm {
+                        ReviewAlgorithm::Sm2 => &[(Action::GradeAgain, "Again"), (Action::GradeHard, "Hard"), (Action::GradeGood, "Good"), (Action::GradeEasy, "Easy")],
+                        ReviewAlgorithm::Leitner => &[(Action::GradeAgain, "Missed it"), (Action::GradeGood, "Got it")],
+                    };
+                    ui.horizontal(|ui_h| {
+                        ui_h.label("How well did you know it?");
+                        for &(grade, label) in grades {
+                            if ui_h.button(label).on_hover_text(keys.binding(grade).to_string()).clicked() {
+                                action = Some(grade);
+                            }
+                        }
+                    });
+                }
 
                 ui.add_space((6.0 * scale).round());
-                if ui.button(if self.show_hint { "Hide Hint" } else { "Show Hint" }).clicked() {
-                    self.show_hint = !self.show_hint;
+                if ui.button(if self.show_hint { "Hide Hint" } else { "Show Hint" }).on_hover_text(keys.binding(Action::ToggleHint).to_string()).clicked() {
+                    action = Some(Action::ToggleHint);
                 }
                 if self.show_hint {
                     ui.collapsing("Hints", |ui| {
@@ -456,6 +931,13 @@ impl StudyHelperApp {
                         }
                     });
                 }
+                if matches!(self.config.study.algorithm, ReviewAlgorithm::Leitner) {
+                    ui.add_space((6.0 * scale).round());
+                    self.leitner_overview(ui, idx, scale);
+                }
+                if let Some(action) = action {
+                    self.run_action(action);
+                }
             }
         } else {
             ui.label("Select a study set in the Study Sets view or create one there.");
@@ -502,35 +984,25 @@ impl StudyHelperApp {
                 // Put the Refresh button below the ComboBox to reduce horizontal clutter.
                 ui_left.add_space((4.0 * scale).round());
                 if ui_left.small_button("Refresh classes").clicked() {
-                    if !self.storage_base_path.trim().is_empty() {
-                        if let Ok(list) = crate::storage::list_class_folders(std::path::Path::new(&self.storage_base_path)) {
-                            self.available_classes = list;
-                            if !self.available_classes.is_empty() {
-                                self.selected_class = Some(0);
-                            }
-                        }
-                        // re-check consistency after refreshing
-                        self.ensure_storage_consistency();
-                    }
+                    self.request_class_list();
                 }
+                if ui_left.small_button("Manage classes").clicked() {
+                    self.show_manage_classes_popup = true;
+                    self.new_class_name.clear();
+                    self.class_rename_target = None;
+                    self.refresh_class_summaries();
+                }
+                self.card_search_ui(ui_left, scale);
 
                 // apply selection if changed
                 if self.selected_class != Some(sel_class) {
                     self.selected_class = Some(sel_class);
-                    // load sets for this class
+                    // the sets are loaded by ensure_storage_consistency on the next frame
                     if !self.storage_base_path.trim().is_empty() {
                         if !self.available_classes.is_empty() && sel_class < self.available_classes.len() {
-                            if let Ok(sets) = crate::storage::load_sets_from_class_folder(std::path::Path::new(&self.storage_base_path), &self.available_classes[sel_class]) {
-                                self.study_sets = sets;
-                                if self.study_sets.is_empty() {
-                                    self.selected_set = None;
-                                } else {
-                                    self.selected_set = Some(0);
-                                }
-                            }
-                            // persist the chosen class name
                             self.storage_class_name = self.available_classes[sel_class].clone();
-                            let _ = save_config(&crate::storage::Config { storage_base_path: self.storage_base_path.clone(), storage_class_name: self.storage_class_name.clone() });
+                            self.selected_set = None;
+                            self.persist_config();
                         } else {
                             // no available classes: clear sets and selection
                             self.study_sets.clear();
@@ -544,7 +1016,7 @@ impl StudyHelperApp {
                 if self.study_sets.is_empty() {
                     ui_left.label(RichText::new("(no sets yet)").italics());
                 } else {
-                    let labels: Vec<String> = self.study_sets.iter().map(|s| s.name().to_string()).collect();
+                    let labels: Vec<String> = (0..self.study_sets.len()).map(|i| self.set_label(i)).collect();
                     let mut selected = self.selected_set.unwrap_or(0);
                     ui_left.horizontal(|ui_h| {
                         egui::ComboBox::from_id_salt("study_set_select")
@@ -569,6 +1041,33 @@ impl StudyHelperApp {
                         }
                     }); // end study_set_select horizontal
 
+                    ui_left.horizontal(|ui_h| {
+                        if let Some(idx) = self.selected_set.filter(|i| *i < self.study_sets.len()) {
+                            let name = self.study_sets[idx].name().to_string();
+                            if ui_h.small_button("Rename").clicked() {
+                                self.set_action = Some(SetAction::Rename);
+                                self.set_action_name = name.clone();
+                            }
+                            if ui_h.small_button("Duplicate").clicked() {
+                                self.set_action = Some(SetAction::Duplicate);
+                                self.set_action_name = crate::storage::unique_set_name(&name, |n| self.set_name_taken(n, None));
+                            }
+                            if ui_h.small_button("Move/Copy").clicked() {
+                                self.set_action = Some(SetAction::MoveOrCopy);
+                                self.set_action_name = name;
+                                self.set_action_target_class = 0;
+                            }
+                            // rotating backups are a feature of the JSON folder layout
+                            if self.config.storage_backend == StorageBackend::Json && ui_h.small_button("Backups").clicked() {
+                                self.show_backups_popup = true;
+                                self.refresh_set_backups();
+                            }
+                            if ui_h.small_button("Delete").clicked() {
+                                self.pending_delete = Some(PendingDelete::Set { set_idx: idx });
+                            }
+                        }
+                    });
+
                     // Flashcard selector for the selected set
                             if let Some(idx) = self.selected_set {
                         if idx < self.study_sets.len() {
@@ -602,8 +1101,8 @@ impl StudyHelperApp {
                                             self.new_question = c.question().to_string();
                                             self.new_answer = c.answer().to_string();
                                             self.new_hints = c.get_hints().clone();
-                                            // switch focus to right side by ensuring selection remains
-                                            // (no explicit focus API here)
+                                            // the form on the right now updates this card instead of adding one
+                                            self.editing_card = Some(self.current_card_index);
                                         }
                                     }
 
@@ -624,34 +1123,9 @@ impl StudyHelperApp {
             });
 
 
-        // Perform deferred removal (if any). Do this after UI code to avoid borrow conflicts.
+        // Ask for confirmation of a deferred removal (if any). Do this after UI code to avoid borrow conflicts.
         if let Some((set_idx, card_idx)) = to_remove_card {
-            if set_idx < self.study_sets.len() {
-                // remove the card and update indices/state
-                if let Some(removed) = self.study_sets[set_idx].remove_flashcard(card_idx) {
-                    // clamp current card index for the currently selected set
-                    if Some(set_idx) == self.selected_set {
-                        let remaining = self.study_sets[set_idx].get_all_flashcards().len();
-                        if remaining == 0 {
-                            self.current_card_index = 0;
-                        } else if self.current_card_index >= remaining {
-                            self.current_card_index = remaining - 1;
-                        }
-                    }
-
-                    self.status_message = format!("Removed flashcard: '{}'", removed.question());
-                    log::info!("Removed flashcard '{}' from set '{}'", removed.question(), self.study_sets[set_idx].name());
-
-                    // Persist immediately if storage is configured so the change is not lost
-                    if !self.storage_base_path.trim().is_empty() && !self.storage_class_name.trim().is_empty() {
-                        let base = std::path::Path::new(&self.storage_base_path);
-                        match crate::storage::save_set_into_class_folder(base, &self.storage_class_name, self.study_sets[set_idx].name(), &self.study_sets[set_idx]) {
-                            Ok(p) => self.status_message = format!("Removed and saved: {}", p.display()),
-                            Err(e) => self.status_message = format!("Removed but save failed: {}", e),
-                        }
-                    }
-                }
-            }
+            self.pending_delete = Some(PendingDelete::Card { set_idx, card_idx });
         }
             // vertical separator between left and right panes
             ui.add(egui::Separator::default().vertical());
@@ -671,15 +1145,48 @@ impl StudyHelperApp {
                 if let Some(idx) = self.selected_set {
                     if idx < self.study_sets.len() {
                         let set = &mut self.study_sets[idx];
+                        let set_name = set.name().to_string();
+                        // edits below that were not written to disk mark the set dirty afterwards
+                        let mut unsaved_edit = false;
+                        let mut save_requested = false;
+                        // drop a stale edit target (e.g. after an undo removed the card)
+                        if self.editing_card.is_some_and(|i| i >= set.get_all_flashcards().len()) {
+                            self.editing_card = None;
+                        }
 
                         ui_right.label(RichText::new(format!("Set: {} ({} cards)", set.name(), set.get_all_flashcards().len())).heading());
                         ui_right.add_space((6.0 * scale).round());
 
-                        ui_right.label(RichText::new("Add / Edit flashcard").heading());
+                        ui_right.horizontal(|ui_h| {
+                            match self.editing_card {
+                                Some(ci) => {
+                                    ui_h.label(RichText::new(format!("Edit flashcard #{}", ci + 1)).heading());
+                                    if ui_h.small_button("Cancel edit").clicked() {
+                                        self.editing_card = None;
+                                        self.new_question.clear();
+                                        self.new_answer.clear();
+                                        self.new_hints.clear();
+                                        self.new_hint_input.clear();
+                                    }
+                                }
+                                None => {
+                                    ui_h.label(RichText::new("Add flashcard").heading());
+                                    if ui_h.small_button("Import outline…").on_hover_text("Add many cards from Markdown or plain-text notes").clicked() {
+                                        self.show_outline_import = true;
+                                    }
+                                    if ui_h.small_button("Paste from Quizlet…").on_hover_text("Add cards from text exported by Quizlet").clicked() {
+                                        self.show_paste_import = true;
+                                    }
+                                }
+                            }
+                        });
                         ui_right.add_space((4.0 * scale).round());
 
                         ui_right.label("Question:");
-                        ui_right.text_edit_singleline(&mut self.new_question);
+                        let question_edit = ui_right.text_edit_singleline(&mut self.new_question);
+                        if std::mem::take(&mut self.focus_new_question) {
+                            question_edit.request_focus();
+                        }
                         ui_right.label("Answer:");
                         ui_right.text_edit_singleline(&mut self.new_answer);
 
@@ -689,6 +1196,14 @@ impl StudyHelperApp {
                             if ui_h.button("Add Hint").clicked() {
                                 let hint = self.new_hint_input.trim().to_string();
                                 if !hint.is_empty() {
+                                    // hints of an existing card are edited in place so they can be undone
+                                    if let Some(ci) = self.editing_card
+                                        && let Some(card) = set.get_flashcard_mut(ci)
+                                    {
+                                        card.add_hint(hint.clone());
+                                        self.history.record(EditCommand::AddHint { set: set_name.clone(), card: ci, hint: hint.clone() });
+                                        unsaved_edit = true;
+                                    }
                                     self.new_hints.push(hint);
                                 }
                                 self.new_hint_input.clear();
@@ -709,7 +1224,14 @@ impl StudyHelperApp {
                             }
                             if let Some(i) = to_remove {
                                 if i < self.new_hints.len() {
-                                    self.new_hints.remove(i);
+                                    let hint = self.new_hints.remove(i);
+                                    if let Some(ci) = self.editing_card
+                                        && let Some(card) = set.get_flashcard_mut(ci)
+                                        && card.remove_hint(i).is_some()
+                                    {
+                                        self.history.record(EditCommand::RemoveHint { set: set_name.clone(), card: ci, index: i, hint });
+                                        unsaved_edit = true;
+                                    }
                                 }
                             }
                         }
@@ -718,36 +1240,37 @@ impl StudyHelperApp {
                         ui_right.horizontal(|ui_h| {
                             // Add flashcard button (enabled only if question and answer are non-empty)
                             let can_add = !self.new_question.trim().is_empty() && !self.new_answer.trim().is_empty();
-                            if ui_h.add_enabled(can_add, egui::Button::new("Add Flashcard")).clicked() {
-                                // Build the card and push it into the selected set
+                            let button_label = if self.editing_card.is_some() { "Update Flashcard" } else { "Add Flashcard" };
+                            if ui_h.add_enabled(can_add, egui::Button::new(button_label)).clicked() {
+                                // Build the card and push it into the selected set (or replace the edited card)
                                 let q = self.new_question.trim().to_string();
                                 let a = self.new_answer.trim().to_string();
                                 let hints = self.new_hints.clone();
-                                let card = AppFlashcard::new(q.clone(), a.clone(), hints.clone());
-                                set.add_flashcard(card);
-                                // select the newly added card so the user sees it immediately
-                                self.current_card_index = set.get_all_flashcards().len().saturating_sub(1);
-                                self.card_flipped = false;
-                                self.show_hint = false;
-                                // Try to persist the updated set immediately so subsequent
-                                // calls to `ensure_storage_consistency` won't clobber changes.
-                                if !self.storage_base_path.trim().is_empty() && !self.storage_class_name.trim().is_empty() {
-                                    let base = std::path::Path::new(&self.storage_base_path);
-                                    match crate::storage::save_set_into_class_folder(base, &self.storage_class_name, set.name(), set) {
-                                        Ok(p) => {
-                                            self.status_message = format!("Added and saved flashcard: '{}' -> {}", q, p.display());
-                                            log::info!("Saved set '{}' after adding flashcard to {}", set.name(), p.display());
-                                        }
-                                        Err(e) => {
-                                            self.status_message = format!("Added but save failed: {}", e);
-                                            log::warn!("Failed to save set '{}' after add: {}", set.name(), e);
+                                if let Some(ci) = self.editing_card.take() {
+                                    // the edited card keeps its id and everyone's review progress
+                                    if let Some(current) = set.get_flashcard_mut(ci) {
+                                        let before = current.clone();
+                                        current.set_text(q.clone(), a.clone(), hints.clone());
+                                        if !current.same_text(&before) {
+                                            let after = current.clone();
+                                            self.history.record(EditCommand::EditCard { set: set_name.clone(), index: ci, before, after });
                                         }
                                     }
+                                    self.current_card_index = ci;
                                 } else {
-                                    // stronger status message and structured logging for debugging
-                                    self.status_message = format!("Added flashcard: '{}'", q);
-                                    log::debug!("Add Flashcard clicked - question='{}', answer='{}', hints={:?}", q, a, hints);
+                                    let card = AppFlashcard::new(q.clone(), a.clone(), hints.clone());
+                                    let index = set.get_all_flashcards().len();
+                                    set.add_flashcard(card.clone());
+                                    self.history.record(EditCommand::AddCard { set: set_name.clone(), index, card });
+                                    // select the newly added card so the user sees it immediately
+                                    self.current_card_index = index;
                                 }
+                                self.card_flipped = false;
+                                self.show_hint = false;
+                                // persist the updated set right away (on the storage worker)
+                                self.status_message = format!("Added flashcard: '{}'", q);
+                                log::debug!("Add Flashcard clicked - question='{}', answer='{}', hints={:?}", q, a, hints);
+                                save_requested = true;
 
                                 // clear inputs
                                 self.new_question.clear();
@@ -758,22 +1281,39 @@ impl StudyHelperApp {
 
                             // If the Add button is disabled, show a small hint to the user
                             if !can_add {
-                                ui_h.label(egui::RichText::new("Question and Answer required").small().color(egui::Color32::from_gray(160)));
+                                ui_h.label(egui::RichText::new("Question and Answer required").small().weak());
                             }
 
                             // Save the currently edited set to disk (user-chosen base/class)
-                            if ui_h.button("Save Set").clicked() {
+                            let save_label = if self.dirty_sets.contains(set.name()) { "Save Set •" } else { "Save Set" };
+                            if ui_h.button(save_label).clicked() {
                                 if !self.storage_base_path.trim().is_empty() && !self.storage_class_name.trim().is_empty() {
-                                    let base = std::path::Path::new(&self.storage_base_path);
-                                    match crate::storage::save_set_into_class_folder(base, &self.storage_class_name, set.name(), set) {
-                                        Ok(p) => self.status_message = format!("Saved: {}", p.display()),
-                                        Err(e) => self.status_message = format!("Save error: {}", e),
-                                    }
+                                    self.status_message = format!("Saving '{}'", set.name());
+                                    save_requested = true;
                                 } else {
                                     self.status_message = "Set storage not configured. Open 'Create New Set' and set Base folder and Class folder.".to_string();
                                 }
                             }
                         });
+                        if unsaved_edit {
+                            self.mark_dirty(idx);
+                        }
+                        if save_requested && !self.persist_set(idx) {
+                            self.mark_dirty(idx);
+                        }
+
+                        // autosave preferences (stored in config.json)
+                        let mut autosave = self.config.autosave_enabled;
+                        let mut delay = self.config.autosave_delay_secs;
+                        ui_right.horizontal(|ui_h| {
+                            ui_h.checkbox(&mut autosave, "Autosave").on_hover_text("Save changed sets after a pause in editing and when the window loses focus");
+                            ui_h.add_enabled(autosave, egui::DragValue::new(&mut delay).range(1..=600).suffix(" s"));
+                        });
+                        if autosave != self.config.autosave_enabled || delay != self.config.autosave_delay_secs {
+                            self.config.autosave_enabled = autosave;
+                            self.config.autosave_delay_secs = delay;
+                            self.persist_config();
+                        }
                     }
                 }
                 else {
@@ -803,7 +1343,7 @@ impl StudyHelperApp {
                             if let Some(s) = dir.to_str() { 
                                 self.storage_base_path = s.to_string();
                                 // persist base path immediately
-                                let _ = save_config(&crate::storage::Config { storage_base_path: self.storage_base_path.clone(), storage_class_name: self.storage_class_name.clone() });
+                                self.persist_config();
                             }
                         }
                     }
@@ -817,7 +1357,7 @@ impl StudyHelperApp {
                             if let Some(s) = dir.file_name().and_then(|n| n.to_str()) {
                                 self.storage_class_name = s.to_string();
                                 // persist class name
-                                let _ = save_config(&crate::storage::Config { storage_base_path: self.storage_base_path.clone(), storage_class_name: self.storage_class_name.clone() });
+                                self.persist_config();
                             }
                         }
                     }
@@ -826,33 +1366,41 @@ impl StudyHelperApp {
                         if self.storage_base_path.trim().is_empty() || self.storage_class_name.trim().is_empty() {
                             self.status_message = "Please set both Base folder and Class folder".to_string();
                         } else {
-                            let base = std::path::Path::new(&self.storage_base_path);
-                            match crate::storage::load_sets_from_class_folder(base, &self.storage_class_name) {
-                                Ok(sets) => {
-                                    self.study_sets = sets;
-                                    if self.study_sets.is_empty() {
-                                        self.selected_set = None;
-                                        self.status_message = "No sets found in class folder".to_string();
-                                    } else {
-                                        self.selected_set = Some(0);
-                                        self.current_card_index = 0;
-                                        self.card_flipped = false;
-                                        self.show_hint = false;
-                                        self.status_message = format!("Loaded {} sets", self.study_sets.len());
-                                    }
-                                    // save the chosen base/class
-                                    let _ = save_config(&crate::storage::Config { storage_base_path: self.storage_base_path.clone(), storage_class_name: self.storage_class_name.clone() });
-                                    // now ensure everything is consistent with the filesystem
-                                    self.ensure_storage_consistency();
-                                }
-                                Err(e) => {
-                                    self.status_message = format!("Load error: {}", e);
-                                }
-                            }
+                            // re-list the classes; ensure_storage_consistency then (re)loads this one
+                            self.last_storage_class_name.clear();
+                            self.request_class_list();
+                            self.persist_config();
+                            self.status_message = format!("Loading class '{}'", self.storage_class_name.trim());
                         }
                     }
                 });
 
+                ui_win.horizontal(|ui_h| {
+                    ui_h.label("Stored as:");
+                    let mut backend = self.config.storage_backend;
+                    egui::ComboBox::from_id_salt("storage_backend")
+                        .selected_text(backend.label())
+                        .show_ui(ui_h, |ui| {
+                            for b in [StorageBackend::Json, StorageBackend::Sqlite] {
+                                ui.selectable_value(&mut backend, b, b.label());
+                            }
+                        });
+                    if backend != self.config.storage_backend {
+                        self.switch_storage_backend(backend);
+                    }
+                    let other = self.config.storage_backend.other();
+                    let can_migrate = !self.storage_base_path.trim().is_empty();
+                    if ui_h.add_enabled(can_migrate, egui::Button::new(format!("Copy sets from {}", other.label())))
+                        .on_hover_text("Copy every class and set stored the other way into the current store; sets that already exist are left alone")
+                        .clicked()
+                    {
+                        self.migrate_from_backend(other);
+                    }
+                });
+                if self.config.storage_backend == StorageBackend::Sqlite {
+                    ui_win.label(RichText::new(format!("Sets are kept in {} inside the base folder.", crate::storage::SQLITE_DB_FILE)).small());
+                }
+
                 ui_win.add_space((6.0 * scale).round());
                 ui_win.label(RichText::new("Import / Export").heading());
 
@@ -860,17 +1408,42 @@ impl StudyHelperApp {
                     ui_h.label("Import file:");
                     ui_h.text_edit_singleline(&mut self.import_file_path);
                     if ui_h.small_button("Browse").clicked() {
-                        if let Some(f) = FileDialog::new().add_filter("JSON", &["json"]).pick_file() {
+                        if let Some(f) = FileDialog::new()
+                            .add_filter("Study sets and quizzes", &["json", crate::storage::BUNDLE_EXTENSION, crate::formats::QTI_EXTENSION, "xml"])
+                            .add_filter("JSON", &["json"])
+                            .add_filter("Set bundle", &[crate::storage::BUNDLE_EXTENSION])
+                            .add_filter("QTI quiz (into the selected set)", &[crate::formats::QTI_EXTENSION, "xml"])
+                            .pick_file()
+                        {
                             if let Some(s) = f.to_str() { self.import_file_path = s.to_string(); }
                         }
                     }
                     if ui_h.button("Import into class").clicked() {
                         if !self.storage_base_path.trim().is_empty() && !self.storage_class_name.trim().is_empty() && !self.import_file_path.trim().is_empty() {
-                            let base = std::path::Path::new(&self.storage_base_path);
-                            let src = std::path::Path::new(&self.import_file_path);
-                            match crate::storage::import_set_file_to_class(base, &self.storage_class_name, src) {
-                                Ok(p) => self.status_message = format!("Imported: {}", p.display()),
-                                Err(e) => self.status_message = format!("Import error: {}", e),
+                            let location = self.storage_location();
+                            let class_name = self.storage_class_name.clone();
+                            let src = std::path::PathBuf::from(&self.import_file_path);
+                            let is_bundle = src.extension().is_some_and(|e| e.eq_ignore_ascii_case(crate::storage::BUNDLE_EXTENSION));
+                            let is_qti = src.extension().is_some_and(|e| e.eq_ignore_ascii_case(crate::formats::QTI_EXTENSION) || e.eq_ignore_ascii_case("xml"));
+                            if is_bundle {
+                                self.import_bundle(src);
+                            } else if is_qti {
+                                self.import_qti(src);
+                            } else {
+                            self.run_storage("Importing set", move || {
+                                let result = location.open().and_then(|store| store.import_set_file(&class_name, &src));
+                                (class_name, result)
+                            }, |app, (class_name, result)| match result {
+                                Ok(set) => {
+                                    app.status_message = format!("Imported set '{}'", set.name());
+                                    // the JSON watcher may have added it already
+                                    if class_name == app.storage_class_name && !app.study_sets.iter().any(|s| s.name() == set.name()) {
+                                        app.last_saved.insert(set.name().to_string(), set.clone());
+                                        app.study_sets.push(set);
+                                    }
+                                }
+                                Err(e) => app.status_message = format!("Import error: {}", e),
+                            });
                             }
                         }
                     }
@@ -887,16 +1460,40 @@ impl StudyHelperApp {
                     if ui_h.button("Export selected").clicked() {
                         if let Some(idx) = self.selected_set {
                             if idx < self.study_sets.len() && !self.export_dest_path.trim().is_empty() {
-                                let src = std::path::Path::new(&self.storage_base_path).join(&self.storage_class_name).join(format!("{}.json", sanitize_filename::sanitize(self.study_sets[idx].name())));
-                                let dst = std::path::Path::new(&self.export_dest_path);
-                                match crate::storage::export_set_file(&src, &dst) {
-                                    Ok(()) => self.status_message = format!("Exported to {}", dst.display()),
-                                    Err(e) => self.status_message = format!("Export error: {}", e),
-                                }
+                                let location = self.storage_location();
+                                let class_name = self.storage_class_name.clone();
+                                let set_name = self.study_sets[idx].name().to_string();
+                                let dst = std::path::PathBuf::from(&self.export_dest_path);
+                                self.run_storage("Exporting set", move || {
+                                    let result = location.open().and_then(|store| store.export_set_file(&class_name, &set_name, &dst));
+                                    (dst, result)
+                                }, |app, (dst, result)| match result {
+                                    Ok(()) => app.status_message = format!("Exported to {}", dst.display()),
+                                    Err(e) => app.status_message = format!("Export error: {}", e),
+                                });
                             }
                         }
                     }
                 });
+                ui_win.horizontal(|ui_h| {
+                    if ui_h.add_enabled(self.selected_set.is_some(), egui::Button::new("Export bundle"))
+                        .on_hover_text(format!("Write the selected set and its media as one .{} file for sharing", crate::storage::BUNDLE_EXTENSION))
+                        .clicked()
+                    {
+                        self.export_selected_bundle();
+                    }
+                    ui_h.checkbox(&mut self.bundle_include_reviews, "Include my review history and Match times");
+                });
+                if ui_win.add_enabled(self.selected_set.is_some(), egui::Button::new("Export web page"))
+                    .on_hover_text("Save the selected set as one HTML file with flip cards and self-grading quizzes; opens in any browser, offline")
+                    .clicked()
+                {
+                    self.export_selected_html();
+                }
+
+                ui_win.add_space((6.0 * scale).round());
+                ui_win.label(RichText::new("Print").heading());
+                self.print_controls(ui_win);
 
                 if !self.status_message.is_empty() {
                     ui_win.label(&self.status_message);
@@ -905,15 +1502,14 @@ impl StudyHelperApp {
                 ui_win.add_space((6.0 * scale).round());
                 ui_win.horizontal(|ui_h| {
                     if ui_h.button("Create").clicked() {
-                        if !self.new_set_name.trim().is_empty() {
+                        if !self.new_set_name.trim().is_empty() && self.set_name_taken(self.new_set_name.trim(), None) {
+                            self.status_message = format!("A set named '{}' (or one with the same file name) already exists", self.new_set_name.trim());
+                        } else if !self.new_set_name.trim().is_empty() {
                             let s = AppStudySet::new(self.new_set_name.trim().to_string());
                             self.study_sets.push(s);
                             self.selected_set = Some(self.study_sets.len() - 1);
-                            // Optionally save immediately if storage provided
-                            if !self.storage_base_path.trim().is_empty() && !self.storage_class_name.trim().is_empty() {
-                                let base = std::path::Path::new(&self.storage_base_path);
-                                let _ = crate::storage::save_set_into_class_folder(base, &self.storage_class_name, self.new_set_name.trim(), &self.study_sets[self.study_sets.len()-1]);
-                            }
+                            // save immediately if storage is configured
+                            self.persist_set(self.study_sets.len() - 1);
                             self.new_set_name.clear();
                             self.show_create_set_popup = false;
                         }
@@ -924,7 +1520,535 @@ impl StudyHelperApp {
                 });
             });
         }
-        
+
+        if self.show_manage_classes_popup {
+            self.manage_classes_window(ui.ctx(), scale);
+        }
+        if self.class_delete_target.is_some() {
+            self.confirm_delete_class_window(ui.ctx(), scale);
+        }
+        if self.archive_preview.is_some() {
+            self.archive_import_window(ui.ctx(), scale);
+        }
+        if self.show_outline_import {
+            self.outline_import_window(ui.ctx(), scale);
+        }
+        if self.show_paste_import {
+            self.paste_import_window(ui.ctx(), scale);
+        }
+        if self.set_action.is_some() {
+            self.set_action_window(ui.ctx(), scale);
+        }
+        if self.show_backups_popup {
+            self.backups_window(ui.ctx(), scale);
+        }
+    }
+
+    /// Reload the backup list for the selected set.
+    fn refresh_set_backups(&mut self) {
+        self.set_backups.clear();
+        if self.storage_base_path.trim().is_empty() || self.storage_class_name.trim().is_empty() {
+            return;
+        }
+        let Some(set) = self.selected_set.and_then(|i| self.study_sets.get(i)) else { return };
+        let base = std::path::PathBuf::from(&self.storage_base_path);
+        let class_name = self.storage_class_name.clone();
+        let set_name = set.name().to_string();
+        self.run_storage("Listing backups", move || {
+            let result = crate::storage::list_set_backups(&base, &class_name, &set_name);
+            (set_name, result)
+        }, |app, (set_name, result)| {
+            // ignore the result if another set was selected meanwhile
+            if app.selected_set.and_then(|i| app.study_sets.get(i)).is_none_or(|s| s.name() != set_name) {
+                return;
+            }
+            match result {
+                Ok(list) => app.set_backups = list,
+                Err(e) => app.status_message = format!("Error listing backups: {}", e),
+            }
+        });
+    }
+
+    /// Browser for the rotating backups of the selected set with a Restore action per entry.
+    fn backups_window(&mut self, ctx: &egui::Context, scale: f32) {
+        let Some(idx) = self.selected_set.filter(|i| *i < self.study_sets.len()) else {
+            self.show_backups_popup = false;
+            return;
+        };
+        let set_name = self.study_sets[idx].name().to_string();
+        let backups = self.set_backups.clone();
+        let mut restore: Option<std::path::PathBuf> = None;
+        let mut close = false;
+
+        egui::Window::new("Set Backups").collapsible(false).resizable(false).show(ctx, |ui_win| {
+            ui_win.label(format!("Backups of '{}' (newest first, up to {} kept)", set_name, crate::storage::MAX_BACKUPS_PER_SET));
+            ui_win.add_space((4.0 * scale).round());
+            if self.storage_base_path.trim().is_empty() || self.storage_class_name.trim().is_empty() {
+                ui_win.label("Set storage not configured. Open 'Create New Set' and set Base folder and Class folder.");
+            } else if backups.is_empty() {
+                ui_win.label(RichText::new("(no backups yet - one is taken when a saved set is overwritten)").italics());
+            } else {
+                egui::Grid::new("set_backups_grid").striped(true).show(ui_win, |ui_g| {
+                    ui_g.label(RichText::new("Saved at").strong());
+                    ui_g.label(RichText::new("Cards").strong());
+                    ui_g.label(RichText::new("Quizzes").strong());
+                    ui_g.label("");
+                    ui_g.end_row();
+                    for b in &backups {
+                        ui_g.label(crate::storage::format_timestamp(b.created_at));
+                        ui_g.label(b.card_count.to_string());
+                        ui_g.label(b.quiz_count.to_string());
+                        if ui_g.small_button("Restore").clicked() {
+                            restore = Some(b.path.clone());
+                        }
+                        ui_g.end_row();
+                    }
+                });
+                ui_win.label(RichText::new("Restoring replaces the set (including unsaved edits); the current version is backed up first.").small());
+            }
+            if !self.status_message.is_empty() {
+                ui_win.label(&self.status_message);
+            }
+            ui_win.add_space((6.0 * scale).round());
+            if ui_win.button("Close").clicked() {
+                close = true;
+            }
+        });
+
+        if let Some(path) = restore {
+            let base = std::path::PathBuf::from(&self.storage_base_path);
+            let class_name = self.storage_class_name.clone();
+            self.run_storage(format!("Restoring '{}'", set_name), move || {
+                let result = crate::storage::restore_set_backup(&base, &class_name, &set_name, &path);
+                (class_name, set_name, result)
+            }, |app, (class_name, set_name, result)| {
+                let set = match result {
+                    Ok(set) if class_name == app.storage_class_name => set,
+                    Ok(_) => return,
+                    Err(e) => {
+                        app.status_message = format!("Restore error: {}", e);
+                        return;
+                    }
+                };
+                let Some(idx) = app.study_sets.iter().position(|s| s.name() == set_name) else { return };
+                app.dirty_sets.remove(&set_name);
+                app.last_saved.insert(set_name.clone(), set.clone());
+                app.study_sets[idx] = set;
+                if app.selected_set == Some(idx) {
+                    app.current_card_index = 0;
+                    app.card_flipped = false;
+                    app.show_hint = false;
+                    app.selected_quiz = None;
+                }
+                app.status_message = format!("Restored '{}' from backup", set_name);
+                app.refresh_set_backups();
+            });
+        }
+        if close {
+            self.show_backups_popup = false;
+        }
+    }
+
+    /// True if `name` collides with another loaded set once both are sanitized into
+    /// file names. The loaded sets mirror the class folder (the watcher picks up files
+    /// added outside the app), and the storage calls still refuse to overwrite a file.
+    /// `ignore` is the index of a set that may keep its own file name.
+    fn set_name_taken(&self, name: &str, ignore: Option<usize>) -> bool {
+        let file_name = sanitize_filename::sanitize(name);
+        self.study_sets.iter().enumerate()
+            .any(|(i, s)| Some(i) != ignore && sanitize_filename::sanitize(s.name()) == file_name)
+    }
+
+    /// Popup for renaming, duplicating, or moving/copying the selected set.
+    fn set_action_window(&mut self, ctx: &egui::Context, scale: f32) {
+        let Some(action) = self.set_action else { return };
+        let Some(idx) = self.selected_set.filter(|i| *i < self.study_sets.len()) else {
+            self.set_action = None;
+            return;
+        };
+        let title = match action {
+            SetAction::Rename => "Rename Set",
+            SetAction::Duplicate => "Duplicate Set",
+            SetAction::MoveOrCopy => "Move / Copy Set",
+        };
+        let other_classes: Vec<String> = self.available_classes.iter().filter(|c| **c != self.storage_class_name).cloned().collect();
+        let mut run: Option<(SetAction, bool)> = None; // (action, remove source for move)
+        let mut cancel = false;
+
+        egui::Window::new(title).collapsible(false).resizable(false).show(ctx, |ui_win| {
+            ui_win.label(format!("Set: {}", self.study_sets[idx].name()));
+            ui_win.add_space((4.0 * scale).round());
+            match action {
+                SetAction::Rename | SetAction::Duplicate => {
+                    ui_win.label("New name:");
+                    ui_win.text_edit_singleline(&mut self.set_action_name);
+                }
+                SetAction::MoveOrCopy => {
+                    if other_classes.is_empty() {
+                        ui_win.label(RichText::new("(no other classes - create one in Manage classes)").italics());
+                    } else {
+                        if self.set_action_target_class >= other_classes.len() { self.set_action_target_class = 0; }
+                        ui_win.label("Target class:");
+                        egui::ComboBox::from_id_salt("set_action_target_class")
+                            .selected_text(&other_classes[self.set_action_target_class])
+                            .show_ui(ui_win, |ui| {
+                                for (i, c) in other_classes.iter().enumerate() {
+                                    ui.selectable_value(&mut self.set_action_target_class, i, c);
+                                }
+                            });
+                    }
+                }
+            }
+            if !self.status_message.is_empty() {
+                ui_win.label(&self.status_message);
+            }
+            ui_win.add_space((6.0 * scale).round());
+            ui_win.horizontal(|ui_h| {
+                match action {
+                    SetAction::Rename => if ui_h.button("Rename").clicked() { run = Some((action, false)); },
+                    SetAction::Duplicate => if ui_h.button("Duplicate").clicked() { run = Some((action, false)); },
+                    SetAction::MoveOrCopy => {
+                        let enabled = !other_classes.is_empty();
+                        if ui_h.add_enabled(enabled, egui::Button::new("Move")).clicked() { run = Some((action, true)); }
+                        if ui_h.add_enabled(enabled, egui::Button::new("Copy")).clicked() { run = Some((action, false)); }
+                    }
+                }
+                if ui_h.button("Cancel").clicked() {
+                    cancel = true;
+                }
+            });
+        });
+
+        if cancel {
+            self.set_action = None;
+            return;
+        }
+        let Some((action, remove_source)) = run else { return };
+        let storage_ready = !self.storage_base_path.trim().is_empty() && !self.storage_class_name.trim().is_empty();
+        let location = self.storage_location();
+        let new_name = self.set_action_name.trim().to_string();
+
+        match action {
+            SetAction::Rename => {
+                if new_name.is_empty() {
+                    self.status_message = "Set name cannot be empty".to_string();
+                } else if new_name == self.study_sets[idx].name() {
+                    self.set_action = None;
+                } else if self.set_name_taken(&new_name, Some(idx)) {
+                    self.status_message = format!("A set named '{}' (or one with the same file name) already exists", new_name);
+                } else if storage_ready {
+                    // the popup stays open until the rename is done; its result is applied by name
+                    let mut renamed = self.study_sets[idx].clone();
+                    let class_name = self.storage_class_name.clone();
+                    self.run_storage(format!("Renaming '{}'", renamed.name()), move || {
+                        let old_name = renamed.name().to_string();
+                        let result = location.open().and_then(|store| store.rename_set(&class_name, &mut renamed, &new_name));
+                        (class_name, old_name, renamed, result)
+                    }, |app, (class_name, old_name, renamed, result)| {
+                        if let Err(e) = result {
+                            app.status_message = format!("Rename error: {}", e);
+                            return;
+                        }
+                        app.status_message = format!("Renamed set '{}' to '{}'", old_name, renamed.name());
+                        app.set_action = None;
+                        if class_name != app.storage_class_name {
+                            return;
+                        }
+                        let Some(set) = app.study_sets.iter_mut().find(|s| s.name() == old_name) else { return };
+                        set.set_name(renamed.name().to_string());
+                        // the renamed file holds the set as it was when the rename was queued
+                        let edited_since = *set != renamed;
+                        if app.dirty_sets.remove(&old_name) && edited_since {
+                            app.dirty_sets.insert(renamed.name().to_string());
+                        }
+                        app.last_saved.remove(&old_name);
+                        app.last_saved.insert(renamed.name().to_string(), renamed);
+                    });
+                } else {
+                    if self.dirty_sets.remove(self.study_sets[idx].name()) {
+                        self.dirty_sets.insert(new_name.clone());
+                    }
+                    self.study_sets[idx].set_name(new_name.clone());
+                    self.status_message = format!("Renamed set to '{}'", new_name);
+                    self.set_action = None;
+                }
+            }
+            SetAction::Duplicate => {
+                if new_name.is_empty() {
+                    self.status_message = "Set name cannot be empty".to_string();
+                } else if self.set_name_taken(&new_name, None) {
+                    self.status_message = format!("A set named '{}' (or one with the same file name) already exists", new_name);
+                } else if storage_ready {
+                    let source = self.study_sets[idx].clone();
+                    let class_name = self.storage_class_name.clone();
+                    self.run_storage(format!("Duplicating '{}'", source.name()), move || {
+                        let result = location.open().and_then(|store| store.copy_set(&source, &class_name, &new_name));
+                        (class_name, result)
+                    }, |app, (class_name, result)| match result {
+                        Ok(copy) => {
+                            app.status_message = format!("Duplicated set as '{}'", copy.name());
+                            app.set_action = None;
+                            if class_name == app.storage_class_name && !app.set_name_taken(copy.name(), None) {
+                                app.last_saved.insert(copy.name().to_string(), copy.clone());
+                                app.study_sets.push(copy);
+                                app.selected_set = Some(app.study_sets.len() - 1);
+                                app.current_card_index = 0;
+                                app.card_flipped = false;
+                                app.show_hint = false;
+                            }
+                        }
+                        Err(e) => app.status_message = format!("Duplicate error: {}", e),
+                    });
+                } else {
+                    let mut copy = self.study_sets[idx].clone();
+                    copy.set_name(new_name.clone());
+                    self.study_sets.push(copy);
+                    self.selected_set = Some(self.study_sets.len() - 1);
+                    self.current_card_index = 0;
+                    self.card_flipped = false;
+                    self.show_hint = false;
+                    self.status_message = format!("Duplicated set as '{}'", new_name);
+                    self.set_action = None;
+                }
+            }
+            SetAction::MoveOrCopy => {
+                let Some(target) = other_classes.get(self.set_action_target_class).cloned() else { return };
+                if !storage_ready {
+                    self.status_message = "Set storage not configured. Open 'Create New Set' and set Base folder and Class folder.".to_string();
+                    return;
+                }
+                let set = self.study_sets[idx].clone();
+                let class_name = self.storage_class_name.clone();
+                let label = format!("{} '{}'", if remove_source { "Moving" } else { "Copying" }, set.name());
+                self.run_storage(label, move || {
+                    let result = location.open().and_then(|store| if remove_source {
+                        store.move_set(&set, &class_name, &target)
+                    } else {
+                        store.copy_set(&set, &target, set.name()).map(|_| ())
+                    });
+                    (class_name, set.name().to_string(), target, result)
+                }, move |app, (class_name, set_name, target, result)| match result {
+                    Ok(()) => {
+                        if remove_source {
+                            app.status_message = format!("Moved '{}' to class '{}'", set_name, target);
+                            if class_name == app.storage_class_name
+                                && let Some(idx) = app.study_sets.iter().position(|s| s.name() == set_name)
+                            {
+                                app.dirty_sets.remove(&set_name);
+                                app.study_sets.remove(idx);
+                                app.selected_set = if app.study_sets.is_empty() { None } else { Some(idx.min(app.study_sets.len() - 1)) };
+                                app.current_card_index = 0;
+                            }
+                        } else {
+                            app.status_message = format!("Copied '{}' to class '{}'", set_name, target);
+                        }
+                        app.set_action = None;
+                    }
+                    Err(e) => app.status_message = format!("{} error: {}", if remove_source { "Move" } else { "Copy" }, e),
+                });
+            }
+        }
+    }
+
+    /// Recompute the set/card counts shown in the class manager (in the background).
+    fn refresh_class_summaries(&mut self) {
+        if self.storage_base_path.trim().is_empty() {
+            self.class_summaries.clear();
+            return;
+        }
+        let location = self.storage_location();
+        let classes = self.available_classes.clone();
+        self.run_storage("Counting sets", move || {
+            let store = location.open();
+            classes.into_iter().map(|class_name| {
+                let summary = match &store {
+                    Ok(store) => store.summarize_class(&class_name),
+                    Err(e) => Err(std::io::Error::new(e.kind(), e.to_string())),
+                };
+                summary.unwrap_or_else(|e| {
+                    log::warn!("Failed to summarize class '{}': {}", class_name, e);
+                    ClassSummary { name: class_name, ..Default::default() }
+                })
+            }).collect::<Vec<_>>()
+        }, |app, summaries| app.class_summaries = summaries);
+    }
+
+    /// Window listing every class with its counts, plus create / rename / delete actions.
+    fn manage_classes_window(&mut self, ctx: &egui::Context, scale: f32) {
+        let summaries = self.class_summaries.clone();
+        // actions are collected here and applied after the window closure
+        let mut rename_request: Option<(String, String)> = None;
+        let mut export_request: Option<String> = None;
+        let mut create_request = false;
+        let mut close = false;
+
+        egui::Window::new("Manage Classes").collapsible(false).resizable(false).show(ctx, |ui_win| {
+            if self.storage_base_path.trim().is_empty() {
+                ui_win.label("Set a Base folder first (Create New Set > Storage).");
+            } else if summaries.is_empty() {
+                ui_win.label(RichText::new("(no classes)").italics());
+            } else {
+                egui::Grid::new("class_manager_grid").striped(true).show(ui_win, |ui_g| {
+                    ui_g.label(RichText::new("Class").strong());
+                    ui_g.label(RichText::new("Sets").strong());
+                    ui_g.label(RichText::new("Cards").strong());
+                    ui_g.label("");
+                    ui_g.end_row();
+                    for summary in &summaries {
+                        if self.class_rename_target.as_deref() == Some(summary.name.as_str()) {
+                            ui_g.text_edit_singleline(&mut self.class_rename_input);
+                        } else {
+                            ui_g.label(&summary.name);
+                        }
+                        ui_g.label(summary.set_count.to_string());
+                        ui_g.label(summary.card_count.to_string());
+                        ui_g.horizontal(|ui_h| {
+                            if self.class_rename_target.as_deref() == Some(summary.name.as_str()) {
+                                if ui_h.small_button("OK").clicked() {
+                                    rename_request = Some((summary.name.clone(), self.class_rename_input.clone()));
+                                }
+                                if ui_h.small_button("Cancel").clicked() {
+                                    self.class_rename_target = None;
+                                }
+                            } else {
+                                if ui_h.small_button("Rename").clicked() {
+                                    self.class_rename_target = Some(summary.name.clone());
+                                    self.class_rename_input = summary.name.clone();
+                                }
+                                if ui_h.small_button("Delete").clicked() {
+                                    self.class_delete_target = Some(summary.name.clone());
+                                    self.class_delete_to_trash = true;
+                                }
+                                if ui_h.small_button("Export").on_hover_text("Write this class into an archive file").clicked() {
+                                    export_request = Some(summary.name.clone());
+                                }
+                            }
+                        });
+                        ui_g.end_row();
+                    }
+                });
+            }
+
+            ui_win.add_space((6.0 * scale).round());
+            ui_win.horizontal(|ui_h| {
+                ui_h.label("New class:");
+                ui_h.text_edit_singleline(&mut self.new_class_name);
+                let can_create = !self.storage_base_path.trim().is_empty() && !self.new_class_name.trim().is_empty();
+                if ui_h.add_enabled(can_create, egui::Button::new("Create")).clicked() {
+                    create_request = true;
+                }
+            });
+
+            ui_win.add_space((6.0 * scale).round());
+            self.archive_controls(ui_win);
+
+            if !self.status_message.is_empty() {
+                ui_win.label(&self.status_message);
+            }
+            ui_win.add_space((6.0 * scale).round());
+            if ui_win.button("Close").clicked() {
+                close = true;
+            }
+        });
+
+        if let Some(class_name) = export_request {
+            self.export_archive(vec![class_name.clone()], &class_name);
+        }
+        if create_request {
+            let location = self.storage_location();
+            let class_name = self.new_class_name.trim().to_string();
+            self.run_storage("Creating class", move || {
+                let result = location.open().and_then(|store| store.create_class(&class_name));
+                (class_name, result)
+            }, |app, (class_name, result)| match result {
+                Ok(()) => {
+                    app.status_message = format!("Created class '{}'", class_name);
+                    app.new_class_name.clear();
+                    app.request_class_list();
+                }
+                Err(e) => app.status_message = format!("Create class error: {}", e),
+            });
+        }
+        if let Some((old_name, new_name)) = rename_request {
+            let location = self.storage_location();
+            self.run_storage(format!("Renaming class '{}'", old_name), move || {
+                let result = location.open().and_then(|store| store.rename_class(&old_name, &new_name));
+                (old_name, new_name.trim().to_string(), result)
+            }, |app, (old_name, new_name, result)| match result {
+                Ok(_) => {
+                    // keep the renamed class selected without reloading its sets
+                    if app.storage_class_name == old_name {
+                        app.storage_class_name = new_name.clone();
+                        app.last_storage_class_name = new_name.clone();
+                        app.persist_config();
+                    }
+                    if app.dirty_class == old_name {
+                        app.dirty_class = new_name.clone();
+                    }
+                    app.status_message = format!("Renamed class '{}' to '{}'", old_name, new_name);
+                    app.class_rename_target = None;
+                    app.request_class_list();
+                }
+                Err(e) => app.status_message = format!("Rename class error: {}", e),
+            });
+        }
+        if close {
+            self.show_manage_classes_popup = false;
+            self.class_rename_target = None;
+        }
+    }
+
+    /// Confirmation dialog shown before a class folder is deleted.
+    fn confirm_delete_class_window(&mut self, ctx: &egui::Context, scale: f32) {
+        let Some(class_name) = self.class_delete_target.clone() else { return };
+        let summary = self.class_summaries.iter().find(|s| s.name == class_name).cloned().unwrap_or_default();
+        let mut confirmed = false;
+        let mut cancelled = false;
+
+        egui::Window::new("Delete Class").collapsible(false).resizable(false).order(egui::Order::Foreground).show(ctx, |ui_win| {
+            ui_win.label(format!("Delete class '{}' ({} sets, {} cards)?", class_name, summary.set_count, summary.card_count));
+            ui_win.checkbox(&mut self.class_delete_to_trash, format!("Move to trash ({} folder inside the base folder)", crate::storage::CLASS_TRASH_DIR));
+            if !self.class_delete_to_trash {
+                ui_win.label(RichText::new("The folder and every set in it will be permanently deleted.").color(ui_win.visuals().error_fg_color));
+            }
+            ui_win.add_space((6.0 * scale).round());
+            ui_win.horizontal(|ui_h| {
+                if ui_h.button("Delete").clicked() {
+                    confirmed = true;
+                }
+                if ui_h.button("Cancel").clicked() {
+                    cancelled = true;
+                }
+            });
+        });
+
+        if confirmed {
+            let location = self.storage_location();
+            let to_trash = self.class_delete_to_trash;
+            self.run_storage(format!("Deleting class '{}'", class_name), move || {
+                let result = location.open().and_then(|store| store.delete_class(&class_name, to_trash));
+                (class_name, result)
+            }, move |app, (class_name, result)| match result {
+                Ok(()) => {
+                    app.status_message = if to_trash {
+                        format!("Moved class '{}' to trash", class_name)
+                    } else {
+                        format!("Deleted class '{}'", class_name)
+                    };
+                    if app.storage_class_name == class_name {
+                        // the sets in memory belong to the deleted class
+                        app.study_sets.clear();
+                        app.selected_set = None;
+                        app.dirty_sets.clear();
+                    }
+                    app.request_class_list();
+                }
+                Err(e) => app.status_message = format!("Delete class error: {}", e),
+            });
+            self.class_delete_target = None;
+        } else if cancelled {
+            self.class_delete_target = None;
+        }
     }
 
     fn quiz_view(&mut self, ui: &mut egui::Ui, scale: f32) {
@@ -959,7 +2083,7 @@ impl StudyHelperApp {
                 if self.study_sets.is_empty() {
                     ui_left.label(RichText::new("(no sets)").italics());
                 } else {
-                    let labels: Vec<String> = self.study_sets.iter().map(|s| s.name().to_string()).collect();
+                    let labels: Vec<String> = (0..self.study_sets.len()).map(|i| self.set_label(i)).collect();
                     let mut selected = self.selected_set.unwrap_or(0);
                     if selected >= labels.len() { selected = 0; }
                     egui::ComboBox::from_id_salt("quiz_set_select").selected_text(&labels[selected]).show_ui(ui_left, |ui| {
@@ -988,7 +2112,8 @@ impl StudyHelperApp {
                                 // Start Quiz button placed above the quiz dropdown
                                 ui_left.add_space((4.0 * scale).round());
                                 if ui_left.button("Start Quiz").clicked() {
-                                    self.show_quiz_window = true;
+                                    self.selected_quiz = Some(qsel);
+                                    self.start_quiz_session();
                                     self.status_message = "Starting quiz...".to_string();
                                 }
                                 egui::ComboBox::from_id_salt("quiz_select").selected_text(&titles[qsel]).show_ui(ui_left, |ui| {
@@ -1010,38 +2135,33 @@ impl StudyHelperApp {
                                 ui_left.vertical(|ui_v| {
                                     if ui_v.small_button("Delete Quiz").clicked() {
                                         if let Some(qi) = self.selected_quiz {
-                                            if let Some(removed) = self.study_sets[idx].remove_quiz(qi) {
-                                                self.status_message = format!("Deleted quiz '{}'", removed.title());
-                                                // persist set if storage configured
-                                                if !self.storage_base_path.trim().is_empty() && !self.storage_class_name.trim().is_empty() {
-                                                    let base = std::path::Path::new(&self.storage_base_path);
-                                                    let set_ref = &self.study_sets[idx];
-                                                    match crate::storage::save_set_into_class_folder(base, &self.storage_class_name, set_ref.name(), set_ref) {
-                                                        Ok(p) => self.status_message = format!("Deleted and saved: {}", p.display()),
-                                                        Err(e) => self.status_message = format!("Deleted but save failed: {}", e),
-                                                    }
-                                                }
-                                                // adjust selection: if no remaining quizzes, clear selection
-                                                let remaining = self.study_sets[idx].get_all_quizzes().len();
-                                                if remaining == 0 {
-                                                    self.selected_quiz = None;
-                                                    self.quiz_current_question_index = 0;
-                                                } else if qi >= remaining {
-                                                    self.selected_quiz = Some(0);
-                                                    self.quiz_current_question_index = 0;
-                                                }
-                                            }
+                                            self.pending_delete = Some(PendingDelete::Quiz { set_idx: idx, quiz_idx: qi });
                                         }
                                     }
-                                    ui_v.add_space((4.0 * scale).round());
-                                    if ui_v.button("Save").clicked() {
-                                        if !self.storage_base_path.trim().is_empty() && !self.storage_class_name.trim().is_empty() {
-                                            let base = std::path::Path::new(&self.storage_base_path);
-                                            let set_ref = &self.study_sets[idx];
-                                            match crate::storage::save_set_into_class_folder(base, &self.storage_class_name, set_ref.name(), set_ref) {
-                                                Ok(p) => self.status_message = format!("Saved: {}", p.display()),
-                                                Err(e) => self.status_message = format!("Save error: {}", e),
+                                    if ui_v.small_button("Print exam…").on_hover_text("Save a blank exam sheet and a separate answer key as PDFs").clicked()
+                                        && let Some(qi) = self.selected_quiz
+                                    {
+                                        self.export_quiz_pdfs(idx, qi);
+                                    }
+                                    ui_v.horizontal(|ui_h| {
+                                        for format in [crate::formats::QuizTextFormat::Gift, crate::formats::QuizTextFormat::Aiken] {
+                                            if ui_h.small_button(format!("Export {}…", format.label())).on_hover_text("Save for upload to an LMS such as Moodle").clicked()
+                                                && let Some(qi) = self.selected_quiz
+                                            {
+                                                self.export_quiz_file(idx, qi, format);
                                             }
+                                        }
+                                        if ui_h.small_button("Export QTI…").on_hover_text("Save as an IMS QTI 2.1 zip package").clicked()
+                                            && let Some(qi) = self.selected_quiz
+                                        {
+                                            self.export_quiz_qti(idx, qi);
+                                        }
+                                    });
+                                    ui_v.add_space((4.0 * scale).round());
+                                    let save_label = if self.is_dirty(self.study_sets[idx].name()) { "Save •" } else { "Save" };
+                                    if ui_v.button(save_label).clicked() {
+                                        if self.persist_set(idx) {
+                                            self.status_message = format!("Saving '{}'", self.study_sets[idx].name());
                                         } else {
                                             self.status_message = "Set storage not configured. Open 'Create New Set' and set Base folder and Class folder.".to_string();
                                         }
@@ -1054,6 +2174,9 @@ impl StudyHelperApp {
                                 self.show_create_quiz_popup = true;
                                 self.new_quiz_name.clear();
                             }
+                            if ui_left.small_button("Import GIFT / Aiken…").on_hover_text("Add a quiz from a Moodle GIFT or Aiken text file").clicked() {
+                                self.import_quiz_file(idx);
+                            }
                         }
                     }
                 }
@@ -1098,21 +2221,29 @@ impl StudyHelperApp {
                                         ui_list.add_space((6.0 * scale).round());
                                         ui_list.vertical(|ui_lv| {
                                             if ui_lv.small_button("Add").clicked() {
+                                                let before = self.study_sets[set_idx].get_all_quizzes().get(qi).cloned();
                                                 if let Some(quiz_mut) = self.study_sets[set_idx].get_all_quizzes_mut().get_mut(qi) {
                                                     quiz_mut.add_question("New question".to_string(), Vec::new(), "".to_string(), crate::models::QuestionType::FillInTheBlank);
                                                     // select the newly added question
                                                     self.quiz_current_question_index = quiz_mut.question_count().saturating_sub(1);
                                                 }
+                                                if let Some(before) = before {
+                                                    self.record_quiz_edit(set_idx, qi, before, "Add question");
+                                                }
                                             }
                                             ui_lv.add_space((4.0 * scale).round());
                                             if ui_lv.small_button("Delete").clicked() {
                                                 let idx = self.quiz_current_question_index;
+                                                let before = self.study_sets[set_idx].get_all_quizzes().get(qi).cloned();
                                                 if let Some(quiz_mut) = self.study_sets[set_idx].get_all_quizzes_mut().get_mut(qi) {
                                                     if idx < quiz_mut.question_count() {
                                                         quiz_mut.remove_question(idx);
                                                         if self.quiz_current_question_index > 0 { self.quiz_current_question_index -= 1; }
                                                     }
                                                 }
+                                                if let Some(before) = before {
+                                                    self.record_quiz_edit(set_idx, qi, before, "Delete question");
+                                                }
                                             }
                                         });
                                     });
@@ -1148,6 +2279,7 @@ impl StudyHelperApp {
                                                 ui_edit.add_space((6.0 * scale).round());
                                                 ui_edit.horizontal(|ui_apply| {
                                                     if ui_apply.button("Apply").clicked() {
+                                                        let before = self.study_sets[set_idx].get_all_quizzes().get(qi).cloned();
                                                         if let Some(quiz_mut) = self.study_sets[set_idx].get_all_quizzes_mut().get_mut(qi) {
                                                             // apply title change from buffer
                                                             quiz_mut.set_title(self.quiz_edit_title.clone());
@@ -1159,22 +2291,24 @@ impl StudyHelperApp {
                                                                 self.status_message = "Failed to apply changes".to_string();
                                                             }
                                                         }
+                                                        if let Some(before) = before {
+                                                            self.record_quiz_edit(set_idx, qi, before, "Edit quiz");
+                                                        }
                                                     }
                                                     if ui_apply.button("Save").clicked() {
                                                         // write buffer back to model and persist set
+                                                        let before = self.study_sets[set_idx].get_all_quizzes().get(qi).cloned();
                                                         if let Some(quiz_mut) = self.study_sets[set_idx].get_all_quizzes_mut().get_mut(qi) {
                                                             quiz_mut.set_title(self.quiz_edit_title.clone());
                                                             let new_opts: Vec<String> = self.quiz_edit_opts_joined.split(',').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect();
                                                             let new_data = crate::models::QuestionData { prompt: self.quiz_edit_prompt.clone(), options: new_opts, answer: self.quiz_edit_answer.clone(), question_type: self.quiz_edit_qtype.clone() };
                                                             let _ = quiz_mut.update_question(sel_q, new_data);
                                                         }
-                                                        if !self.storage_base_path.trim().is_empty() && !self.storage_class_name.trim().is_empty() {
-                                                            let base = std::path::Path::new(&self.storage_base_path);
-                                                            let set_ref = &self.study_sets[set_idx];
-                                                            match crate::storage::save_set_into_class_folder(base, &self.storage_class_name, set_ref.name(), set_ref) {
-                                                                Ok(p) => self.status_message = format!("Saved: {}", p.display()),
-                                                                Err(e) => self.status_message = format!("Save error: {}", e),
-                                                            }
+                                                        if let Some(before) = before {
+                                                            self.record_quiz_edit(set_idx, qi, before, "Edit quiz");
+                                                        }
+                                                        if self.persist_set(set_idx) {
+                                                            self.status_message = format!("Saving '{}'", self.study_sets[set_idx].name());
                                                         } else {
                                                             self.status_message = "Set storage not configured.".to_string();
                                                         }
@@ -1234,19 +2368,13 @@ impl StudyHelperApp {
                                         q.add_placeholder_questions(self.new_quiz_mc_count, self.new_quiz_tf_count, self.new_quiz_sa_count, self.new_quiz_mb_count);
 
                                         // attach quiz to the selected set
+                                        let quiz_index = self.study_sets[idx].get_all_quizzes().len();
+                                        self.history.record(EditCommand::CreateQuiz { set: self.study_sets[idx].name().to_string(), index: quiz_index, quiz: q.clone() });
                                         self.study_sets[idx].add_quiz(q);
 
                                         // persist if configured
-                                        if !self.storage_base_path.trim().is_empty() && !self.storage_class_name.trim().is_empty() {
-                                            let base = std::path::Path::new(&self.storage_base_path);
-                                            let set_ref = &self.study_sets[idx];
-                                            match crate::storage::save_set_into_class_folder(base, &self.storage_class_name, set_ref.name(), set_ref) {
-                                                Ok(p) => self.status_message = format!("Created and saved quiz in {}", p.display()),
-                                                Err(e) => self.status_message = format!("Created but save failed: {}", e),
-                                            }
-                                        } else {
-                                            self.status_message = format!("Created quiz '{}'", self.new_quiz_name.trim());
-                                        }
+                                        self.persist_set(idx);
+                                        self.status_message = format!("Created quiz '{}'", self.new_quiz_name.trim());
 
                                         // select the newly created quiz
                                         self.selected_quiz = Some(self.study_sets[idx].get_all_quizzes().len() - 1);
@@ -1275,38 +2403,7 @@ impl StudyHelperApp {
         }
         // Distraction-free quiz window (rendered when Start Quiz is clicked)
         if self.show_quiz_window {
-            // Get full screen rect and size the window to match it
-            let screen_rect = ui.ctx().input(|i| i.screen_rect);
-            let win_w = screen_rect.width().max(1.0);
-            let win_h = screen_rect.height().max(1.0);
-
-            // Use a Window forced to the foreground so it overlays everything and looks like a modal
-            egui::Window::new("Quiz Session")
-                .order(egui::Order::Foreground)
-                .collapsible(false)
-                .resizable(false)
-                .movable(false)
-                .title_bar(false)
-                .default_pos(egui::pos2(screen_rect.left(), screen_rect.top()))
-                .fixed_size(egui::vec2(win_w, win_h))
-                .show(ui.ctx(), |ui_win| {
-                    // Paint a dark background for the whole window to fully obscure underlying UI
-                    let win_rect = ui.ctx().available_rect();
-                    let panel_bg = egui::Color32::from_rgb(18, 18, 20);
-                    ui_win.painter().rect_filled(win_rect, 0.0, panel_bg);
-
-                    // Content centered and in white for dark mode
-                    ui_win.vertical_centered(|ui_c| {
-                        ui_c.add_space(8.0);
-                        ui_c.label(RichText::new("Quiz Session").heading().color(egui::Color32::WHITE));
-                        ui_c.add_space(12.0);
-                        ui_c.label(RichText::new("(Distraction-free mode — quiz content will appear here.)").color(egui::Color32::WHITE));
-                        ui_c.add_space(18.0);
-                        if ui_c.add_sized(egui::Vec2::new(96.0, 36.0), egui::Button::new(RichText::new("Exit").color(egui::Color32::WHITE))).clicked() {
-                            self.show_quiz_window = false;
-                        }
-                    });
-                });
+            self.quiz_session_window(ui.ctx(), scale);
         }
     }
 }
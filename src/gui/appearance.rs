@@ -0,0 +1,115 @@
+// src/gui/appearance.rs
+use eframe::egui;
+use egui::{FontId, RichText, TextStyle, Vec2};
+use crate::theme::{self, Theme, DEFAULT_FONT_SIZE, FONT_SIZE_RANGE, ZOOM_RANGE};
+use rfd::FileDialog;
+use super::StudyHelperApp;
+
+/// Font files offered by the font pickers.
+const FONT_EXTENSIONS: &[&str] = &["ttf", "otf", "ttc"];
+
+impl StudyHelperApp {
+    /// Apply the theme, text sizes and zoom to this frame and load changed font
+    /// files in the background. Returns the UI scale the views size themselves by.
+    pub(super) fn apply_appearance(&mut self, ctx: &egui::Context) -> f32 {
+        let appearance = &self.config.appearance;
+        let fonts = appearance.font_files();
+        if fonts != self.fonts_requested {
+            self.fonts_requested = fonts.clone();
+            let ctx = ctx.clone();
+            self.run_storage("Loading fonts", move || theme::load_fonts(&fonts.0, &fonts.1), move |app, result| match result {
+                Ok(definitions) => ctx.set_fonts(definitions),
+                Err(e) => app.status_message = format!("Font not changed: {}", e),
+            });
+        }
+
+        // Without a fixed zoom, scale with the available width so the UI grows on large displays.
+        let appearance = &self.config.appearance;
+        let scale = match appearance.zoom {
+            Some(zoom) => zoom.clamp(*ZOOM_RANGE.start(), *ZOOM_RANGE.end()),
+            None => {
+                let available = ctx.available_rect().size();
+                let base_width = 900.0_f32; // tweakable "design" width
+                let scale = (available.x / base_width).clamp(0.7, 3.0);
+                if scale.is_finite() && scale > 0.0 { scale } else { 1.0 }
+            }
+        };
+        let text = scale * appearance.font_size.clamp(*FONT_SIZE_RANGE.start(), *FONT_SIZE_RANGE.end()) / DEFAULT_FONT_SIZE;
+
+        // Some egui versions expose only immutable access, so clone, modify and set back.
+        let mut style = (*ctx.style()).clone();
+        style.visuals = appearance.visuals(ctx.system_theme());
+        style.text_styles.insert(TextStyle::Heading, FontId::proportional(28.0 * text));
+        style.text_styles.insert(TextStyle::Body, FontId::proportional(16.0 * text));
+        style.text_styles.insert(TextStyle::Button, FontId::proportional(18.0 * text));
+        style.text_styles.insert(TextStyle::Small, FontId::proportional(12.0 * text));
+        style.text_styles.insert(TextStyle::Monospace, FontId::monospace(14.0 * text));
+        style.spacing.button_padding = Vec2::new((10.0 * scale).round(), (6.0 * scale).round());
+        style.spacing.item_spacing = Vec2::new((8.0 * scale).round(), (8.0 * scale).round());
+        ctx.set_style(style);
+        scale
+    }
+
+    /// Theme, font and size settings; they apply from the next frame.
+    pub(super) fn appearance_ui(&mut self, ui: &mut egui::Ui, scale: f32) {
+        let appearance = &mut self.config.appearance;
+        ui.horizontal(|ui_h| {
+            ui_h.label("Theme:");
+            egui::ComboBox::from_id_salt("appearance_theme").selected_text(appearance.theme.label()).show_ui(ui_h, |ui_c| {
+                for choice in Theme::ALL {
+                    ui_c.selectable_value(&mut appearance.theme, choice, choice.label());
+                }
+            });
+        });
+
+        ui.add_space((4.0 * scale).round());
+        font_picker(ui, "Font:", "Built-in", &mut appearance.font_path);
+        font_picker(ui, "CJK font:", "None", &mut appearance.cjk_font_path);
+        ui.horizontal(|ui_h| {
+            ui_h.label(RichText::new("Used for Chinese, Japanese and Korean characters the main font lacks.").small());
+            if ui_h.small_button("Find installed").clicked() {
+                match theme::find_cjk_font() {
+                    Some(path) => appearance.cjk_font_path = path.to_string_lossy().to_string(),
+                    None => self.status_message = "No CJK font found in the usual places; choose one with Browse…".to_string(),
+                }
+            }
+        });
+
+        ui.add_space((4.0 * scale).round());
+        ui.horizontal(|ui_h| {
+            ui_h.label("Text size:");
+            ui_h.add(egui::Slider::new(&mut appearance.font_size, FONT_SIZE_RANGE).step_by(1.0).suffix(" pt"));
+        });
+        ui.horizontal(|ui_h| {
+            let mut fixed = appearance.zoom.is_some();
+            if ui_h.checkbox(&mut fixed, "Fixed zoom:").on_hover_text("Otherwise the interface grows and shrinks with the window").changed() {
+                appearance.zoom = fixed.then_some(scale);
+            }
+            if let Some(zoom) = &mut appearance.zoom {
+                ui_h.add(egui::Slider::new(zoom, ZOOM_RANGE).step_by(0.05).custom_formatter(|z, _| format!("{:.0}%", z * 100.0)));
+            }
+        });
+
+        ui.add_space((6.0 * scale).round());
+        if ui.button("Reset appearance").clicked() {
+            *appearance = Default::default();
+        }
+    }
+}
+
+/// Label, chosen file and Browse/Clear buttons for one font file.
+fn font_picker(ui: &mut egui::Ui, label: &str, unset: &str, path: &mut String) {
+    ui.horizontal(|ui_h| {
+        ui_h.label(label);
+        let shown = std::path::Path::new(path.as_str()).file_name().map(|n| n.to_string_lossy().to_string());
+        ui_h.label(RichText::new(shown.unwrap_or_else(|| unset.to_string())).monospace()).on_hover_text(path.as_str());
+        if ui_h.small_button("Browse…").clicked()
+            && let Some(file) = FileDialog::new().add_filter("Fonts", FONT_EXTENSIONS).pick_file()
+        {
+            *path = file.to_string_lossy().to_string();
+        }
+        if !path.is_empty() && ui_h.small_button("Clear").clicked() {
+            path.clear();
+        }
+    });
+}
@@ -0,0 +1,148 @@
+// src/gui/autosave.rs
+use eframe::egui;
+use egui::RichText;
+use super::StudyHelperApp;
+
+impl StudyHelperApp {
+    /// Flag the set at `set_idx` as edited in memory and restart the autosave delay.
+    pub(super) fn mark_dirty(&mut self, set_idx: usize) {
+        if let Some(name) = self.study_sets.get(set_idx).map(|s| s.name().to_string()) {
+            self.note_unsaved(&name);
+            self.last_edit = Some(std::time::Instant::now());
+        }
+    }
+
+    /// Flag a set as not (yet) written to disk. Saves clear the flag when they succeed.
+    pub(super) fn note_unsaved(&mut self, set_name: &str) {
+        if self.dirty_sets.is_empty() {
+            self.dirty_class = self.storage_class_name.clone();
+        }
+        self.dirty_sets.insert(set_name.to_string());
+    }
+
+    pub(super) fn is_dirty(&self, set_name: &str) -> bool {
+        self.dirty_sets.contains(set_name)
+    }
+
+    /// Set name as shown in the set pickers, with a marker for unsaved changes.
+    pub(super) fn set_label(&self, set_idx: usize) -> String {
+        match self.study_sets.get(set_idx) {
+            Some(set) if self.is_dirty(set.name()) => format!("{} •", set.name()),
+            Some(set) => set.name().to_string(),
+            None => String::new(),
+        }
+    }
+
+    /// Queue saves for every dirty set into the class folder it was loaded from.
+    /// Sets with an unresolved on-disk conflict are skipped.
+    pub(super) fn save_dirty_sets(&mut self) {
+        if self.dirty_sets.is_empty() || self.storage_base_path.trim().is_empty() || self.dirty_class.trim().is_empty() {
+            return;
+        }
+        // names that no longer match a loaded set have nothing left to save
+        self.dirty_sets.retain(|name| self.study_sets.iter().any(|s| s.name() == name));
+        let to_save: Vec<_> = self.study_sets.iter()
+            .filter(|s| self.dirty_sets.contains(s.name()) && !self.has_conflict(s.name()))
+            .cloned()
+            .collect();
+        for set in to_save {
+            self.queue_set_save(self.dirty_class.clone(), set);
+        }
+    }
+
+    /// Save pending edits before the loaded sets are replaced from disk (e.g. on a class switch).
+    /// The saves are queued ahead of the load, so the load sees them.
+    pub(super) fn flush_dirty_sets_before_reload(&mut self) {
+        self.save_dirty_sets();
+        self.set_conflicts.clear();
+    }
+
+    /// Run the debounced autosave and intercept window close while edits are unsaved.
+    /// Called once per frame before any view is drawn.
+    pub(super) fn autosave_tick(&mut self, ctx: &egui::Context) {
+        let focused = ctx.input(|i| i.viewport().focused).unwrap_or(true);
+        let lost_focus = self.window_focused && !focused;
+        self.window_focused = focused;
+
+        if self.config.autosave_enabled && !self.dirty_sets.is_empty() && !self.storage_base_path.trim().is_empty() {
+            let delay = std::time::Duration::from_secs(self.config.autosave_delay_secs as u64);
+            let elapsed = self.last_edit.map(|t| t.elapsed()).unwrap_or(delay);
+            if lost_focus || elapsed >= delay {
+                if !self.storage.is_busy() {
+                    self.save_dirty_sets();
+                }
+                // don't queue the same saves again every frame
+                self.last_edit = Some(std::time::Instant::now());
+            } else {
+                ctx.request_repaint_after(delay - elapsed);
+            }
+        }
+
+        if ctx.input(|i| i.viewport().close_requested()) && !self.allow_close {
+            let unsaved = !self.dirty_sets.is_empty();
+            if unsaved || self.storage.is_busy() {
+                ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+                if unsaved && !self.config.autosave_enabled {
+                    self.show_exit_prompt = true;
+                } else {
+                    // save what's left and quit once the worker is done
+                    self.save_dirty_sets();
+                    self.quit_when_saved = true;
+                }
+            }
+        }
+
+        if self.quit_when_saved && !self.storage.is_busy() {
+            self.quit_when_saved = false;
+            if self.dirty_sets.is_empty() {
+                self.allow_close = true;
+                ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+            } else {
+                // some saves failed or are blocked by a conflict
+                self.show_exit_prompt = true;
+            }
+        }
+    }
+
+    /// "Unsaved changes" dialog shown when the window is closed with dirty sets.
+    pub(super) fn exit_prompt_window(&mut self, ctx: &egui::Context) {
+        let mut names: Vec<&String> = self.dirty_sets.iter().collect();
+        names.sort();
+        let names: Vec<String> = names.into_iter().cloned().collect();
+        let storage_ready = !self.storage_base_path.trim().is_empty() && !self.dirty_class.trim().is_empty();
+        let mut save_and_quit = false;
+        let mut quit = false;
+        let mut cancel = false;
+        egui::Window::new("Unsaved Changes").collapsible(false).resizable(false).order(egui::Order::Foreground).show(ctx, |ui_win| {
+            ui_win.label("These sets have changes that are not saved:");
+            for name in &names {
+                ui_win.label(format!("• {}", name));
+            }
+            if !storage_ready {
+                ui_win.label(RichText::new("Storage is not configured, so they cannot be saved.").small().color(ui_win.visuals().error_fg_color));
+            }
+            ui_win.horizontal(|ui_h| {
+                if ui_h.add_enabled(storage_ready, egui::Button::new("Save and quit")).clicked() {
+                    save_and_quit = true;
+                }
+                if ui_h.button("Quit without saving").clicked() {
+                    quit = true;
+                }
+                if ui_h.button("Cancel").clicked() {
+                    cancel = true;
+                }
+            });
+        });
+        if save_and_quit {
+            self.show_exit_prompt = false;
+            self.save_dirty_sets();
+            self.quit_when_saved = true;
+        } else if quit {
+            self.show_exit_prompt = false;
+            self.allow_close = true;
+            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+        } else if cancel {
+            self.show_exit_prompt = false;
+        }
+    }
+}
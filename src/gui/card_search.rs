@@ -0,0 +1,80 @@
+// src/gui/card_search.rs
+use eframe::egui;
+use egui::RichText;
+use super::StudyHelperApp;
+
+/// Most results shown for one search.
+const MAX_SEARCH_RESULTS: usize = 50;
+
+impl StudyHelperApp {
+    /// Search box for the cards of the current class, with clickable results.
+    pub(super) fn card_search_ui(&mut self, ui: &mut egui::Ui, scale: f32) {
+        if !self.storage_configured() {
+            return;
+        }
+        ui.add_space((6.0 * scale).round());
+        ui.label("Search cards:");
+        let response = ui.text_edit_singleline(&mut self.card_search_query)
+            .on_hover_text("Finds saved cards whose question or answer has words starting with the search words");
+        if std::mem::take(&mut self.focus_card_search) {
+            response.request_focus();
+        }
+        if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+            self.run_card_search();
+        }
+        if self.card_search_query.trim().is_empty() {
+            self.card_search_results.clear();
+            return;
+        }
+
+        let mut open: Option<(String, usize)> = None;
+        if !self.card_search_results.is_empty() {
+            egui::ScrollArea::vertical().id_salt("card_search_results").max_height((160.0 * scale).round()).show(ui, |ui_s| {
+                for m in &self.card_search_results {
+                    if ui_s.small_button(format!("{} · {}", m.set_name, m.question)).clicked() {
+                        open = Some((m.set_name.clone(), m.index));
+                    }
+                }
+            });
+            if self.card_search_results.len() == MAX_SEARCH_RESULTS {
+                ui.label(RichText::new(format!("(first {} matches)", MAX_SEARCH_RESULTS)).small());
+            }
+        }
+
+        if let Some((set_name, index)) = open
+            && let Some(set_idx) = self.study_sets.iter().position(|s| s.name() == set_name)
+        {
+            self.selected_set = Some(set_idx);
+            self.current_card_index = index.min(self.study_sets[set_idx].get_all_flashcards().len().saturating_sub(1));
+            self.card_flipped = false;
+            self.show_hint = false;
+        }
+    }
+
+    fn run_card_search(&mut self) {
+        let query = self.card_search_query.trim().to_string();
+        if query.is_empty() {
+            return;
+        }
+        let location = self.storage_location();
+        let class_name = self.storage_class_name.clone();
+        self.run_storage("Searching cards", move || {
+            let result = location.open().and_then(|store| store.search_cards(&class_name, &query, MAX_SEARCH_RESULTS));
+            (query, result)
+        }, |app, (query, result)| {
+            // a newer search replaced this one
+            if query != app.card_search_query.trim() {
+                return;
+            }
+            match result {
+                Ok(matches) => {
+                    if matches.is_empty() {
+                        app.status_message = format!("No cards match '{}'", query);
+                    }
+                    app.card_search_results = matches;
+                }
+                Err(e) => app.status_message = format!("Search error: {}", e),
+            }
+        });
+    }
+}
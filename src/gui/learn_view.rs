@@ -0,0 +1,222 @@
+// src/gui/learn_view.rs
+use eframe::egui;
+use egui::{RichText, TextStyle};
+use crate::keymap::Action;
+use crate::models::{LearnSession, LearnStage};
+use super::StudyHelperApp;
+
+impl StudyHelperApp {
+    /// Start a Learn round over the selected set. Cards the active profile has never
+    /// reviewed come first; a round holds at most `new_cards_per_day` cards (0 = all).
+    /// With `only` given, the round is limited to those card positions.
+    fn start_learn_session(&mut self, only: Option<Vec<usize>>) {
+        let Some(set_idx) = self.selected_set.filter(|i| *i < self.study_sets.len()) else { return };
+        let profile = &self.profiles.active;
+        let mut cards: Vec<(usize, crate::models::Flashcard)> = self.study_sets[set_idx].get_all_flashcards().iter().cloned().enumerate()
+            .filter(|(i, _)| only.as_ref().is_none_or(|o| o.contains(i)))
+            .collect();
+        if self.config.study.shuffle {
+            fastrand::shuffle(&mut cards);
+        }
+        cards.sort_by_key(|(_, card)| card.review(profile).is_some());
+        let limit = self.config.study.new_cards_per_day as usize;
+        if limit > 0 && only.is_none() {
+            cards.truncate(limit);
+        }
+        self.learn_answer.clear();
+        self.learn_session = Some((set_idx, LearnSession::new(cards)));
+    }
+
+    /// Check the typed answer, or move on once the answer has been checked.
+    pub(super) fn learn_submit(&mut self) {
+        let Some((_, session)) = self.learn_session.as_mut() else { return };
+        if session.feedback().is_some() {
+            session.advance();
+            self.learn_answer.clear();
+        } else if session.current().is_some_and(|c| c.stage == LearnStage::Typed) {
+            session.answer(&self.learn_answer, &self.config.study.answer_matching);
+        }
+    }
+
+    /// Whether the Submit shortcut applies to the Learn view.
+    pub(super) fn learn_accepts_submit(&self) -> bool {
+        self.learn_session.as_ref().is_some_and(|(_, s)| {
+            s.feedback().is_some() || s.current().is_some_and(|c| c.stage == LearnStage::Typed)
+        })
+    }
+
+    /// "Study set:" combo box of the Learn and Match views; changing the set
+    /// also moves the flashcard view to its first card.
+    pub(super) fn study_set_picker(&mut self, ui: &mut egui::Ui, id_salt: &str) {
+        ui.horizontal(|ui_h| {
+            ui_h.label("Study set:");
+            if self.study_sets.is_empty() {
+                ui_h.label(RichText::new("(no sets yet)").italics());
+            } else {
+                let labels: Vec<String> = (0..self.study_sets.len()).map(|i| self.set_label(i)).collect();
+                let mut selected = self.selected_set.unwrap_or(0).min(labels.len() - 1);
+                egui::ComboBox::from_id_salt(id_salt).selected_text(&labels[selected]).show_ui(ui_h, |ui_c| {
+                    for (i, label) in labels.iter().enumerate() {
+                        ui_c.selectable_value(&mut selected, i, label);
+                    }
+                });
+                if self.selected_set != Some(selected) {
+                    self.selected_set = Some(selected);
+                    self.current_card_index = 0;
+                    self.card_flipped = false;
+                    self.show_hint = false;
+                }
+            }
+        });
+    }
+
+    /// Guided study of the selected set: multiple choice first, then typed recall.
+    pub(super) fn learn_view(&mut self, ui: &mut egui::Ui, scale: f32) {
+        ui.label(RichText::new("Learn").heading());
+        ui.add_space((6.0 * scale).round());
+
+        self.study_set_picker(ui, "learn_set_select");
+        ui.add_space((8.0 * scale).round());
+
+        // a round belongs to the set it was started on
+        if self.learn_session.as_ref().is_some_and(|(set_idx, _)| Some(*set_idx) != self.selected_set) {
+            self.learn_session = None;
+        }
+        let Some(set_idx) = self.selected_set.filter(|i| *i < self.study_sets.len()) else {
+            ui.label("Choose a study set to learn.");
+            return;
+        };
+
+        let button_size = egui::Vec2::new((120.0 * scale).round(), (36.0 * scale).round());
+        let large = TextStyle::Body.resolve(ui.style()).size * 1.4;
+        let correct_color = crate::theme::success_color(ui.visuals());
+        let wrong_color = ui.visuals().error_fg_color;
+        let submit_key = self.config.shortcuts.binding(Action::Submit).to_string();
+        let mut start: Option<Option<Vec<usize>>> = None;
+        let mut submit = false;
+        let mut end = false;
+
+        match self.learn_session.as_mut() {
+            None => {
+                let cards = self.study_sets[set_idx].get_all_flashcards();
+                if cards.is_empty() {
+                    ui.label("This set has no flashcards.");
+                    return;
+                }
+                let limit = self.config.study.new_cards_per_day as usize;
+                let round = if limit == 0 { cards.len() } else { cards.len().min(limit) };
+                ui.label("Each card is asked as multiple choice first, then you type the answer. Missed cards come back until you know them all.");
+                ui.label(RichText::new(format!("This round: {} of {} cards (new cards per day in Settings).", round, cards.len())).small().weak());
+                ui.add_space((8.0 * scale).round());
+                if ui.add_sized(button_size, egui::Button::new("Start")).clicked() {
+                    start = Some(None);
+                }
+            }
+            Some((_, session)) if session.is_finished() => {
+                let total = session.cards().len();
+                ui.label(RichText::new(format!("Mastered {} of {} cards", session.mastered_count(), total)).size(large));
+                ui.label(format!("{:.0}% of answers were right", session.accuracy() * 100.0));
+                let first_try = session.cards().iter().filter(|c| c.misses == 0).count();
+                ui.label(format!("{} cards without a single miss", first_try));
+                let mut missed: Vec<_> = session.cards().iter().filter(|c| c.misses > 0).collect();
+                missed.sort_by_key(|c| std::cmp::Reverse(c.misses));
+                if !missed.is_empty() {
+                    ui.add_space((8.0 * scale).round());
+                    ui.label(RichText::new("Needed practice:").strong());
+                    egui::ScrollArea::vertical().id_salt("learn_summary").max_height((240.0 * scale).round()).show(ui, |ui_s| {
+                        egui::Grid::new("learn_missed").striped(true).show(ui_s, |ui_g| {
+                            for card in &missed {
+                                ui_g.label(card.card.question());
+                                ui_g.label(card.card.answer());
+                                ui_g.label(RichText::new(format!("missed {}×", card.misses)).color(wrong_color));
+                                ui_g.end_row();
+                            }
+                        });
+                    });
+                }
+                ui.add_space((8.0 * scale).round());
+                ui.horizontal(|ui_h| {
+                    if !missed.is_empty() && ui_h.button("Practice missed cards").clicked() {
+                        start = Some(Some(missed.iter().map(|c| c.set_index).collect()));
+                    }
+                    if ui_h.button("New round").clicked() {
+                        start = Some(None);
+                    }
+                    if ui_h.button("Done").clicked() {
+                        end = true;
+                    }
+                });
+            }
+            Some((_, session)) => {
+                let Some(card) = session.current().cloned() else { return };
+                ui.label(format!("Mastered {} of {}", session.mastered_count(), session.cards().len()));
+                ui.add(egui::ProgressBar::new(session.mastered_count() as f32 / session.cards().len() as f32)
+                    .desired_width((320.0 * scale).round()));
+                ui.add_space((10.0 * scale).round());
+                ui.label(RichText::new(card.card.question()).size(large));
+                ui.add_space((10.0 * scale).round());
+                let feedback = session.feedback().cloned();
+                let mut chosen = None;
+                if card.stage == LearnStage::Choice || (feedback.is_some() && !session.options().is_empty()) {
+                    ui.label(RichText::new("Choose the answer:").weak());
+                    for option in session.options() {
+                        let picked = feedback.as_ref().is_some_and(|f| f.given == *option);
+                        let mut text = RichText::new(option);
+                        if feedback.is_some() && option == card.card.answer() {
+                            text = text.color(correct_color);
+                        } else if picked {
+                            text = text.color(wrong_color);
+                        }
+                        if ui.add_enabled(feedback.is_none(), egui::Button::selectable(picked, text)).clicked() {
+                            chosen = Some(option.clone());
+                        }
+                    }
+                } else {
+                    ui.label(RichText::new("Type the answer:").weak());
+                    let edit = ui.add_enabled(feedback.is_none(), egui::TextEdit::singleline(&mut self.learn_answer).hint_text("Your answer"));
+                    if feedback.is_none() && !edit.has_focus() && self.learn_answer.is_empty() {
+                        edit.request_focus();
+                    }
+                }
+                if let Some(option) = chosen {
+                    session.answer(&option, &self.config.study.answer_matching);
+                }
+
+                ui.add_space((10.0 * scale).round());
+                match &feedback {
+                    Some(f) if f.correct => {
+                        let note = if card.stage == LearnStage::Mastered { "Correct! Card mastered." } else { "Correct! Next time, type it." };
+                        ui.label(RichText::new(note).color(correct_color));
+                    }
+                    Some(_) => {
+                        ui.label(RichText::new(format!("Answer: {}", card.card.answer())).color(wrong_color));
+                        if card.stage == LearnStage::Typed && ui.small_button("I was right").on_hover_text("Count this answer as correct").clicked() {
+                            session.override_correct();
+                        }
+                    }
+                    None => {}
+                }
+                let label = if feedback.is_some() { "Continue" } else { "Check" };
+                let can_submit = feedback.is_some() || card.stage == LearnStage::Typed;
+                ui.horizontal(|ui_h| {
+                    if ui_h.add_enabled(can_submit, egui::Button::new(label).min_size(button_size)).on_hover_text(&submit_key).clicked() {
+                        submit = true;
+                    }
+                    if ui_h.button("End round").clicked() {
+                        end = true;
+                    }
+                });
+            }
+        }
+
+        if submit {
+            self.learn_submit();
+        }
+        if end {
+            self.learn_session = None;
+        }
+        if let Some(only) = start {
+            self.start_learn_session(only);
+        }
+    }
+}
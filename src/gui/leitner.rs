@@ -0,0 +1,80 @@
+// src/gui/leitner.rs
+use eframe::egui;
+use egui::RichText;
+use crate::models::{leitner_box, ReviewAlgorithm, DEFAULT_LEITNER_INTERVALS, LEITNER_BOXES};
+use super::StudyHelperApp;
+
+impl StudyHelperApp {
+    /// How many cards of set `set_idx` are in each Leitner box for the active profile,
+    /// and how many of them are due.
+    pub(super) fn leitner_overview(&self, ui: &mut egui::Ui, set_idx: usize, scale: f32) {
+        let Some(set) = self.study_sets.get(set_idx) else { return };
+        let intervals = &self.config.study.leitner_intervals;
+        let now = crate::storage::unix_timestamp();
+        let mut counts = vec![(0usize, 0usize); intervals.len()];
+        for card in set.get_all_flashcards() {
+            let review = card.review(&self.profiles.active);
+            let (cards, due) = &mut counts[leitner_box(review, intervals.len()) - 1];
+            *cards += 1;
+            *due += review.is_none_or(|r| r.due_at <= now) as usize;
+        }
+        let total = set.get_all_flashcards().len().max(1);
+        egui::CollapsingHeader::new("Leitner boxes").id_salt("leitner_overview").default_open(true).show(ui, |ui_c| {
+            egui::Grid::new("leitner_boxes").num_columns(4).spacing([(12.0 * scale).round(), (4.0 * scale).round()]).show(ui_c, |ui_g| {
+                for heading in ["Box", "Reviewed every", "Cards", "Due now"] {
+                    ui_g.label(RichText::new(heading).strong());
+                }
+                ui_g.end_row();
+                for (n, ((cards, due), days)) in counts.iter().zip(intervals).enumerate() {
+                    ui_g.label(format!("{}", n + 1));
+                    ui_g.label(if *days == 1 { "day".to_string() } else { format!("{} days", days) });
+                    ui_g.add(egui::ProgressBar::new(*cards as f32 / total as f32)
+                        .desired_width((160.0 * scale).round())
+                        .text(cards.to_string()));
+                    ui_g.label(due.to_string());
+                    ui_g.end_row();
+                }
+            });
+            ui_c.label(RichText::new("Cards never graded start in box 1. \"Got it\" moves a card up a box, \"Missed it\" sends it back to box 1.").small().weak());
+        });
+    }
+
+    /// Scheduler choice and, for the Leitner system, the interval of every box.
+    pub(super) fn review_algorithm_ui(&mut self, ui: &mut egui::Ui) {
+        let study = &mut self.config.study;
+        ui.horizontal(|ui_h| {
+            ui_h.label("Review scheduling:");
+            egui::ComboBox::from_id_salt("review_algorithm").selected_text(study.algorithm.label()).show_ui(ui_h, |ui_c| {
+                for algorithm in ReviewAlgorithm::ALL {
+                    ui_c.selectable_value(&mut study.algorithm, algorithm, algorithm.label());
+                }
+            });
+        });
+        if study.algorithm != ReviewAlgorithm::Leitner {
+            return;
+        }
+        let intervals = &mut study.leitner_intervals;
+        for (n, days) in intervals.iter_mut().enumerate() {
+            ui.horizontal(|ui_h| {
+                ui_h.label(format!("Box {}: review every", n + 1));
+                ui_h.add(egui::DragValue::new(days).range(1..=3650));
+                ui_h.label("days");
+            });
+        }
+        ui.horizontal(|ui_h| {
+            if ui_h.add_enabled(intervals.len() < *LEITNER_BOXES.end(), egui::Button::new("Add box").small()).clicked() {
+                let last = intervals.last().copied().unwrap_or(1);
+                intervals.push(last.saturating_mul(2).min(3650));
+            }
+            if ui_h.add_enabled(intervals.len() > *LEITNER_BOXES.start(), egui::Button::new("Remove last box").small())
+                .on_hover_text("Cards in the last box move to the one before it")
+                .clicked()
+            {
+                intervals.pop();
+            }
+            if ui_h.small_button("Reset boxes").clicked() {
+                *intervals = DEFAULT_LEITNER_INTERVALS.to_vec();
+            }
+        });
+    }
+}
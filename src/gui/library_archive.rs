@@ -0,0 +1,186 @@
+// src/gui/library_archive.rs
+use std::collections::HashMap;
+use std::path::PathBuf;
+use eframe::egui;
+use egui::RichText;
+use rfd::FileDialog;
+use crate::storage::{ArchivedSet, ConflictPolicy, ARCHIVE_EXTENSION};
+use super::StudyHelperApp;
+
+/// An archive the user picked for import, shown with its conflicts before anything is written.
+pub(super) struct ArchivePreview {
+    path: PathBuf,
+    sets: Vec<ArchivedSet>,
+    /// Policy per entry of `sets`; only used for conflicting ones.
+    policies: Vec<ConflictPolicy>,
+}
+
+impl StudyHelperApp {
+    /// Archive path field and the export / import buttons of the class manager.
+    pub(super) fn archive_controls(&mut self, ui: &mut egui::Ui) {
+        ui.label(RichText::new("Archives").strong());
+        ui.horizontal(|ui_h| {
+            ui_h.label("Archive file:");
+            ui_h.text_edit_singleline(&mut self.archive_path);
+            if ui_h.small_button("Browse").clicked()
+                && let Some(f) = FileDialog::new().add_filter("Study Helper archive", &[ARCHIVE_EXTENSION]).pick_file()
+                && let Some(s) = f.to_str()
+            {
+                self.archive_path = s.to_string();
+            }
+        });
+        let ready = !self.storage_base_path.trim().is_empty();
+        ui.horizontal(|ui_h| {
+            if ui_h.add_enabled(ready && !self.available_classes.is_empty(), egui::Button::new("Export library"))
+                .on_hover_text("Write every class and set into one archive file")
+                .clicked()
+            {
+                let classes = self.available_classes.clone();
+                self.export_archive(classes, "library");
+            }
+            if ui_h.add_enabled(ready && !self.archive_path.trim().is_empty(), egui::Button::new("Import archive…"))
+                .on_hover_text("Merge a class or library archive into your classes")
+                .clicked()
+            {
+                self.preview_archive_import();
+            }
+        });
+    }
+
+    /// Export the stored sets of `class_names` to the archive path, asking for one if it is empty.
+    pub(super) fn export_archive(&mut self, class_names: Vec<String>, default_name: &str) {
+        let mut dst = if self.archive_path.trim().is_empty() {
+            let picked = FileDialog::new()
+                .add_filter("Study Helper archive", &[ARCHIVE_EXTENSION])
+                .set_file_name(format!("{}.{}", default_name, ARCHIVE_EXTENSION))
+                .save_file();
+            match picked {
+                Some(p) => p,
+                None => return,
+            }
+        } else {
+            PathBuf::from(self.archive_path.trim())
+        };
+        if dst.extension().is_none_or(|e| !e.eq_ignore_ascii_case(ARCHIVE_EXTENSION)) {
+            dst.as_mut_os_string().push(format!(".{}", ARCHIVE_EXTENSION));
+        }
+        // the archive holds the stored sets, so pending edits go first
+        self.save_dirty_sets();
+        let location = self.storage_location();
+        self.run_storage("Exporting archive", move || {
+            let result = location.open().and_then(|store| crate::storage::export_archive(store.as_ref(), &location.base_dir, &class_names, &dst));
+            (dst, result)
+        }, |app, (dst, result)| match result {
+            Ok(summary) => {
+                app.status_message = format!("Exported {} sets in {} classes to {}", summary.sets, summary.classes, dst.display());
+                app.archive_path = dst.display().to_string();
+            }
+            Err(e) => app.status_message = format!("Archive export error: {}", e),
+        });
+    }
+
+    /// Read the archive's contents and open the import window with its conflicts.
+    fn preview_archive_import(&mut self) {
+        let location = self.storage_location();
+        let src = PathBuf::from(self.archive_path.trim());
+        self.run_storage("Reading archive", move || {
+            let result = location.open().and_then(|store| crate::storage::preview_archive(store.as_ref(), &src));
+            (src, result)
+        }, |app, (path, result)| match result {
+            Ok(sets) if sets.is_empty() => app.status_message = "The archive contains no sets".to_string(),
+            Ok(sets) => {
+                let policies = vec![app.archive_default_policy; sets.len()];
+                app.archive_preview = Some(ArchivePreview { path, sets, policies });
+            }
+            Err(e) => app.status_message = format!("Archive import error: {}", e),
+        });
+    }
+
+    /// Lists what an archive import would add and lets the user pick a policy per conflicting set.
+    pub(super) fn archive_import_window(&mut self, ctx: &egui::Context, scale: f32) {
+        let Some(preview) = self.archive_preview.as_mut() else { return };
+        let mut import = false;
+        let mut cancel = false;
+        let new_sets = preview.sets.iter().filter(|s| !s.conflict).count();
+        let conflicts = preview.sets.len() - new_sets;
+
+        egui::Window::new("Import Archive").collapsible(false).resizable(true).order(egui::Order::Foreground).show(ctx, |ui_win| {
+            ui_win.label(format!("{}: {} new sets, {} already exist.", preview.path.display(), new_sets, conflicts));
+            if conflicts > 0 {
+                ui_win.horizontal(|ui_h| {
+                    ui_h.label("For all existing sets:");
+                    for policy in ConflictPolicy::ALL {
+                        if ui_h.small_button(policy.label()).clicked() {
+                            self.archive_default_policy = policy;
+                            for (p, set) in preview.policies.iter_mut().zip(&preview.sets) {
+                                if set.conflict {
+                                    *p = policy;
+                                }
+                            }
+                        }
+                    }
+                });
+                egui::ScrollArea::vertical().max_height((240.0 * scale).round()).show(ui_win, |ui_s| {
+                    egui::Grid::new("archive_conflicts_grid").striped(true).show(ui_s, |ui_g| {
+                        for (i, set) in preview.sets.iter().enumerate().filter(|(_, s)| s.conflict) {
+                            ui_g.label(format!("{} / {}", set.class_name, set.set_name));
+                            egui::ComboBox::from_id_salt(("archive_policy", i))
+                                .selected_text(preview.policies[i].label())
+                                .show_ui(ui_g, |ui_c| {
+                                    for policy in ConflictPolicy::ALL {
+                                        ui_c.selectable_value(&mut preview.policies[i], policy, policy.label());
+                                    }
+                                });
+                            ui_g.end_row();
+                        }
+                    });
+                });
+            }
+            ui_win.add_space((6.0 * scale).round());
+            ui_win.horizontal(|ui_h| {
+                if ui_h.button("Import").clicked() {
+                    import = true;
+                }
+                if ui_h.button("Cancel").clicked() {
+                    cancel = true;
+                }
+            });
+        });
+
+        if import && let Some(preview) = self.archive_preview.take() {
+            self.run_archive_import(preview);
+        } else if cancel {
+            self.archive_preview = None;
+        }
+    }
+
+    fn run_archive_import(&mut self, preview: ArchivePreview) {
+        // overwritten sets may be loaded, so save pending edits and reload afterwards
+        self.flush_dirty_sets_before_reload();
+        let policies: HashMap<(String, String), ConflictPolicy> = preview.sets.into_iter().zip(preview.policies)
+            .filter(|(set, _)| set.conflict)
+            .map(|(set, policy)| ((set.class_name, set.set_name), policy))
+            .collect();
+        let default_policy = self.archive_default_policy;
+        let location = self.storage_location();
+        let src = preview.path;
+        self.run_storage("Importing archive", move || {
+            location.open().and_then(|store| crate::storage::import_archive(store.as_ref(), &location.base_dir, &src, |class_name, set_name| {
+                policies.get(&(class_name.to_string(), set_name.to_string())).copied().unwrap_or(default_policy)
+            }))
+        }, |app, result| {
+            match result {
+                Ok(report) => {
+                    app.status_message = format!("Archive imported: {} added, {} overwritten, {} kept both, {} skipped",
+                        report.added, report.overwritten, report.kept_both, report.skipped);
+                    if !report.failed.is_empty() {
+                        app.status_message.push_str(&format!("; {} failed: {}", report.failed.len(), report.failed.join("; ")));
+                    }
+                }
+                Err(e) => app.status_message = format!("Archive import error: {}", e),
+            }
+            app.last_storage_class_name.clear();
+            app.request_class_list();
+        });
+    }
+}
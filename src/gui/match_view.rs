@@ -0,0 +1,184 @@
+// src/gui/match_view.rs
+use std::time::{Duration, Instant};
+use eframe::egui;
+use egui::{RichText, TextStyle};
+use crate::models::{MatchClick, MatchGame, MatchScore, MATCH_PENALTY};
+use super::StudyHelperApp;
+
+/// How long a wrong pair stays highlighted.
+const WRONG_FLASH: Duration = Duration::from_millis(600);
+/// Tiles per row of the grid.
+const TILE_COLUMNS: usize = 4;
+
+/// A Match game on the set it was started with.
+pub(super) struct MatchRound {
+    set_idx: usize,
+    game: MatchGame,
+    /// The last wrong pair and when it was clicked.
+    wrong: Option<(usize, usize, Instant)>,
+    /// Leaderboard place of the finished game; `None` until it is recorded.
+    place: Option<Option<usize>>,
+}
+
+fn format_time(time: Duration) -> String {
+    format!("{:.1} s", time.as_secs_f32())
+}
+
+impl StudyHelperApp {
+    fn start_match_game(&mut self, set_idx: usize) {
+        let game = MatchGame::new(self.study_sets[set_idx].get_all_flashcards());
+        self.match_round = Some(MatchRound { set_idx, game, wrong: None, place: None });
+    }
+
+    /// Put the finished game on its set's leaderboard; autosave stores it with the set.
+    fn record_match_game(&mut self) {
+        let Some(round) = self.match_round.as_mut().filter(|r| r.place.is_none()) else { return };
+        let Some(score) = round.game.score(&self.profiles.active, crate::storage::unix_timestamp()) else { return };
+        let Some(set) = self.study_sets.get_mut(round.set_idx) else { return };
+        round.place = Some(set.record_match_score(score));
+        let set_idx = round.set_idx;
+        self.mark_dirty(set_idx);
+    }
+
+    /// Timed tile matching over the selected set, with its best times.
+    pub(super) fn match_view(&mut self, ui: &mut egui::Ui, scale: f32) {
+        ui.label(RichText::new("Match").heading());
+        ui.add_space((6.0 * scale).round());
+
+        self.study_set_picker(ui, "match_set_select");
+        ui.add_space((8.0 * scale).round());
+
+        if self.match_round.as_ref().is_some_and(|r| Some(r.set_idx) != self.selected_set) {
+            self.match_round = None;
+        }
+        let Some(set_idx) = self.selected_set.filter(|i| *i < self.study_sets.len()) else {
+            ui.label("Choose a study set to play.");
+            return;
+        };
+        if MatchGame::pair_count(self.study_sets[set_idx].get_all_flashcards()) < 2 {
+            ui.label("Match needs a set with at least two cards with different questions and answers.");
+            return;
+        }
+
+        let large = TextStyle::Body.resolve(ui.style()).size * 1.4;
+        let button_size = egui::Vec2::new((120.0 * scale).round(), (36.0 * scale).round());
+        let mut start = false;
+        let mut end = false;
+
+        match self.match_round.as_mut() {
+            None => {
+                ui.label(format!(
+                    "Click a question and its answer to clear them. Every wrong pair adds {} to your time.",
+                    format_time(MATCH_PENALTY)
+                ));
+                ui.add_space((8.0 * scale).round());
+                if ui.add_sized(button_size, egui::Button::new("Start")).clicked() {
+                    start = true;
+                }
+            }
+            Some(round) if round.game.is_finished() => {
+                ui.label(RichText::new(format!("Done in {}", format_time(round.game.elapsed()))).size(large));
+                ui.label(format!("{} pairs, {} wrong", round.game.pairs(), round.game.mistakes()));
+                match round.place {
+                    Some(Some(1)) => { ui.label(RichText::new("New best time!").color(crate::theme::success_color(ui.visuals()))); }
+                    Some(Some(place)) => { ui.label(format!("Place {} on the leaderboard", place)); }
+                    _ => {}
+                }
+                ui.add_space((8.0 * scale).round());
+                ui.horizontal(|ui_h| {
+                    if ui_h.add_sized(button_size, egui::Button::new("Play again")).clicked() {
+                        start = true;
+                    }
+                    if ui_h.add_sized(button_size, egui::Button::new("Done")).clicked() {
+                        end = true;
+                    }
+                });
+            }
+            Some(round) => {
+                // keep the clock ticking
+                ui.ctx().request_repaint_after(Duration::from_millis(100));
+                if round.wrong.is_some_and(|(_, _, at)| at.elapsed() > WRONG_FLASH) {
+                    round.wrong = None;
+                }
+                ui.horizontal(|ui_h| {
+                    ui_h.label(RichText::new(format_time(round.game.elapsed())).size(large).monospace());
+                    if round.game.mistakes() > 0 {
+                        let penalty = MATCH_PENALTY * round.game.mistakes();
+                        ui_h.label(RichText::new(format!("+{} penalty", format_time(penalty))).color(ui_h.visuals().error_fg_color));
+                    }
+                });
+                ui.add_space((8.0 * scale).round());
+
+                let tile_size = egui::Vec2::new((170.0 * scale).round(), (72.0 * scale).round());
+                let wrong_fill = ui.visuals().error_fg_color.gamma_multiply(0.35);
+                let mut clicked = None;
+                egui::Grid::new("match_tiles").spacing([(8.0 * scale).round(), (8.0 * scale).round()]).show(ui, |ui_g| {
+                    for (i, tile) in round.game.tiles().iter().enumerate() {
+                        if tile.cleared {
+                            ui_g.allocate_space(tile_size);
+                        } else {
+                            let mut button = egui::Button::selectable(round.game.selected() == Some(i), RichText::new(&tile.text)).wrap();
+                            if round.wrong.is_some_and(|(a, b, _)| a == i || b == i) {
+                                button = button.fill(wrong_fill);
+                            }
+                            if ui_g.add_sized(tile_size, button).clicked() {
+                                clicked = Some(i);
+                            }
+                        }
+                        if (i + 1) % TILE_COLUMNS == 0 {
+                            ui_g.end_row();
+                        }
+                    }
+                });
+                if let Some(i) = clicked {
+                    if let MatchClick::Wrong(a, b) = round.game.click(i) {
+                        round.wrong = Some((a, b, Instant::now()));
+                    } else {
+                        round.wrong = None;
+                    }
+                }
+                ui.add_space((8.0 * scale).round());
+                if ui.button("Give up").clicked() {
+                    end = true;
+                }
+            }
+        }
+
+        if end {
+            self.match_round = None;
+        }
+        if start {
+            self.start_match_game(set_idx);
+        }
+        self.record_match_game();
+
+        ui.add_space((12.0 * scale).round());
+        let pairs = self.match_round.as_ref()
+            .map_or_else(|| MatchGame::pair_count(self.study_sets[set_idx].get_all_flashcards()), |r| r.game.pairs()) as u32;
+        leaderboard(ui, self.study_sets[set_idx].match_scores(), pairs, scale);
+    }
+}
+
+/// Best times of games with `pairs` pairs.
+fn leaderboard(ui: &mut egui::Ui, scores: &[MatchScore], pairs: u32, scale: f32) {
+    ui.label(RichText::new(format!("Best times ({} pairs)", pairs)).strong());
+    let scores: Vec<&MatchScore> = scores.iter().filter(|s| s.pairs == pairs).collect();
+    if scores.is_empty() {
+        ui.label(RichText::new("No games yet.").italics());
+        return;
+    }
+    egui::Grid::new("match_leaderboard").striped(true).spacing([(12.0 * scale).round(), (4.0 * scale).round()]).show(ui, |ui_g| {
+        for heading in ["#", "Time", "Wrong", "Profile", "Played"] {
+            ui_g.label(RichText::new(heading).strong());
+        }
+        ui_g.end_row();
+        for (place, score) in scores.iter().enumerate() {
+            ui_g.label((place + 1).to_string());
+            ui_g.label(format_time(Duration::from_millis(score.time_ms)));
+            ui_g.label(score.mistakes.to_string());
+            ui_g.label(&score.profile);
+            ui_g.label(crate::storage::format_timestamp(score.played_at));
+            ui_g.end_row();
+        }
+    });
+}
@@ -0,0 +1,168 @@
+// src/gui/outline_import.rs
+use eframe::egui;
+use egui::RichText;
+use rfd::FileDialog;
+use crate::formats::{OutlineParse, OutlineStyle};
+use crate::history::EditCommand;
+use crate::models::Flashcard;
+use super::StudyHelperApp;
+
+/// Preview rows shown before the list is cut off.
+pub(super) const MAX_PREVIEW_ROWS: usize = 200;
+
+impl StudyHelperApp {
+    /// Re-parse the pasted outline with the current options.
+    fn refresh_outline_preview(&mut self) {
+        self.outline_preview = crate::formats::parse_outline(&self.outline_text, &self.config.outline);
+    }
+
+    /// Read an outline file on the storage worker and put its text in the import window.
+    fn load_outline_file(&mut self, path: std::path::PathBuf) {
+        self.run_storage("Reading outline", move || {
+            let result = std::fs::read_to_string(&path);
+            (path, result)
+        }, |app, (path, result)| match result {
+            Ok(text) => {
+                app.outline_text = text;
+                app.refresh_outline_preview();
+            }
+            Err(e) => app.status_message = format!("Cannot read {}: {}", path.display(), e),
+        });
+    }
+
+    /// Window for pasting or loading a Markdown / plain-text outline, previewing the
+    /// cards it yields and adding them to the selected set (as one undo step).
+    pub(super) fn outline_import_window(&mut self, ctx: &egui::Context, scale: f32) {
+        let before = self.config.outline.clone();
+        let mut changed = false;
+        let mut add = false;
+        let mut export = false;
+        let mut close = false;
+        let set_name = self.selected_set.and_then(|i| self.study_sets.get(i)).map(|s| s.name().to_string());
+
+        egui::Window::new("Import Cards from Outline").collapsible(false).resizable(true).show(ctx, |ui_win| {
+            ui_win.horizontal(|ui_h| {
+                ui_h.label("Format:");
+                egui::ComboBox::from_id_salt("outline_style").selected_text(self.config.outline.style.label()).show_ui(ui_h, |ui_c| {
+                    for style in OutlineStyle::ALL {
+                        ui_c.selectable_value(&mut self.config.outline.style, style, style.label());
+                    }
+                });
+                if self.config.outline.style == OutlineStyle::Separator {
+                    ui_h.label("Separator:");
+                    ui_h.add(egui::TextEdit::singleline(&mut self.config.outline.separator).desired_width((60.0 * scale).round()));
+                }
+                if ui_h.small_button("Load file…").clicked()
+                    && let Some(path) = FileDialog::new().add_filter("Outline", &["md", "markdown", "txt"]).pick_file()
+                {
+                    self.load_outline_file(path);
+                }
+            });
+            let hint = match self.config.outline.style {
+                OutlineStyle::Separator => format!("term {0} definition {0} optional hint", self.config.outline.separator.trim()),
+                OutlineStyle::QaBlocks => "Q: question\nA: answer\nH: optional hint".to_string(),
+                OutlineStyle::Headings => "## Question\nAnswer text below the heading".to_string(),
+            };
+            egui::ScrollArea::vertical().id_salt("outline_text").max_height((180.0 * scale).round()).show(ui_win, |ui_s| {
+                changed |= ui_s.add(egui::TextEdit::multiline(&mut self.outline_text).hint_text(hint).desired_rows(8).desired_width(f32::INFINITY)).changed();
+            });
+
+            let OutlineParse { cards, skipped } = &self.outline_preview;
+            ui_win.label(RichText::new(format!("Preview: {} cards", cards.len())).strong());
+            egui::ScrollArea::vertical().id_salt("outline_preview").max_height((200.0 * scale).round()).show(ui_win, |ui_s| {
+                egui::Grid::new("outline_preview_grid").striped(true).show(ui_s, |ui_g| {
+                    for card in cards.iter().take(MAX_PREVIEW_ROWS) {
+                        ui_g.label(card.question());
+                        ui_g.label(card.answer());
+                        ui_g.label(RichText::new(card.get_hints().join(" / ")).small());
+                        ui_g.end_row();
+                    }
+                });
+                if cards.len() > MAX_PREVIEW_ROWS {
+                    ui_s.label(RichText::new(format!("… and {} more", cards.len() - MAX_PREVIEW_ROWS)).italics());
+                }
+            });
+            if !skipped.is_empty() {
+                ui_win.collapsing(format!("{} lines skipped", skipped.len()), |ui_c| {
+                    for (line, reason) in skipped.iter().take(MAX_PREVIEW_ROWS) {
+                        ui_c.label(RichText::new(format!("line {}: {}", line, reason)).small());
+                    }
+                });
+            }
+
+            ui_win.add_space((6.0 * scale).round());
+            ui_win.horizontal(|ui_h| {
+                let target = set_name.as_deref().unwrap_or("no set selected");
+                if ui_h.add_enabled(set_name.is_some() && !cards.is_empty(), egui::Button::new(format!("Add {} cards to '{}'", cards.len(), target))).clicked() {
+                    add = true;
+                }
+                if ui_h.add_enabled(set_name.is_some(), egui::Button::new("Export set as outline…"))
+                    .on_hover_text("Save the selected set's cards in this format")
+                    .clicked()
+                {
+                    export = true;
+                }
+                if ui_h.button("Close").clicked() {
+                    close = true;
+                }
+            });
+        });
+
+        if changed || self.config.outline != before {
+            self.refresh_outline_preview();
+        }
+        if self.config.outline != before {
+            self.persist_config();
+        }
+        if add {
+            self.add_outline_cards();
+        } else if export {
+            self.export_outline();
+        } else if close {
+            self.show_outline_import = false;
+        }
+    }
+
+    /// Append the previewed cards to the selected set.
+    fn add_outline_cards(&mut self) {
+        let Some(idx) = self.selected_set.filter(|i| *i < self.study_sets.len()) else { return };
+        let cards = std::mem::take(&mut self.outline_preview.cards);
+        self.append_cards(idx, cards);
+        self.outline_text.clear();
+        self.outline_preview = OutlineParse::default();
+        self.show_outline_import = false;
+    }
+
+    /// Add imported cards to the end of set `idx` as one undo step and save it.
+    pub(super) fn append_cards(&mut self, idx: usize, cards: Vec<Flashcard>) {
+        let Some(set) = self.study_sets.get_mut(idx) else { return };
+        let index = set.get_all_flashcards().len();
+        for card in &cards {
+            set.add_flashcard(card.clone());
+        }
+        self.status_message = format!("Added {} cards to '{}'", cards.len(), set.name());
+        self.history.record(EditCommand::AddCards { set: set.name().to_string(), index, cards });
+        self.current_card_index = index;
+        self.persist_set(idx);
+    }
+
+    /// Save the selected set's cards as an outline in the chosen format.
+    fn export_outline(&mut self) {
+        let Some(set) = self.selected_set.and_then(|i| self.study_sets.get(i)) else { return };
+        let extension = if self.config.outline.style == OutlineStyle::Separator { "txt" } else { "md" };
+        let picked = FileDialog::new()
+            .add_filter("Outline", &[extension])
+            .set_file_name(format!("{}.{}", sanitize_filename::sanitize(set.name()), extension))
+            .save_file();
+        let Some(dst) = picked else { return };
+        let text = crate::formats::cards_to_outline(set.get_all_flashcards(), &self.config.outline);
+        let count = set.get_all_flashcards().len();
+        self.run_storage("Exporting outline", move || {
+            let result = crate::storage::write_atomic(&dst, text.as_bytes());
+            (dst, result)
+        }, move |app, (dst, result)| match result {
+            Ok(()) => app.status_message = format!("Saved {} cards to {}", count, dst.display()),
+            Err(e) => app.status_message = format!("Export error: {}", e),
+        });
+    }
+}
@@ -0,0 +1,132 @@
+// src/gui/paste_import.rs
+use eframe::egui;
+use egui::RichText;
+use crate::formats::{OutlineParse, TextSeparator};
+use crate::models::StudySet as AppStudySet;
+use super::outline_import::MAX_PREVIEW_ROWS;
+use super::StudyHelperApp;
+
+/// Separator picker; a custom separator is edited with `\t` and `\n` written out.
+fn separator_picker(ui: &mut egui::Ui, id: &str, label: &str, separator: &mut TextSeparator, custom: &mut String, scale: f32) {
+    ui.horizontal(|ui_h| {
+        ui_h.label(label);
+        egui::ComboBox::from_id_salt(id).selected_text(separator.label()).show_ui(ui_h, |ui_c| {
+            for choice in TextSeparator::ALL {
+                ui_c.selectable_value(separator, choice, choice.label());
+            }
+        });
+        if *separator == TextSeparator::Custom {
+            let mut shown = custom.replace('\t', "\\t").replace('\n', "\\n");
+            let edit = egui::TextEdit::singleline(&mut shown).desired_width((80.0 * scale).round());
+            if ui_h.add(edit).on_hover_text("Write \\t for a tab and \\n for a line break").changed() {
+                *custom = shown.replace("\\t", "\t").replace("\\n", "\n");
+            }
+        }
+    });
+}
+
+impl StudyHelperApp {
+    /// Window for pasting text exported from Quizlet, with a live preview, that
+    /// appends the cards to the selected set or creates a new set from them.
+    pub(super) fn paste_import_window(&mut self, ctx: &egui::Context, scale: f32) {
+        let before = self.config.quizlet.clone();
+        let mut changed = false;
+        let mut apply = false;
+        let mut close = false;
+        let set_name = self.selected_set.and_then(|i| self.study_sets.get(i)).map(|s| s.name().to_string());
+        if set_name.is_none() {
+            self.paste_new_set = true;
+        }
+
+        egui::Window::new("Paste Cards from Quizlet").collapsible(false).resizable(true).show(ctx, |ui_win| {
+            ui_win.label(RichText::new("In Quizlet choose Export, copy the text and paste it below.").small());
+            let options = &mut self.config.quizlet;
+            separator_picker(ui_win, "paste_term_sep", "Between term and definition:", &mut options.term_separator, &mut options.custom_term_separator, scale);
+            separator_picker(ui_win, "paste_card_sep", "Between cards:", &mut options.card_separator, &mut options.custom_card_separator, scale);
+            egui::ScrollArea::vertical().id_salt("paste_text").max_height((180.0 * scale).round()).show(ui_win, |ui_s| {
+                let edit = egui::TextEdit::multiline(&mut self.paste_text).hint_text("term\tdefinition").desired_rows(8).desired_width(f32::INFINITY);
+                changed |= ui_s.add(edit).changed();
+            });
+
+            let OutlineParse { cards, skipped } = &self.paste_preview;
+            ui_win.label(RichText::new(format!("Preview: {} cards", cards.len())).strong());
+            egui::ScrollArea::vertical().id_salt("paste_preview").max_height((200.0 * scale).round()).show(ui_win, |ui_s| {
+                egui::Grid::new("paste_preview_grid").striped(true).show(ui_s, |ui_g| {
+                    for card in cards.iter().take(MAX_PREVIEW_ROWS) {
+                        ui_g.label(card.question());
+                        ui_g.label(card.answer());
+                        ui_g.end_row();
+                    }
+                });
+                if cards.len() > MAX_PREVIEW_ROWS {
+                    ui_s.label(RichText::new(format!("… and {} more", cards.len() - MAX_PREVIEW_ROWS)).italics());
+                }
+            });
+            if !skipped.is_empty() {
+                ui_win.collapsing(format!("{} entries skipped", skipped.len()), |ui_c| {
+                    for (card, reason) in skipped.iter().take(MAX_PREVIEW_ROWS) {
+                        ui_c.label(RichText::new(format!("card {}: {}", card, reason)).small());
+                    }
+                });
+            }
+
+            ui_win.add_space((6.0 * scale).round());
+            ui_win.horizontal(|ui_h| {
+                if let Some(name) = &set_name {
+                    ui_h.radio_value(&mut self.paste_new_set, false, format!("Add to '{}'", name));
+                }
+                ui_h.radio_value(&mut self.paste_new_set, true, "Create a new set:");
+                ui_h.add_enabled(self.paste_new_set, egui::TextEdit::singleline(&mut self.paste_set_name).hint_text("Set name"));
+            });
+            ui_win.horizontal(|ui_h| {
+                let ready = !cards.is_empty() && (!self.paste_new_set || !self.paste_set_name.trim().is_empty());
+                let label = if self.paste_new_set { format!("Create set with {} cards", cards.len()) } else { format!("Add {} cards", cards.len()) };
+                if ui_h.add_enabled(ready, egui::Button::new(label)).clicked() {
+                    apply = true;
+                }
+                if ui_h.button("Close").clicked() {
+                    close = true;
+                }
+            });
+        });
+
+        if changed || self.config.quizlet != before {
+            self.paste_preview = crate::formats::parse_quizlet(&self.paste_text, &self.config.quizlet);
+        }
+        if self.config.quizlet != before {
+            self.persist_config();
+        }
+        if apply {
+            self.apply_paste_import();
+        } else if close {
+            self.show_paste_import = false;
+        }
+    }
+
+    fn apply_paste_import(&mut self) {
+        if self.paste_new_set {
+            let name = self.paste_set_name.trim().to_string();
+            if self.set_name_taken(&name, None) {
+                self.status_message = format!("A set named '{}' (or one with the same file name) already exists", name);
+                return;
+            }
+            let mut set = AppStudySet::new(name.clone());
+            for card in std::mem::take(&mut self.paste_preview.cards) {
+                set.add_flashcard(card);
+            }
+            self.status_message = format!("Created set '{}' with {} cards", name, set.get_all_flashcards().len());
+            self.study_sets.push(set);
+            self.selected_set = Some(self.study_sets.len() - 1);
+            self.current_card_index = 0;
+            self.persist_set(self.study_sets.len() - 1);
+        } else {
+            let Some(idx) = self.selected_set.filter(|i| *i < self.study_sets.len()) else { return };
+            let cards = std::mem::take(&mut self.paste_preview.cards);
+            self.append_cards(idx, cards);
+        }
+        self.paste_text.clear();
+        self.paste_set_name.clear();
+        self.paste_preview = OutlineParse::default();
+        self.show_paste_import = false;
+    }
+}
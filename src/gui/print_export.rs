@@ -0,0 +1,85 @@
+// src/gui/print_export.rs
+use std::path::PathBuf;
+use eframe::egui;
+use rfd::FileDialog;
+use crate::formats::{DuplexFlip, PaperSize, MAX_CARDS_PER_SIDE};
+use super::StudyHelperApp;
+
+/// Ask where to save a PDF, suggesting `file_name`.
+fn pick_pdf_destination(file_name: &str) -> Option<PathBuf> {
+    let mut path = FileDialog::new().add_filter("PDF", &["pdf"]).set_file_name(file_name).save_file()?;
+    if path.extension().is_none_or(|e| !e.eq_ignore_ascii_case("pdf")) {
+        path.set_extension("pdf");
+    }
+    Some(path)
+}
+
+impl StudyHelperApp {
+    /// Card layout options and the button that prints the selected set's cards.
+    pub(super) fn print_controls(&mut self, ui: &mut egui::Ui) {
+        let before = self.config.card_sheet.clone();
+        let has_set = self.selected_set.is_some();
+        let mut export = false;
+        let sheet = &mut self.config.card_sheet;
+        ui.horizontal(|ui_h| {
+            ui_h.label("Cards per page:");
+            ui_h.add(egui::DragValue::new(&mut sheet.columns).range(1..=MAX_CARDS_PER_SIDE));
+            ui_h.label("×");
+            ui_h.add(egui::DragValue::new(&mut sheet.rows).range(1..=MAX_CARDS_PER_SIDE));
+            ui_h.checkbox(&mut sheet.cut_lines, "Cut lines");
+        });
+        ui.horizontal(|ui_h| {
+            egui::ComboBox::from_id_salt("print_paper").selected_text(sheet.paper.label()).show_ui(ui_h, |ui_c| {
+                for paper in PaperSize::ALL {
+                    ui_c.selectable_value(&mut sheet.paper, paper, paper.label());
+                }
+            });
+            egui::ComboBox::from_id_salt("print_flip").selected_text(sheet.flip.label()).show_ui(ui_h, |ui_c| {
+                for flip in DuplexFlip::ALL {
+                    ui_c.selectable_value(&mut sheet.flip, flip, flip.label());
+                }
+            });
+            export = ui_h.add_enabled(has_set, egui::Button::new("Export cards PDF"))
+                .on_hover_text("Questions on the front pages, answers on the back pages; print double-sided with the same flip setting")
+                .clicked();
+        });
+        if export {
+            self.export_cards_pdf();
+        }
+        if self.config.card_sheet != before {
+            self.persist_config();
+        }
+    }
+
+    /// Render the selected set's cards to a PDF chosen by the user.
+    fn export_cards_pdf(&mut self) {
+        let Some(set) = self.selected_set.and_then(|i| self.study_sets.get(i)).cloned() else { return };
+        let Some(dst) = pick_pdf_destination(&format!("{}.pdf", sanitize_filename::sanitize(set.name()))) else { return };
+        let options = self.config.card_sheet.clone();
+        self.run_storage(format!("Printing '{}'", set.name()), move || {
+            let result = crate::formats::flashcards_pdf(&set, &options).and_then(|bytes| crate::storage::write_atomic(&dst, &bytes));
+            (dst, result)
+        }, |app, (dst, result)| match result {
+            Ok(()) => app.status_message = format!("Saved cards PDF to {}", dst.display()),
+            Err(e) => app.status_message = format!("PDF export error: {}", e),
+        });
+    }
+
+    /// Write a quiz as an exam sheet plus a separate answer key next to it.
+    pub(super) fn export_quiz_pdfs(&mut self, set_idx: usize, quiz_idx: usize) {
+        let Some(quiz) = self.study_sets.get(set_idx).and_then(|s| s.get_all_quizzes().get(quiz_idx)).cloned() else { return };
+        let Some(exam) = pick_pdf_destination(&format!("{}.pdf", sanitize_filename::sanitize(quiz.title()))) else { return };
+        let stem = exam.file_stem().and_then(|s| s.to_str()).unwrap_or("quiz").to_string();
+        let key = exam.with_file_name(format!("{} - answer key.pdf", stem));
+        self.run_storage(format!("Printing '{}'", quiz.title()), move || {
+            let result = crate::formats::quiz_pdf(&quiz, false)
+                .and_then(|bytes| crate::storage::write_atomic(&exam, &bytes))
+                .and_then(|()| crate::formats::quiz_pdf(&quiz, true))
+                .and_then(|bytes| crate::storage::write_atomic(&key, &bytes));
+            (exam, key, result)
+        }, |app, (exam, key, result)| match result {
+            Ok(()) => app.status_message = format!("Saved exam to {} and answer key to {}", exam.display(), key.display()),
+            Err(e) => app.status_message = format!("PDF export error: {}", e),
+        });
+    }
+}
@@ -0,0 +1,156 @@
+// src/gui/profiles.rs
+use eframe::egui;
+use egui::RichText;
+use crate::history::EditHistory;
+use crate::models::DEFAULT_PROFILE;
+use crate::storage::{load_config, save_config, Config, CONFIG_VERSION};
+use super::StudyHelperApp;
+
+/// Read and validate the config of `profile`. Returns the config, the problems found
+/// and whether the file should be rewritten (older or repaired, never newer).
+pub(super) fn load_validated_config(profile: &str) -> (Config, Vec<String>, bool) {
+    match load_config(profile) {
+        Ok(mut config) => {
+            let from_version = config.version;
+            let problems = config.validate();
+            let upgrade = from_version < CONFIG_VERSION || (from_version == CONFIG_VERSION && !problems.is_empty());
+            (config, problems, upgrade)
+        }
+        Err(e) => (Config::default(), vec![format!("The settings file could not be read, so default settings are used: {}", e)], false),
+    }
+}
+
+impl StudyHelperApp {
+    /// Profile picker for the welcome header, with buttons to add and remove profiles.
+    pub(super) fn profile_switcher(&mut self, ui: &mut egui::Ui) {
+        let mut switch_to = None;
+        ui.horizontal(|ui_h| {
+            ui_h.label("Profile:");
+            egui::ComboBox::from_id_salt("profile_select").selected_text(&self.profiles.active).show_ui(ui_h, |ui_c| {
+                for name in &self.profiles.names {
+                    if ui_c.selectable_label(*name == self.profiles.active, name).clicked() && *name != self.profiles.active {
+                        switch_to = Some(name.clone());
+                    }
+                }
+            });
+            if ui_h.small_button("New profile…").on_hover_text("Separate review progress, settings and last class for another person").clicked() {
+                self.new_profile = Some((String::new(), true));
+            }
+            if self.profiles.active != DEFAULT_PROFILE
+                && ui_h.small_button("Delete profile").on_hover_text("Remove this profile's settings; its progress stays in the sets").clicked()
+            {
+                self.delete_active_profile();
+            }
+        });
+        if let Some(name) = switch_to {
+            self.switch_profile(name, None);
+        }
+    }
+
+    /// Popup asking for the name of a new profile.
+    pub(super) fn new_profile_window(&mut self, ctx: &egui::Context) {
+        let Some((name, share)) = self.new_profile.as_mut() else { return };
+        let taken = self.profiles.name_taken(name);
+        let mut create = false;
+        let mut cancel = false;
+        egui::Window::new("New Profile").collapsible(false).resizable(false).show(ctx, |ui_win| {
+            ui_win.label("Name:");
+            ui_win.text_edit_singleline(name);
+            if taken && !name.trim().is_empty() {
+                ui_win.label(RichText::new("A profile with this name already exists.").small().color(ui_win.visuals().warn_fg_color));
+            }
+            ui_win.checkbox(share, "Use the same study sets as this profile")
+                .on_hover_text("Cards are shared; each profile keeps its own review progress");
+            ui_win.horizontal(|ui_h| {
+                if ui_h.add_enabled(!taken, egui::Button::new("Create")).clicked() {
+                    create = true;
+                }
+                if ui_h.button("Cancel").clicked() {
+                    cancel = true;
+                }
+            });
+        });
+        if create && let Some((name, share)) = self.new_profile.take() {
+            let name = name.trim().to_string();
+            let mut config = Config::default();
+            if share {
+                config.storage_base_path = self.storage_base_path.clone();
+                config.storage_class_name = self.storage_class_name.clone();
+                config.storage_backend = self.config.storage_backend;
+            }
+            self.profiles.names.push(name.clone());
+            self.switch_profile(name, Some(config));
+        } else if cancel {
+            self.new_profile = None;
+        }
+    }
+
+    /// Save pending edits, then load the settings of `name` (or start it with
+    /// `new_config`) and reload its study sets. The profile becomes active once its
+    /// settings are loaded, so nothing is saved under its name with the old settings.
+    fn switch_profile(&mut self, name: String, new_config: Option<Config>) {
+        self.flush_dirty_sets_before_reload();
+        let mut profiles = self.profiles.clone();
+        profiles.active = name.clone();
+        self.run_storage("Switching profile", move || {
+            let loaded = match new_config {
+                Some(config) => save_config(&name, &config).map(|()| (config, Vec::new(), false)),
+                None => Ok(load_validated_config(&name)),
+            };
+            let saved = if loaded.is_ok() { crate::storage::save_profiles(&profiles) } else { Ok(()) };
+            (name, loaded, saved)
+        }, |app, (name, loaded, saved)| {
+            match loaded {
+                Ok((config, problems, upgrade)) => {
+                    app.profiles.active = name;
+                    app.apply_profile_config(config, problems);
+                    if upgrade {
+                        app.persist_config();
+                    }
+                }
+                Err(e) => app.status_message = format!("Failed to create the profile's settings: {}", e),
+            }
+            if let Err(e) = saved {
+                app.status_message = format!("Failed to save the profile list: {}", e);
+            }
+        });
+    }
+
+    /// Replace the settings with those of the active profile and drop the loaded
+    /// sets, so `ensure_storage_consistency` loads the profile's classes.
+    fn apply_profile_config(&mut self, config: Config, problems: Vec<String>) {
+        self.storage_base_path = config.storage_base_path.clone();
+        self.storage_class_name = config.storage_class_name.clone();
+        self.config = config;
+        self.config_problems = problems;
+        self.last_storage_base_path.clear();
+        self.last_storage_class_name.clear();
+        self.available_classes.clear();
+        self.selected_class = None;
+        self.study_sets.clear();
+        self.selected_set = None;
+        self.current_card_index = 0;
+        self.card_flipped = false;
+        self.show_hint = false;
+        self.editing_card = None;
+        self.learn_session = None;
+        self.match_round = None;
+        self.dirty_sets.clear();
+        // undo steps refer to the previous profile's sets
+        self.history = EditHistory::default();
+        self.status_message = format!("Switched to profile '{}'", self.profiles.active);
+    }
+
+    /// Switch to the default profile and forget the current one.
+    fn delete_active_profile(&mut self) {
+        let name = self.profiles.active.clone();
+        self.profiles.names.retain(|n| *n != name);
+        self.switch_profile(DEFAULT_PROFILE.to_string(), None);
+        self.run_storage("Deleting profile", move || crate::storage::delete_profile_config(&name).map(|()| name), |app, result| {
+            app.status_message = match result {
+                Ok(name) => format!("Deleted profile '{}'", name),
+                Err(e) => format!("Failed to delete the profile's settings: {}", e),
+            };
+        });
+    }
+}
@@ -0,0 +1,158 @@
+// src/gui/quiz_exchange.rs
+use std::path::PathBuf;
+use eframe::egui;
+use egui::RichText;
+use rfd::FileDialog;
+use crate::formats::{QuizImport, QuizTextFormat};
+use crate::history::EditCommand;
+use super::StudyHelperApp;
+
+/// Problems listed in the status line before the rest are counted.
+const MAX_LISTED: usize = 3;
+
+/// "; line 4: essay questions are not supported; ... (+2 more)"
+fn describe(label: &str, items: &[(usize, String)]) -> String {
+    if items.is_empty() {
+        return String::new();
+    }
+    let listed: Vec<String> = items.iter().take(MAX_LISTED).map(|(n, reason)| format!("{} {}: {}", label, n, reason)).collect();
+    let more = if items.len() > MAX_LISTED { format!(" (+{} more)", items.len() - MAX_LISTED) } else { String::new() };
+    format!("; {}{}", listed.join("; "), more)
+}
+
+impl StudyHelperApp {
+    /// Read a GIFT or Aiken file and attach it to set `set_idx` as a new quiz.
+    pub(super) fn import_quiz_file(&mut self, set_idx: usize) {
+        let picked = FileDialog::new()
+            .add_filter("Quiz (GIFT, Aiken)", &["gift", "txt"])
+            .add_filter("All files", &["*"])
+            .pick_file();
+        let Some(src) = picked else { return };
+        let title = src.file_stem().and_then(|s| s.to_str()).unwrap_or("Imported quiz").to_string();
+        self.run_storage("Importing quiz", move || {
+            std::fs::read_to_string(&src).map(|text| {
+                let format = if src.extension().is_some_and(|e| e.eq_ignore_ascii_case("gift")) {
+                    QuizTextFormat::Gift
+                } else {
+                    QuizTextFormat::detect(&text)
+                };
+                (format, format.parse(&text, &title))
+            })
+        }, move |app, result| match result {
+            Ok((format, import)) => app.attach_imported_quiz(set_idx, format.label(), "line", import),
+            Err(e) => app.status_message = format!("Import error: {}", e),
+        });
+    }
+
+    /// Add an imported quiz to a set (one undo step). Anything left out or changed
+    /// is listed in the import report window; `unit` is "line" or "item".
+    pub(super) fn attach_imported_quiz(&mut self, set_idx: usize, format: &str, unit: &str, import: QuizImport) {
+        let QuizImport { quiz, skipped, converted } = import;
+        let mut report: Vec<String> = skipped.iter().map(|(n, reason)| format!("Skipped {} {}: {}", unit, n, reason)).collect();
+        report.extend(converted.iter().map(|(n, note)| format!("Changed {} {}: {}", unit, n, note)));
+        let problems = if report.is_empty() {
+            String::new()
+        } else {
+            format!(" ({} skipped, {} changed; see the report)", skipped.len(), converted.len())
+        };
+        if quiz.question_count() == 0 {
+            self.status_message = format!("No usable questions found in the {} file{}", format, problems);
+        } else if let Some(set) = self.study_sets.get_mut(set_idx) {
+            let index = set.get_all_quizzes().len();
+            self.status_message = format!("Imported {} quiz '{}' with {} questions into '{}'{}", format, quiz.title(), quiz.question_count(), set.name(), problems);
+            self.history.record(EditCommand::CreateQuiz { set: set.name().to_string(), index, quiz: quiz.clone() });
+            set.add_quiz(quiz.clone());
+            self.selected_quiz = Some(index);
+            self.quiz_current_question_index = 0;
+            self.populate_quiz_edit_buffers();
+            self.persist_set(set_idx);
+        } else {
+            return;
+        }
+        if !report.is_empty() {
+            self.quiz_import_report = Some((format!("{} import: {}", format, quiz.title()), report));
+        }
+    }
+
+    /// Read a QTI package into a new quiz on the selected set.
+    pub(super) fn import_qti(&mut self, src: PathBuf) {
+        let Some(set_idx) = self.selected_set.filter(|i| *i < self.study_sets.len()) else {
+            self.status_message = "Select the set the QTI quiz should be added to first".to_string();
+            return;
+        };
+        let title = src.file_stem().and_then(|s| s.to_str()).unwrap_or("Imported quiz").to_string();
+        self.run_storage("Importing QTI package", move || crate::formats::read_qti(&src, &title), move |app, result| match result {
+            Ok(import) => app.attach_imported_quiz(set_idx, "QTI", "item", import),
+            Err(e) => app.status_message = format!("Import error: {}", e),
+        });
+    }
+
+    /// Lists what the last quiz import skipped or had to change.
+    pub(super) fn quiz_import_report_window(&mut self, ctx: &egui::Context, scale: f32) {
+        let Some((title, lines)) = &self.quiz_import_report else { return };
+        let mut close = false;
+        egui::Window::new("Quiz Import Report").collapsible(false).resizable(true).show(ctx, |ui_win| {
+            ui_win.label(RichText::new(title).strong());
+            ui_win.label("These questions could not be imported as they were:");
+            egui::ScrollArea::vertical().max_height((260.0 * scale).round()).show(ui_win, |ui_s| {
+                for line in lines {
+                    ui_s.label(line);
+                }
+            });
+            ui_win.add_space((6.0 * scale).round());
+            if ui_win.button("Close").clicked() {
+                close = true;
+            }
+        });
+        if close {
+            self.quiz_import_report = None;
+        }
+    }
+
+    /// Save a quiz as GIFT or Aiken for upload to an LMS.
+    pub(super) fn export_quiz_file(&mut self, set_idx: usize, quiz_idx: usize, format: QuizTextFormat) {
+        let Some(quiz) = self.study_sets.get(set_idx).and_then(|s| s.get_all_quizzes().get(quiz_idx)).cloned() else { return };
+        let picked = FileDialog::new()
+            .add_filter(format.label(), &["txt", "gift"])
+            .set_file_name(format!("{}.txt", sanitize_filename::sanitize(quiz.title())))
+            .save_file();
+        let Some(dst) = picked else { return };
+        let (text, skipped) = format.write(&quiz);
+        let written = quiz.question_count() - skipped.len();
+        self.run_storage(format!("Exporting '{}'", quiz.title()), move || {
+            let result = crate::storage::write_atomic(&dst, text.as_bytes());
+            (dst, result)
+        }, move |app, (dst, result)| match result {
+            Ok(()) => app.status_message = format!(
+                "Saved {} questions as {} to {}{}",
+                written, format.label(), dst.display(), describe("skipped question", &skipped),
+            ),
+            Err(e) => app.status_message = format!("Export error: {}", e),
+        });
+    }
+
+    /// Save a quiz as a QTI 2.1 zip package.
+    pub(super) fn export_quiz_qti(&mut self, set_idx: usize, quiz_idx: usize) {
+        let Some(quiz) = self.study_sets.get(set_idx).and_then(|s| s.get_all_quizzes().get(quiz_idx)).cloned() else { return };
+        let picked = FileDialog::new()
+            .add_filter("QTI package", &[crate::formats::QTI_EXTENSION])
+            .set_file_name(format!("{}.{}", sanitize_filename::sanitize(quiz.title()), crate::formats::QTI_EXTENSION))
+            .save_file();
+        let Some(mut dst) = picked else { return };
+        if dst.extension().is_none_or(|e| !e.eq_ignore_ascii_case(crate::formats::QTI_EXTENSION)) {
+            dst.set_extension(crate::formats::QTI_EXTENSION);
+        }
+        let total = quiz.question_count();
+        self.run_storage(format!("Exporting '{}'", quiz.title()), move || {
+            let result = crate::formats::quiz_to_qti_package(&quiz)
+                .and_then(|(bytes, skipped)| crate::storage::write_atomic(&dst, &bytes).map(|()| skipped));
+            (dst, result)
+        }, move |app, (dst, result)| match result {
+            Ok(skipped) => app.status_message = format!(
+                "Saved {} questions as a QTI package to {}{}",
+                total - skipped.len(), dst.display(), describe("skipped question", &skipped),
+            ),
+            Err(e) => app.status_message = format!("Export error: {}", e),
+        });
+    }
+}
@@ -0,0 +1,160 @@
+// src/gui/quiz_session.rs
+use eframe::egui;
+use egui::{RichText, TextStyle};
+use crate::keymap::Action;
+use crate::models::{QuestionData, QuestionType, Quiz as AppQuiz};
+use super::StudyHelperApp;
+
+/// Progress through a quiz taken in the session window.
+#[derive(Default)]
+pub(super) struct QuizSession {
+    /// Question numbers in the order they are asked (shuffled if the study settings say so).
+    pub(super) order: Vec<usize>,
+    /// Position in `order`.
+    pub(super) index: usize,
+    pub(super) answer: String,
+    /// Whether the submitted answer was right; `None` until the question is submitted.
+    pub(super) checked: Option<bool>,
+    pub(super) score: usize,
+    pub(super) finished: bool,
+}
+
+impl StudyHelperApp {
+    /// Start the selected quiz from its first question.
+    pub(super) fn start_quiz_session(&mut self) {
+        let count = self.session_quiz().map_or(0, |q| q.question_count());
+        let mut order: Vec<usize> = (0..count).collect();
+        if self.config.study.shuffle {
+            fastrand::shuffle(&mut order);
+        }
+        self.quiz_session = QuizSession { order, ..Default::default() };
+        self.show_quiz_window = true;
+    }
+
+    fn session_quiz(&self) -> Option<&AppQuiz> {
+        self.selected_set.and_then(|i| self.study_sets.get(i))?.get_all_quizzes().get(self.selected_quiz?)
+    }
+
+    fn session_question(&self) -> Option<(usize, QuestionData)> {
+        let quiz = self.session_quiz()?;
+        let number = *self.quiz_session.order.get(self.quiz_session.index)?;
+        quiz.get_question_data(number).map(|q| (self.quiz_session.order.len(), q))
+    }
+
+    /// Check the current answer, or move on once it has been checked.
+    pub(super) fn quiz_session_submit(&mut self) {
+        let Some((count, _)) = self.session_question() else {
+            self.quiz_session.finished = true;
+            return;
+        };
+        let session = &mut self.quiz_session;
+        if session.checked.is_some() {
+            session.index += 1;
+            session.answer.clear();
+            session.checked = None;
+            session.finished = session.index >= count;
+            return;
+        }
+        let number = session.order[session.index];
+        let right = self.session_quiz().is_some_and(|q| q.check_answer(number, &self.quiz_session.answer, &self.config.study.answer_matching));
+        let session = &mut self.quiz_session;
+        session.score += right as usize;
+        session.checked = Some(right);
+    }
+
+    /// Full-screen, distraction-free quiz in the current theme; the Submit shortcut presses Submit/Next.
+    pub(super) fn quiz_session_window(&mut self, ctx: &egui::Context, scale: f32) {
+        let screen_rect = ctx.input(|i| i.screen_rect);
+        let win_w = screen_rect.width().max(1.0);
+        let win_h = screen_rect.height().max(1.0);
+        let question = self.session_question();
+        let submit_key = self.config.shortcuts.binding(Action::Submit).to_string();
+        let mut submit = false;
+        let mut exit = false;
+
+        // Use a Window forced to the foreground so it overlays everything and looks like a modal
+        egui::Window::new("Quiz Session")
+            .order(egui::Order::Foreground)
+            .collapsible(false)
+            .resizable(false)
+            .movable(false)
+            .title_bar(false)
+            .default_pos(egui::pos2(screen_rect.left(), screen_rect.top()))
+            .fixed_size(egui::vec2(win_w, win_h))
+            .show(ctx, |ui_win| {
+                // Paint the panel colour over the whole window to fully obscure underlying UI
+                let win_rect = ctx.available_rect();
+                ui_win.painter().rect_filled(win_rect, 0.0, ui_win.visuals().panel_fill);
+                let text = ui_win.visuals().strong_text_color();
+                let large = TextStyle::Body.resolve(ui_win.style()).size * 1.4;
+                let correct = crate::theme::success_color(ui_win.visuals());
+                let wrong = ui_win.visuals().error_fg_color;
+                let button_size = egui::Vec2::new((96.0 * scale).round(), (36.0 * scale).round());
+
+                ui_win.vertical_centered(|ui_c| {
+                    ui_c.add_space((8.0 * scale).round());
+                    ui_c.label(RichText::new("Quiz Session").heading().color(text));
+                    ui_c.add_space((12.0 * scale).round());
+                    let session = &mut self.quiz_session;
+                    match &question {
+                        Some((count, q)) if !session.finished => {
+                            ui_c.label(RichText::new(format!("Question {} of {}", session.index + 1, count)).color(text));
+                            ui_c.add_space((6.0 * scale).round());
+                            ui_c.label(RichText::new(&q.prompt).size(large).color(text));
+                            ui_c.add_space((12.0 * scale).round());
+                            let locked = session.checked.is_some();
+                            match q.question_type {
+                                QuestionType::MultipleChoice => {
+                                    for option in &q.options {
+                                        let selected = session.answer == *option;
+                                        if ui_c.add_enabled(!locked, egui::Button::selectable(selected, RichText::new(option).color(text))).clicked() {
+                                            session.answer = option.clone();
+                                        }
+                                    }
+                                }
+                                QuestionType::FillInTheBlank => {
+                                    let edit = ui_c.add_enabled(!locked, egui::TextEdit::singleline(&mut session.answer).hint_text("Your answer"));
+                                    if !locked && !edit.has_focus() && session.answer.is_empty() {
+                                        edit.request_focus();
+                                    }
+                                }
+                            }
+                            ui_c.add_space((12.0 * scale).round());
+                            match session.checked {
+                                Some(true) => { ui_c.label(RichText::new("Correct!").color(correct)); }
+                                Some(false) => { ui_c.label(RichText::new(format!("Answer: {}", q.answer)).color(wrong)); }
+                                None => {}
+                            }
+                            let label = if locked { "Next" } else { "Submit" };
+                            if ui_c.add_sized(button_size, egui::Button::new(RichText::new(label).color(text)))
+                                .on_hover_text(&submit_key)
+                                .clicked()
+                            {
+                                submit = true;
+                            }
+                        }
+                        Some((count, _)) => {
+                            ui_c.label(RichText::new(format!("Score: {} / {}", session.score, count)).size(large).color(text));
+                        }
+                        None if session.finished && session.index > 0 => {
+                            ui_c.label(RichText::new(format!("Score: {} / {}", session.score, session.index)).size(large).color(text));
+                        }
+                        None => {
+                            ui_c.label(RichText::new("This quiz has no questions.").color(text));
+                        }
+                    }
+                    ui_c.add_space((18.0 * scale).round());
+                    if ui_c.add_sized(button_size, egui::Button::new(RichText::new("Exit").color(text))).clicked() {
+                        exit = true;
+                    }
+                });
+            });
+
+        if submit {
+            self.quiz_session_submit();
+        }
+        if exit {
+            self.show_quiz_window = false;
+        }
+    }
+}
@@ -0,0 +1,78 @@
+// src/gui/set_bundles.rs
+use std::path::PathBuf;
+use rfd::FileDialog;
+use super::StudyHelperApp;
+
+impl StudyHelperApp {
+    /// Write the selected set (with unsaved edits) and its media to `export_dest_path` as a bundle.
+    pub(super) fn export_selected_bundle(&mut self) {
+        let Some(set) = self.selected_set.and_then(|i| self.study_sets.get(i)).cloned() else { return };
+        if self.export_dest_path.trim().is_empty() {
+            self.status_message = "Choose an export destination first".to_string();
+            return;
+        }
+        let mut dst = PathBuf::from(self.export_dest_path.trim());
+        if dst.extension().is_none_or(|e| !e.eq_ignore_ascii_case(crate::storage::BUNDLE_EXTENSION)) {
+            dst.as_mut_os_string().push(format!(".{}", crate::storage::BUNDLE_EXTENSION));
+        }
+        let base = PathBuf::from(&self.storage_base_path);
+        let class_name = self.storage_class_name.clone();
+        let include_reviews = self.bundle_include_reviews;
+        self.run_storage(format!("Exporting '{}'", set.name()), move || {
+            let result = crate::storage::export_set_bundle(&base, &class_name, &set, &dst, include_reviews);
+            (dst, result)
+        }, |app, (dst, result)| match result {
+            Ok(manifest) if manifest.media.is_empty() => app.status_message = format!("Exported bundle to {}", dst.display()),
+            Ok(manifest) => app.status_message = format!("Exported bundle with {} media files to {}", manifest.media.len(), dst.display()),
+            Err(e) => app.status_message = format!("Export error: {}", e),
+        });
+    }
+
+    /// Save the selected set as a self-contained web page for people without the app.
+    pub(super) fn export_selected_html(&mut self) {
+        let Some(set) = self.selected_set.and_then(|i| self.study_sets.get(i)).cloned() else { return };
+        let picked = FileDialog::new()
+            .add_filter("Web page", &["html"])
+            .set_file_name(format!("{}.html", sanitize_filename::sanitize(set.name())))
+            .save_file();
+        let Some(mut dst) = picked else { return };
+        if dst.extension().is_none_or(|e| !e.eq_ignore_ascii_case("html") && !e.eq_ignore_ascii_case("htm")) {
+            dst.set_extension("html");
+        }
+        self.run_storage(format!("Exporting '{}'", set.name()), move || {
+            let result = crate::formats::set_to_html(&set).and_then(|html| crate::storage::write_atomic(&dst, html.as_bytes()));
+            (dst, result)
+        }, |app, (dst, result)| match result {
+            Ok(()) => app.status_message = format!("Saved web page to {}", dst.display()),
+            Err(e) => app.status_message = format!("Export error: {}", e),
+        });
+    }
+
+    /// Add the set in a bundle file to the current class, renaming it if the name is taken.
+    pub(super) fn import_bundle(&mut self, src: PathBuf) {
+        let location = self.storage_location();
+        let class_name = self.storage_class_name.clone();
+        self.run_storage("Importing bundle", move || {
+            let result = location.open().and_then(|store| {
+                crate::storage::import_set_bundle(store.as_ref(), &location.base_dir, &class_name, &src)
+            });
+            (class_name, result)
+        }, |app, (class_name, result)| match result {
+            Ok(import) => {
+                app.status_message = match &import.renamed_from {
+                    Some(original) => format!("Imported '{}' as '{}' (name was taken)", original, import.set.name()),
+                    None => format!("Imported set '{}'", import.set.name()),
+                };
+                if import.media_files > 0 {
+                    app.status_message.push_str(&format!(" with {} media files", import.media_files));
+                }
+                // the JSON watcher may have added it already
+                if class_name == app.storage_class_name && !app.study_sets.iter().any(|s| s.name() == import.set.name()) {
+                    app.last_saved.insert(import.set.name().to_string(), import.set.clone());
+                    app.study_sets.push(import.set);
+                }
+            }
+            Err(e) => app.status_message = format!("Import error: {}", e),
+        });
+    }
+}
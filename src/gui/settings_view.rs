@@ -0,0 +1,131 @@
+// src/gui/settings_view.rs
+use eframe::egui;
+use egui::RichText;
+use crate::storage::StorageBackend;
+use rfd::FileDialog;
+use super::StudyHelperApp;
+
+impl StudyHelperApp {
+    /// All persisted settings in one place. Every change is saved to the config right away.
+    pub(super) fn settings_view(&mut self, ui: &mut egui::Ui, scale: f32) {
+        let before = self.storage_config();
+        let width = ui.available_width().min((640.0 * scale).round());
+
+        egui::ScrollArea::vertical().id_salt("settings_scroll").show(ui, |ui_s| {
+            ui_s.set_max_width(width);
+            ui_s.vertical(|ui_v| {
+                ui_v.label(RichText::new("Settings").heading());
+                if !self.config_problems.is_empty() {
+                    ui_v.label(RichText::new("Some settings could not be used as saved:").color(ui_v.visuals().warn_fg_color));
+                    for problem in &self.config_problems {
+                        ui_v.label(RichText::new(format!("• {}", problem)).small());
+                    }
+                    if ui_v.small_button("Dismiss").clicked() {
+                        self.config_problems.clear();
+                    }
+                }
+                if !self.status_message.is_empty() {
+                    ui_v.label(RichText::new(&self.status_message).small());
+                }
+                let source = crate::storage::config_location().1;
+                let config_file = crate::storage::profile_config_path(&self.profiles.active);
+                ui_v.label(RichText::new(format!("Settings of profile '{}', saved in {} ({})", self.profiles.active, config_file.display(), source.label())).small().weak());
+
+                ui_v.add_space((8.0 * scale).round());
+                ui_v.label(RichText::new("Storage").strong());
+                self.storage_settings_ui(ui_v);
+
+                ui_v.add_space((8.0 * scale).round());
+                ui_v.label(RichText::new("Saving").strong());
+                ui_v.horizontal(|ui_h| {
+                    ui_h.checkbox(&mut self.config.autosave_enabled, "Autosave after")
+                        .on_hover_text("Save changed sets after a pause in editing and when the window loses focus");
+                    ui_h.add_enabled(self.config.autosave_enabled, egui::DragValue::new(&mut self.config.autosave_delay_secs).range(1..=600).suffix(" s"));
+                    ui_h.label("without edits");
+                });
+                ui_v.horizontal(|ui_h| {
+                    ui_h.label("Keep deleted items in the trash for");
+                    ui_h.add(egui::DragValue::new(&mut self.config.trash_retention_days).range(1..=3650));
+                    ui_h.label("days");
+                });
+
+                ui_v.add_space((8.0 * scale).round());
+                ui_v.label(RichText::new("Appearance").strong());
+                self.appearance_ui(ui_v, scale);
+
+                ui_v.add_space((8.0 * scale).round());
+                ui_v.label(RichText::new("Studying").strong());
+                let study = &mut self.config.study;
+                ui_v.horizontal(|ui_h| {
+                    ui_h.label("New cards per day:");
+                    ui_h.add(egui::DragValue::new(&mut study.new_cards_per_day).range(0..=9999));
+                });
+                ui_v.checkbox(&mut study.shuffle, "Shuffle questions in quizzes and Learn mode");
+                self.review_algorithm_ui(ui_v);
+                let study = &mut self.config.study;
+                ui_v.label("When checking typed answers:");
+                let rules = &mut study.answer_matching;
+                ui_v.checkbox(&mut rules.case_sensitive, "Upper and lower case must match");
+                ui_v.checkbox(&mut rules.ignore_punctuation, "Ignore punctuation");
+                ui_v.horizontal(|ui_h| {
+                    ui_h.label("Accept up to");
+                    ui_h.add(egui::DragValue::new(&mut rules.allowed_typos).range(0..=5));
+                    ui_h.label("typos in answers of four or more letters");
+                });
+
+                ui_v.add_space((8.0 * scale).round());
+                ui_v.label(RichText::new("Keyboard").strong());
+                if ui_v.button("Edit keyboard shortcuts…").clicked() {
+                    self.show_shortcut_sheet = true;
+                }
+                ui_v.add_space((8.0 * scale).round());
+            });
+        });
+
+        if self.storage_config() != before {
+            self.persist_config();
+        }
+    }
+
+    /// Base folder, class and storage format; the class list reloads when they change.
+    fn storage_settings_ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui_h| {
+            ui_h.label("Base folder:");
+            // applied on Enter or when the field loses focus, not on every keystroke
+            let edit = ui_h.text_edit_singleline(&mut self.base_folder_edit);
+            if edit.lost_focus() {
+                let path = self.base_folder_edit.clone();
+                self.set_storage_base_path(path);
+            } else if !edit.has_focus() {
+                self.base_folder_edit.clone_from(&self.storage_base_path);
+            }
+            if ui_h.small_button("Browse").clicked()
+                && let Some(dir) = FileDialog::new().set_title("Select base folder").pick_folder()
+                && let Some(s) = dir.to_str()
+            {
+                self.set_storage_base_path(s.to_string());
+            }
+        });
+        ui.horizontal(|ui_h| {
+            ui_h.label("Class:");
+            let selected = if self.storage_class_name.is_empty() { "(none)".to_string() } else { self.storage_class_name.clone() };
+            egui::ComboBox::from_id_salt("settings_class").selected_text(selected).show_ui(ui_h, |ui_c| {
+                for class_name in &self.available_classes {
+                    ui_c.selectable_value(&mut self.storage_class_name, class_name.clone(), class_name);
+                }
+            });
+        });
+        ui.horizontal(|ui_h| {
+            ui_h.label("Stored as:");
+            let mut backend = self.config.storage_backend;
+            egui::ComboBox::from_id_salt("settings_storage_backend").selected_text(backend.label()).show_ui(ui_h, |ui_c| {
+                for b in [StorageBackend::Json, StorageBackend::Sqlite] {
+                    ui_c.selectable_value(&mut backend, b, b.label());
+                }
+            });
+            if backend != self.config.storage_backend {
+                self.switch_storage_backend(backend);
+            }
+        });
+    }
+}
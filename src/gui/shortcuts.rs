@@ -0,0 +1,189 @@
+// src/gui/shortcuts.rs
+use eframe::egui;
+use egui::RichText;
+use crate::keymap::{Action, Binding, Keymap};
+use crate::models::{schedule_leitner, schedule_sm2, Grade, ReviewAlgorithm};
+use super::{AppView, StudyHelperApp};
+
+impl StudyHelperApp {
+    /// Run the actions whose shortcuts were pressed this frame, or record the
+    /// next key press as a new binding while the cheat sheet is remapping one.
+    pub(super) fn handle_shortcuts(&mut self, ctx: &egui::Context) {
+        if let Some(action) = self.shortcut_capture {
+            let pressed = ctx.input_mut(|i| {
+                let found = i.events.iter().find_map(|e| match e {
+                    egui::Event::Key { key, pressed: true, modifiers, .. } => Some((*key, *modifiers)),
+                    _ => None,
+                });
+                if found.is_some() {
+                    i.events.retain(|e| !matches!(e, egui::Event::Key { .. }));
+                }
+                found
+            });
+            if let Some((key, modifiers)) = pressed {
+                self.shortcut_capture = None;
+                if key != egui::Key::Escape {
+                    self.config.shortcuts.set(action, Binding::new(key, modifiers));
+                    self.persist_config();
+                }
+            }
+            return;
+        }
+
+        let text_focus = ctx.wants_keyboard_input();
+        for (action, binding) in self.config.shortcuts.by_specificity() {
+            if self.shortcut_enabled(action, text_focus) && ctx.input_mut(|i| i.consume_shortcut(&binding.shortcut())) {
+                self.run_action(action);
+                return;
+            }
+        }
+        // Ctrl+Y is a second, fixed redo shortcut
+        let redo_y = egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Y);
+        if !text_focus && !self.show_quiz_window && ctx.input_mut(|i| i.consume_shortcut(&redo_y)) {
+            self.redo_edit();
+        }
+    }
+
+    /// Whether `action` applies in the current view; plain-key actions never
+    /// fire while a text field has focus so typing is not interrupted.
+    fn shortcut_enabled(&self, action: Action, text_focus: bool) -> bool {
+        let has_card = self.selected_set.and_then(|i| self.study_sets.get(i)).is_some_and(|s| !s.get_all_flashcards().is_empty());
+        let studying = matches!(self.current_view, AppView::Flashcards) && !self.show_quiz_window && !text_focus && has_card;
+        match action {
+            Action::Flip | Action::PreviousCard | Action::NextCard | Action::ToggleHint => studying,
+            Action::GradeAgain | Action::GradeHard | Action::GradeGood | Action::GradeEasy => studying && self.card_flipped,
+            Action::Submit => self.show_quiz_window || (matches!(self.current_view, AppView::Learn) && self.learn_accepts_submit()),
+            Action::Save | Action::ShowShortcuts => true,
+            Action::NewCard | Action::Search => !self.show_quiz_window,
+            Action::Undo | Action::Redo => !text_focus && !self.show_quiz_window,
+        }
+    }
+
+    /// Perform `action`; the study buttons call this too.
+    pub(super) fn run_action(&mut self, action: Action) {
+        let card_count = self.selected_set.and_then(|i| self.study_sets.get(i)).map_or(0, |s| s.get_all_flashcards().len());
+        match action {
+            Action::Flip => self.card_flipped = !self.card_flipped,
+            Action::PreviousCard | Action::NextCard if card_count > 0 => {
+                self.current_card_index = if action == Action::NextCard {
+                    (self.current_card_index + 1) % card_count
+                } else {
+                    (self.current_card_index + card_count - 1) % card_count
+                };
+                self.card_flipped = false;
+                self.show_hint = false;
+            }
+            Action::PreviousCard | Action::NextCard => {}
+            Action::ToggleHint => self.show_hint = !self.show_hint,
+            Action::GradeAgain => self.grade_current_card(Grade::Again),
+            Action::GradeHard => self.grade_current_card(Grade::Hard),
+            Action::GradeGood => self.grade_current_card(Grade::Good),
+            Action::GradeEasy => self.grade_current_card(Grade::Easy),
+            Action::Submit if self.show_quiz_window => self.quiz_session_submit(),
+            Action::Submit => self.learn_submit(),
+            Action::Save => {
+                if let Some(idx) = self.selected_set.filter(|i| *i < self.study_sets.len()) {
+                    if self.persist_set(idx) {
+                        self.status_message = format!("Saving '{}'", self.study_sets[idx].name());
+                    } else {
+                        self.status_message = "Set storage not configured.".to_string();
+                    }
+                }
+                self.save_dirty_sets();
+            }
+            Action::NewCard => {
+                self.current_view = AppView::StudySets;
+                if self.editing_card.is_some() {
+                    self.close_card_edit();
+                }
+                self.focus_new_question = true;
+            }
+            Action::Search => {
+                self.current_view = AppView::StudySets;
+                self.focus_card_search = true;
+            }
+            Action::Undo => self.undo_edit(),
+            Action::Redo => self.redo_edit(),
+            Action::ShowShortcuts => {
+                self.show_shortcut_sheet = !self.show_shortcut_sheet;
+                self.shortcut_capture = None;
+            }
+        }
+    }
+
+    /// Schedule the shown card's next review and move on to the next card.
+    fn grade_current_card(&mut self, grade: Grade) {
+        let Some(idx) = self.selected_set.filter(|i| *i < self.study_sets.len()) else { return };
+        let card_idx = self.current_card_index;
+        let Some(card) = self.study_sets[idx].get_flashcard_mut(card_idx) else { return };
+        let previous = card.review(&self.profiles.active);
+        let now = crate::storage::unix_timestamp();
+        let study = &self.config.study;
+        let review = match study.algorithm {
+            ReviewAlgorithm::Sm2 => schedule_sm2(previous, grade, now),
+            ReviewAlgorithm::Leitner => schedule_leitner(previous, grade, &study.leitner_intervals, now),
+        };
+        let next = match review.interval_days {
+            0 => "card comes back in a few minutes".to_string(),
+            1 => "next review tomorrow".to_string(),
+            days => format!("next review in {} days", days),
+        };
+        self.status_message = match study.algorithm {
+            ReviewAlgorithm::Sm2 => format!("{} — {}", grade.label(), next),
+            ReviewAlgorithm::Leitner => format!("{} — box {}, {}", grade.label(), review.leitner_box, next),
+        };
+        card.set_review(&self.profiles.active, Some(review));
+        self.mark_dirty(idx);
+        self.run_action(Action::NextCard);
+    }
+
+    /// Cheat sheet listing every shortcut, where each one can be remapped.
+    pub(super) fn shortcut_sheet_window(&mut self, ctx: &egui::Context, scale: f32) {
+        let mut open = true;
+        let mut reset = false;
+        egui::Window::new("Keyboard Shortcuts").open(&mut open).collapsible(false).resizable(false).show(ctx, |ui_win| {
+            let keys = &self.config.shortcuts;
+            let mut group = "";
+            egui::Grid::new("shortcut_sheet").num_columns(3).spacing([(12.0 * scale).round(), (4.0 * scale).round()]).show(ui_win, |ui_g| {
+                for action in Action::ALL {
+                    if action.group() != group {
+                        group = action.group();
+                        ui_g.label(RichText::new(group).strong());
+                        ui_g.end_row();
+                    }
+                    ui_g.label(action.label());
+                    if self.shortcut_capture == Some(action) {
+                        ui_g.label(RichText::new("Press a key… (Esc cancels)").italics());
+                    } else {
+                        let conflicts = keys.conflicts(action);
+                        let text = RichText::new(keys.binding(action).to_string()).monospace();
+                        if conflicts.is_empty() {
+                            ui_g.label(text);
+                        } else {
+                            let others: Vec<&str> = conflicts.iter().map(|a| a.label()).collect();
+                            ui_g.label(text.color(ui_g.visuals().warn_fg_color)).on_hover_text(format!("Also bound to: {}", others.join(", ")));
+                        }
+                    }
+                    if ui_g.small_button("Change").clicked() {
+                        self.shortcut_capture = Some(action);
+                    }
+                    ui_g.end_row();
+                }
+            });
+            ui_win.add_space((6.0 * scale).round());
+            ui_win.label(RichText::new("Ctrl+Y also redoes. Single keys do nothing while typing in a text field.").small());
+            if ui_win.button("Reset to defaults").clicked() {
+                reset = true;
+            }
+        });
+        if reset {
+            self.config.shortcuts = Keymap::default();
+            self.shortcut_capture = None;
+            self.persist_config();
+        }
+        if !open {
+            self.show_shortcut_sheet = false;
+            self.shortcut_capture = None;
+        }
+    }
+}
@@ -0,0 +1,272 @@
+// src/gui/storage_jobs.rs
+use std::io;
+use std::path::Path;
+use eframe::egui;
+use crate::models::StudySet as AppStudySet;
+use crate::storage::{StorageBackend, StorageLocation};
+use super::StudyHelperApp;
+
+/// Result of a background storage job, applied to the app on the UI thread.
+pub(super) type StorageDone = Box<dyn FnOnce(&mut StudyHelperApp) + Send>;
+
+impl StudyHelperApp {
+    /// Run `job` on the storage worker and pass its result to `done` on the UI thread.
+    /// Jobs run in submission order, so a save queued before a load is on disk first.
+    pub(super) fn run_storage<R: Send + 'static>(
+        &mut self,
+        label: impl Into<String>,
+        job: impl FnOnce() -> R + Send + 'static,
+        done: impl FnOnce(&mut StudyHelperApp, R) + Send + 'static,
+    ) {
+        self.storage.submit(label, move || {
+            let result = job();
+            Box::new(move |app: &mut StudyHelperApp| done(app, result)) as StorageDone
+        });
+    }
+
+    /// Apply the results of finished jobs. Called once per frame.
+    pub(super) fn poll_storage_jobs(&mut self, ctx: &egui::Context) {
+        for done in self.storage.poll() {
+            done(self);
+        }
+        // a failed save leaves its set dirty, so autosave tries again
+        let failures = self.storage.take_failures();
+        if !failures.is_empty() {
+            self.status_message = format!("Storage failed, changes may not be saved: {}", failures.join("; "));
+        }
+        if self.storage.is_busy() {
+            // the worker can't wake the UI itself, so keep polling until it is idle
+            ctx.request_repaint_after(std::time::Duration::from_millis(50));
+        }
+    }
+
+    /// Spinner and the running job's label while the worker is busy.
+    pub(super) fn storage_progress(&self, ui: &mut egui::Ui) {
+        let Some(label) = self.storage.current_job() else { return };
+        ui.horizontal(|ui_h| {
+            ui_h.spinner();
+            let queued = self.storage.pending_count() - 1;
+            if queued > 0 {
+                ui_h.label(format!("{}… (+{} queued)", label, queued));
+            } else {
+                ui_h.label(format!("{}…", label));
+            }
+        });
+    }
+
+    /// Backend and base folder the storage jobs work on.
+    pub(super) fn storage_location(&self) -> StorageLocation {
+        StorageLocation::new(self.config.storage_backend, &self.storage_base_path)
+    }
+
+    /// Save the current configuration in the background; failures go to the status line.
+    pub(super) fn persist_config(&mut self) {
+        let cfg = self.storage_config();
+        let profile = self.profiles.active.clone();
+        self.run_storage("Saving settings", move || crate::storage::save_config(&profile, &cfg), |app, result| {
+            if let Err(e) = result {
+                app.status_message = format!("Failed to save settings: {}", e);
+            }
+        });
+    }
+
+    /// Save the set at `set_idx` into the current class folder in the background.
+    /// Returns false (and leaves the set dirty) if storage is not configured.
+    pub(super) fn persist_set(&mut self, set_idx: usize) -> bool {
+        let Some(set) = self.study_sets.get(set_idx).cloned() else { return false };
+        self.note_unsaved(set.name());
+        if !self.storage_configured() {
+            return false;
+        }
+        self.queue_set_save(self.storage_class_name.clone(), set);
+        true
+    }
+
+    /// Queue a save of `set` into `class_name`. The set is marked clean once the save
+    /// succeeds, unless it was edited again while the save was running.
+    pub(super) fn queue_set_save(&mut self, class_name: String, set: AppStudySet) {
+        let location = self.storage_location();
+        let label = format!("Saving '{}'", set.name());
+        self.run_storage(label, move || {
+            let result = location.open().and_then(|store| store.save_set(&class_name, &set));
+            (class_name, set, result)
+        }, |app, (class_name, set, result)| app.apply_set_saved(&class_name, set, result));
+    }
+
+    /// Record the outcome of a background save of `set` into `class_name`.
+    pub(super) fn apply_set_saved(&mut self, class_name: &str, set: AppStudySet, result: io::Result<()>) {
+        match result {
+            Ok(()) => {
+                log::info!("Saved set '{}' into class '{}'", set.name(), class_name);
+                if class_name == self.storage_class_name {
+                    if self.study_sets.contains(&set) {
+                        self.dirty_sets.remove(set.name());
+                    }
+                    // lets the watcher recognise this write as our own
+                    self.last_saved.insert(set.name().to_string(), set);
+                }
+            }
+            Err(e) => {
+                log::warn!("Failed to save set '{}': {}", set.name(), e);
+                self.status_message = format!("Save of '{}' failed: {}", set.name(), e);
+            }
+        }
+    }
+
+    /// Point storage at another base folder. Pending edits are saved into the old
+    /// folder first; `ensure_storage_consistency` then lists the new folder's classes.
+    pub(super) fn set_storage_base_path(&mut self, path: String) {
+        if path == self.storage_base_path {
+            return;
+        }
+        self.flush_dirty_sets_before_reload();
+        self.storage_base_path = path;
+    }
+
+    /// Switch between the JSON folders and the SQLite database. Pending edits are
+    /// saved to the old backend first, then everything is reloaded from the new one.
+    pub(super) fn switch_storage_backend(&mut self, backend: StorageBackend) {
+        self.flush_dirty_sets_before_reload();
+        self.config.storage_backend = backend;
+        self.persist_config();
+        // the loaded sets and their edit history belong to the old backend
+        self.history.sync_class("");
+        self.last_storage_base_path.clear();
+        self.status_message = format!("Storing sets as {}", backend.label());
+    }
+
+    /// Copy every class and set from `from` into the active backend (see `storage::migrate_sets`),
+    /// then reload the current class.
+    pub(super) fn migrate_from_backend(&mut self, from: StorageBackend) {
+        self.flush_dirty_sets_before_reload();
+        let source = StorageLocation::new(from, &self.storage_base_path);
+        let target = self.storage_location();
+        self.run_storage(format!("Copying sets from {}", from.label()), move || {
+            let from_store = source.open()?;
+            let to_store = target.open()?;
+            crate::storage::migrate_sets(from_store.as_ref(), to_store.as_ref())
+        }, move |app, result| {
+            match result {
+                Ok(report) => {
+                    app.status_message = format!("Copied {} sets in {} classes from {}", report.sets, report.classes, from.label());
+                    if !report.skipped.is_empty() {
+                        app.status_message.push_str(&format!("; kept {} existing: {}", report.skipped.len(), report.skipped.join(", ")));
+                    }
+                }
+                Err(e) => app.status_message = format!("Copy from {} failed: {}", from.label(), e),
+            }
+            app.last_storage_class_name.clear();
+            app.request_class_list();
+        });
+    }
+
+    /// Re-read the class folder list in the background (see `apply_class_list`).
+    pub(super) fn request_class_list(&mut self) {
+        if self.storage_base_path.trim().is_empty() {
+            return;
+        }
+        let location = self.storage_location();
+        let base = location.base_dir.clone();
+        self.class_list_pending = true;
+        self.run_storage("Listing classes", move || {
+            let result = if base.is_dir() {
+                location.open().and_then(|store| store.list_classes())
+            } else {
+                Err(io::Error::new(io::ErrorKind::NotFound, "base folder does not exist"))
+            };
+            (base, result)
+        }, |app, (base, result)| app.apply_class_list(&base, result));
+    }
+
+    /// Load the sets of the current class in the background, replacing the loaded sets.
+    pub(super) fn load_current_class(&mut self) {
+        let location = self.storage_location();
+        let class_name = self.storage_class_name.clone();
+        self.run_storage(format!("Loading class '{}'", class_name), move || {
+            let result = location.open().and_then(|store| store.load_sets(&class_name));
+            (class_name, result)
+        }, |app, (class_name, result)| app.apply_loaded_class(&class_name, result));
+    }
+
+    /// Store a freshly listed class folder list and resolve the selected class against it.
+    fn apply_class_list(&mut self, base: &Path, result: io::Result<Vec<String>>) {
+        if base != Path::new(&self.storage_base_path) {
+            // the base folder changed while listing; a newer request is queued
+            return;
+        }
+        self.class_list_pending = false;
+        match result {
+            Ok(list) => {
+                self.available_classes = list;
+                if self.available_classes.is_empty() {
+                    // save pending edits back to their class before dropping the sets
+                    self.flush_dirty_sets_before_reload();
+                    self.selected_class = None;
+                    self.study_sets.clear();
+                    self.selected_set = None;
+                    self.last_storage_class_name.clear();
+                    return;
+                }
+                if let Some(pos) = self.available_classes.iter().position(|c| c == &self.storage_class_name) {
+                    self.selected_class = Some(pos);
+                } else if self.storage_class_name.trim().is_empty()
+                    || self.last_storage_class_name.is_empty()
+                    || self.storage_class_name == self.last_storage_class_name
+                {
+                    // nothing configured or loaded yet, or the loaded class disappeared: use the first class
+                    if !self.storage_class_name.trim().is_empty() {
+                        self.status_message = "Configured class not found; switched to first available class".to_string();
+                    }
+                    // unsaved edits go back to the class they came from before switching
+                    self.flush_dirty_sets_before_reload();
+                    self.selected_class = Some(0);
+                    self.storage_class_name = self.available_classes[0].clone();
+                    self.study_sets.clear();
+                    self.selected_set = None;
+                    self.dirty_sets.clear();
+                } else {
+                    // a class name typed in the Create Set popup that doesn't exist yet
+                    self.selected_class = None;
+                }
+                if self.show_manage_classes_popup {
+                    self.refresh_class_summaries();
+                }
+            }
+            Err(e) => {
+                // the folder may only be unreachable for a moment (network drive, lock):
+                // keep the loaded sets and their unsaved edits, and report the problem
+                self.status_message = if e.kind() == io::ErrorKind::NotFound {
+                    "Configured base folder no longer exists".to_string()
+                } else {
+                    format!("Error listing classes: {}", e)
+                };
+            }
+        }
+    }
+
+    fn apply_loaded_class(&mut self, class_name: &str, result: io::Result<Vec<AppStudySet>>) {
+        if class_name != self.storage_class_name {
+            // the user switched classes again while this one was loading
+            return;
+        }
+        match result {
+            Ok(sets) => {
+                self.study_sets = sets;
+                self.dirty_sets.clear();
+                self.last_saved.clear();
+                if self.study_sets.is_empty() {
+                    self.selected_set = None;
+                } else if self.selected_set.is_none_or(|i| i >= self.study_sets.len()) {
+                    self.selected_set = Some(0);
+                }
+                self.current_card_index = 0;
+                self.selected_quiz = None;
+            }
+            Err(e) => {
+                self.study_sets.clear();
+                self.selected_set = None;
+                self.status_message = format!("Error loading sets: {}", e);
+            }
+        }
+    }
+}
@@ -0,0 +1,223 @@
+// src/gui/storage_sync.rs
+use eframe::egui;
+use egui::RichText;
+use crate::models::StudySet as AppStudySet;
+use crate::storage::{StorageBackend, StorageChange, StorageWatcher};
+use super::StudyHelperApp;
+
+/// A set that changed on disk while it had unsaved in-app edits.
+pub(super) struct SetConflict {
+    pub(super) set_name: String,
+    /// The version now on disk; None when the file was deleted.
+    pub(super) disk: Option<AppStudySet>,
+}
+
+impl StudyHelperApp {
+    /// Keep the filesystem watcher pointed at the configured base folder and apply whatever it reported.
+    pub(super) fn sync_storage_watcher(&mut self, ctx: &egui::Context) {
+        // only the JSON layout is edited outside the app; a SQLite store is not watched
+        let base = match self.config.storage_backend {
+            StorageBackend::Json => self.storage_base_path.trim().to_string(),
+            StorageBackend::Sqlite => String::new(),
+        };
+        if self.watcher_requested_for != base {
+            // registering the OS watch touches the disk, so it is set up on the worker
+            self.watcher_requested_for = base.clone();
+            self.storage_watcher = None;
+            if !base.is_empty() {
+                let repaint_ctx = ctx.clone();
+                let dir = std::path::PathBuf::from(&base);
+                self.run_storage("Watching storage folder", move || {
+                    StorageWatcher::new(&dir, move || repaint_ctx.request_repaint())
+                }, move |app, result| match result {
+                    Ok(w) if app.watcher_requested_for == base => app.storage_watcher = Some(w),
+                    Ok(_) => {}
+                    Err(e) => log::warn!("Cannot watch storage folder {}: {}", base, e),
+                });
+            }
+        }
+
+        let changes = match &self.storage_watcher {
+            Some(w) => w.poll(),
+            None => return,
+        };
+        for change in changes {
+            self.apply_storage_change(change);
+        }
+    }
+
+    /// Reload only what an external change touched. Sets of other classes are read on demand anyway.
+    fn apply_storage_change(&mut self, change: StorageChange) {
+        match change {
+            StorageChange::ClassList => self.request_class_list(),
+            StorageChange::SetWritten { class_name, path } if class_name == self.storage_class_name => {
+                let label = format!("Reloading {}", path.file_name().and_then(|n| n.to_str()).unwrap_or("set"));
+                self.run_storage(label, move || {
+                    let result = crate::storage::load_study_set_from_file(&path);
+                    (class_name, path, result)
+                }, |app, (class_name, path, result)| {
+                    // a half-written file fails to parse; the rename that completes it raises another event
+                    if let Ok(disk) = result
+                        && class_name == app.storage_class_name
+                    {
+                        app.apply_set_from_disk(&class_name, &path, disk);
+                    }
+                });
+            }
+            StorageChange::SetRemoved { class_name, path } if class_name == self.storage_class_name => {
+                // checked on the worker: a rename or move we queued ourselves has been applied
+                // to the loaded sets by the time this result comes back
+                self.run_storage("Checking storage folder", move || {
+                    let gone = !path.exists();
+                    (class_name, path, gone)
+                }, |app, (class_name, path, gone)| {
+                    if gone && class_name == app.storage_class_name {
+                        app.apply_set_removed(&class_name, &path);
+                    }
+                });
+            }
+            // changes in other classes only matter for the class manager counts
+            StorageChange::SetWritten { .. } | StorageChange::SetRemoved { .. } => {
+                if self.show_manage_classes_popup {
+                    self.refresh_class_summaries();
+                }
+            }
+        }
+    }
+
+    /// Take over a set file that changed on disk, unless the loaded copy has unsaved edits.
+    fn apply_set_from_disk(&mut self, class_name: &str, path: &std::path::Path, disk: AppStudySet) {
+        let base = std::path::PathBuf::from(&self.storage_base_path);
+        let existing = self.study_sets.iter().position(|s| {
+            s.name() == disk.name() || crate::storage::set_file_path(&base, class_name, s.name()) == path
+        });
+        match existing {
+            // our own save (possibly edited again since), or a sync tool rewriting identical content
+            Some(idx) if self.study_sets[idx] == disk || self.last_saved.get(disk.name()) == Some(&disk) => {}
+            Some(idx) if self.is_dirty(self.study_sets[idx].name()) => {
+                let set_name = self.study_sets[idx].name().to_string();
+                self.add_set_conflict(set_name, Some(disk));
+            }
+            Some(idx) => {
+                log::info!("Reloaded set '{}' after an external change", disk.name());
+                self.status_message = format!("Reloaded '{}' (changed on disk)", disk.name());
+                self.study_sets[idx] = disk;
+                self.clamp_selection_indexes();
+            }
+            None => {
+                log::info!("Found new set '{}' on disk", disk.name());
+                self.status_message = format!("Added set '{}' from disk", disk.name());
+                self.study_sets.push(disk);
+                if self.selected_set.is_none() {
+                    self.selected_set = Some(0);
+                }
+            }
+        }
+    }
+
+    /// Drop a loaded set whose file was removed outside the app, unless it has unsaved edits.
+    fn apply_set_removed(&mut self, class_name: &str, path: &std::path::Path) {
+        let base = std::path::PathBuf::from(&self.storage_base_path);
+        let Some(idx) = self.study_sets.iter().position(|s| crate::storage::set_file_path(&base, class_name, s.name()) == path) else { return };
+        let set_name = self.study_sets[idx].name().to_string();
+        if self.is_dirty(&set_name) {
+            self.add_set_conflict(set_name, None);
+        } else {
+            log::info!("Set '{}' was removed outside the app", set_name);
+            self.status_message = format!("Set '{}' was removed on disk", set_name);
+            self.remove_loaded_set(idx);
+        }
+    }
+
+    fn add_set_conflict(&mut self, set_name: String, disk: Option<AppStudySet>) {
+        log::warn!("Set '{}' changed on disk while it has unsaved edits", set_name);
+        self.status_message = format!("'{}' changed on disk while it has unsaved edits", set_name);
+        match self.set_conflicts.iter_mut().find(|c| c.set_name == set_name) {
+            Some(conflict) => conflict.disk = disk,
+            None => self.set_conflicts.push(SetConflict { set_name, disk }),
+        }
+    }
+
+    pub(super) fn has_conflict(&self, set_name: &str) -> bool {
+        self.set_conflicts.iter().any(|c| c.set_name == set_name)
+    }
+
+    /// Drop the in-memory set at `idx` (its file is already gone) and fix up the selection.
+    fn remove_loaded_set(&mut self, idx: usize) {
+        let removed = self.study_sets.remove(idx);
+        self.dirty_sets.remove(removed.name());
+        self.selected_set = match self.selected_set {
+            _ if self.study_sets.is_empty() => None,
+            Some(sel) if sel > idx => Some(sel - 1),
+            Some(sel) if sel == idx => {
+                self.selected_quiz = None;
+                self.current_card_index = 0;
+                Some(idx.min(self.study_sets.len() - 1))
+            }
+            other => other,
+        };
+    }
+
+    /// Clamp card/quiz indexes after the selected set's contents were replaced.
+    fn clamp_selection_indexes(&mut self) {
+        if let Some(set) = self.selected_set.and_then(|i| self.study_sets.get(i)) {
+            let cards = set.get_all_flashcards().len();
+            if self.current_card_index >= cards {
+                self.current_card_index = 0;
+            }
+            if self.editing_card.is_some_and(|i| i >= cards) {
+                self.editing_card = None;
+            }
+            if self.selected_quiz.is_some_and(|q| q >= set.get_all_quizzes().len()) {
+                self.selected_quiz = None;
+            }
+        }
+    }
+
+    /// Lets the user pick between the in-app edits and the version changed on disk.
+    pub(super) fn conflicts_window(&mut self, ctx: &egui::Context) {
+        let mut keep_mine: Option<usize> = None;
+        let mut use_disk: Option<usize> = None;
+        egui::Window::new("Changed on Disk").collapsible(false).resizable(false).order(egui::Order::Foreground).show(ctx, |ui_win| {
+            for (i, conflict) in self.set_conflicts.iter().enumerate() {
+                let what = if conflict.disk.is_some() { "was changed" } else { "was deleted" };
+                ui_win.label(format!("Set '{}' {} outside Study Helper while it has unsaved edits.", conflict.set_name, what));
+                ui_win.horizontal(|ui_h| {
+                    if ui_h.button("Keep my version").on_hover_text("Save the in-app version; the disk version is kept as a backup").clicked() {
+                        keep_mine = Some(i);
+                    }
+                    let disk_label = if conflict.disk.is_some() { "Use disk version" } else { "Discard my edits" };
+                    if ui_h.button(disk_label).clicked() {
+                        use_disk = Some(i);
+                    }
+                });
+                ui_win.separator();
+            }
+            ui_win.label(RichText::new("Autosave is paused for these sets until you choose.").small());
+        });
+
+        if let Some(i) = keep_mine {
+            let conflict = self.set_conflicts.remove(i);
+            if let Some(idx) = self.study_sets.iter().position(|s| s.name() == conflict.set_name) {
+                self.persist_set(idx);
+                self.status_message = format!("Keeping your version of '{}'", conflict.set_name);
+            }
+        } else if let Some(i) = use_disk {
+            let conflict = self.set_conflicts.remove(i);
+            self.dirty_sets.remove(&conflict.set_name);
+            if let Some(idx) = self.study_sets.iter().position(|s| s.name() == conflict.set_name) {
+                match conflict.disk {
+                    Some(disk) => {
+                        self.study_sets[idx] = disk;
+                        self.clamp_selection_indexes();
+                        self.status_message = format!("Reloaded '{}' from disk", conflict.set_name);
+                    }
+                    None => {
+                        self.remove_loaded_set(idx);
+                        self.status_message = format!("Removed '{}' (deleted on disk)", conflict.set_name);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,167 @@
+// src/gui/trash_view.rs
+use eframe::egui;
+use egui::RichText;
+use crate::storage::{TrashEntry, TrashedItem};
+use super::StudyHelperApp;
+
+impl StudyHelperApp {
+    /// Purge expired entries of the current class's trash and reload the list.
+    pub(super) fn refresh_trash(&mut self) {
+        self.trash_entries.clear();
+        if !self.storage_configured() {
+            return;
+        }
+        let base = std::path::PathBuf::from(&self.storage_base_path);
+        let class_name = self.storage_class_name.clone();
+        let retention_days = self.config.trash_retention_days;
+        self.run_storage("Reading trash", move || {
+            if let Err(e) = crate::storage::purge_expired_trash(&base, &class_name, retention_days) {
+                log::warn!("Failed to purge trash of class '{}': {}", class_name, e);
+            }
+            let result = crate::storage::list_trash(&base, &class_name);
+            (class_name, result)
+        }, |app, (class_name, result)| {
+            if class_name != app.storage_class_name {
+                return;
+            }
+            match result {
+                Ok(entries) => app.trash_entries = entries,
+                Err(e) => app.status_message = format!("Error reading trash: {}", e),
+            }
+        });
+    }
+
+    /// Trash of the current class: restore deleted items or purge them permanently.
+    pub(super) fn trash_view(&mut self, ui: &mut egui::Ui, scale: f32) {
+        ui.label(RichText::new("Trash").heading());
+        ui.add_space((6.0 * scale).round());
+
+        if !self.storage_configured() {
+            ui.label("Set storage not configured. Open 'Create New Set' and set Base folder and Class folder.");
+            return;
+        }
+
+        ui.label(format!("Class: {}", self.storage_class_name));
+        ui.horizontal(|ui_h| {
+            ui_h.label("Keep deleted items for");
+            let mut days = self.config.trash_retention_days;
+            ui_h.add(egui::DragValue::new(&mut days).range(1..=3650));
+            ui_h.label("days");
+            if days != self.config.trash_retention_days {
+                self.config.trash_retention_days = days;
+                self.persist_config();
+            }
+            if ui_h.small_button("Refresh").clicked() {
+                self.refresh_trash();
+            }
+        });
+        ui.add_space((6.0 * scale).round());
+
+        let entries = self.trash_entries.clone();
+        let mut restore: Option<TrashEntry> = None;
+        let mut purge: Option<TrashEntry> = None;
+        if entries.is_empty() {
+            ui.label(RichText::new("(trash is empty)").italics());
+        } else {
+            egui::ScrollArea::vertical().max_height(ui.available_height() - (40.0 * scale).round()).show(ui, |ui_s| {
+                egui::Grid::new("trash_grid").striped(true).show(ui_s, |ui_g| {
+                    ui_g.label(RichText::new("Deleted").strong());
+                    ui_g.label(RichText::new("From set").strong());
+                    ui_g.label(RichText::new("Item").strong());
+                    ui_g.label("");
+                    ui_g.end_row();
+                    for entry in &entries {
+                        ui_g.label(crate::storage::format_timestamp(entry.deleted_at));
+                        ui_g.label(&entry.set_name);
+                        ui_g.label(entry.label());
+                        ui_g.horizontal(|ui_h| {
+                            if ui_h.small_button("Restore").clicked() {
+                                restore = Some(entry.clone());
+                            }
+                            if ui_h.small_button("Delete permanently").clicked() {
+                                purge = Some(entry.clone());
+                            }
+                        });
+                        ui_g.end_row();
+                    }
+                });
+            });
+        }
+
+        if !self.status_message.is_empty() {
+            ui.add_space((6.0 * scale).round());
+            ui.label(&self.status_message);
+        }
+
+        if let Some(entry) = restore {
+            self.restore_trash_entry(entry);
+        }
+        if let Some(entry) = purge {
+            let label = entry.label();
+            self.run_storage("Purging trash entry", move || crate::storage::remove_trash_entry(&entry), move |app, result| {
+                match result {
+                    Ok(()) => app.status_message = format!("Permanently deleted {}", label),
+                    Err(e) => app.status_message = format!("Purge error: {}", e),
+                }
+                app.refresh_trash();
+            });
+        }
+    }
+
+    /// Put a trashed item back where it came from and persist the affected set.
+    fn restore_trash_entry(&mut self, entry: TrashEntry) {
+        let label = entry.label();
+        let set_idx = match entry.item.clone() {
+            TrashedItem::Set(mut set) => {
+                // the name may have been reused since the set was deleted
+                let name = crate::storage::unique_set_name(set.name(), |n| self.set_name_taken(n, None));
+                set.set_name(name);
+                self.study_sets.push(set);
+                self.study_sets.len() - 1
+            }
+            TrashedItem::Flashcard(card) => {
+                let Some(idx) = self.study_sets.iter().position(|s| s.name() == entry.set_name) else {
+                    self.status_message = format!("Cannot restore: set '{}' no longer exists", entry.set_name);
+                    return;
+                };
+                self.study_sets[idx].add_flashcard(card);
+                // undoing the card's delete would add it a second time
+                self.history.forget_set(&entry.set_name);
+                idx
+            }
+            TrashedItem::Quiz(quiz) => {
+                let Some(idx) = self.study_sets.iter().position(|s| s.name() == entry.set_name) else {
+                    self.status_message = format!("Cannot restore: set '{}' no longer exists", entry.set_name);
+                    return;
+                };
+                self.study_sets[idx].add_quiz(quiz);
+                self.history.forget_set(&entry.set_name);
+                idx
+            }
+        };
+        if self.selected_set.is_none() {
+            self.selected_set = Some(set_idx);
+        }
+
+        // the entry is only removed once the set is back on disk
+        let set = self.study_sets[set_idx].clone();
+        self.note_unsaved(set.name());
+        let location = self.storage_location();
+        let class_name = self.storage_class_name.clone();
+        self.run_storage(format!("Restoring {}", label), move || {
+            let result = location.open().and_then(|store| store.save_set(&class_name, &set));
+            if result.is_ok()
+                && let Err(e) = crate::storage::remove_trash_entry(&entry)
+            {
+                log::warn!("Restored '{}' but could not remove its trash entry: {}", label, e);
+            }
+            (class_name, set, label, result)
+        }, |app, (class_name, set, label, result)| {
+            if result.is_ok() {
+                app.status_message = format!("Restored {} into set '{}'", label, set.name());
+            }
+            app.apply_set_saved(&class_name, set, result);
+            app.refresh_trash();
+        });
+    }
+}
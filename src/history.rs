@@ -0,0 +1,298 @@
+// src/history.rs
+
+/*
+Undo/redo support for edits made in the GUI. Every edit to a set's cards or
+quizzes is described by an `EditCommand` that knows how to apply and revert
+itself; `EditHistory` keeps the undo and redo stacks.
+
+Commands refer to sets by name (names are unique within a class folder) and
+the history is tied to a single class: it is cleared when the class changes.
+*/
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use crate::models::{Flashcard, Quiz, StudySet};
+
+/// Trash entry written for a deleted card or quiz. The storage worker fills it in
+/// once the entry is on disk; undo and redo jobs take and replace it on the worker.
+pub type TrashSlot = Arc<Mutex<Option<PathBuf>>>;
+
+/// Maximum number of undo steps kept.
+pub const HISTORY_LIMIT: usize = 100;
+
+/// A reversible edit to one study set.
+#[derive(Clone)]
+pub enum EditCommand {
+    AddCard { set: String, index: usize, card: Flashcard },
+    /// Several cards inserted together starting at `index` (e.g. from an outline import).
+    AddCards { set: String, index: usize, cards: Vec<Flashcard> },
+    EditCard { set: String, index: usize, before: Flashcard, after: Flashcard },
+    /// `trash` is the trash entry written for the deleted card, removed again on undo.
+    DeleteCard { set: String, index: usize, card: Flashcard, trash: TrashSlot },
+    AddHint { set: String, card: usize, hint: String },
+    RemoveHint { set: String, card: usize, index: usize, hint: String },
+    CreateQuiz { set: String, index: usize, quiz: Quiz },
+    /// Snapshot-based quiz edit (Apply/Save, title changes, added/removed questions).
+    EditQuiz { set: String, index: usize, before: Quiz, after: Quiz, label: String },
+    DeleteQuiz { set: String, index: usize, quiz: Quiz, trash: TrashSlot },
+}
+
+impl EditCommand {
+    /// Name of the set this command edits.
+    pub fn set_name(&self) -> &str {
+        match self {
+            EditCommand::AddCard { set, .. }
+            | EditCommand::AddCards { set, .. }
+            | EditCommand::EditCard { set, .. }
+            | EditCommand::DeleteCard { set, .. }
+            | EditCommand::AddHint { set, .. }
+            | EditCommand::RemoveHint { set, .. }
+            | EditCommand::CreateQuiz { set, .. }
+            | EditCommand::EditQuiz { set, .. }
+            | EditCommand::DeleteQuiz { set, .. } => set,
+        }
+    }
+
+    /// Short description for the edit-history panel.
+    pub fn description(&self) -> String {
+        match self {
+            EditCommand::AddCard { card, .. } => format!("Add card '{}'", card.question()),
+            EditCommand::AddCards { cards, .. } => format!("Add {} cards", cards.len()),
+            EditCommand::EditCard { after, .. } => format!("Edit card '{}'", after.question()),
+            EditCommand::DeleteCard { card, .. } => format!("Delete card '{}'", card.question()),
+            EditCommand::AddHint { hint, .. } => format!("Add hint '{}'", hint),
+            EditCommand::RemoveHint { hint, .. } => format!("Remove hint '{}'", hint),
+            EditCommand::CreateQuiz { quiz, .. } => format!("Create quiz '{}'", quiz.title()),
+            EditCommand::EditQuiz { label, after, .. } => format!("{} ('{}')", label, after.title()),
+            EditCommand::DeleteQuiz { quiz, .. } => format!("Delete quiz '{}'", quiz.title()),
+        }
+    }
+
+    /// Whether applying or reverting the command changes the card at `index`
+    /// or moves it to another position.
+    pub fn affects_card(&self, index: usize) -> bool {
+        match self {
+            EditCommand::AddCard { index: at, .. } | EditCommand::AddCards { index: at, .. } | EditCommand::DeleteCard { index: at, .. } => *at <= index,
+            EditCommand::EditCard { index: at, .. } | EditCommand::AddHint { card: at, .. } | EditCommand::RemoveHint { card: at, .. } => *at == index,
+            EditCommand::CreateQuiz { .. } | EditCommand::EditQuiz { .. } | EditCommand::DeleteQuiz { .. } => false,
+        }
+    }
+
+    /// Apply (or re-apply) the command. Returns false if its target no longer exists.
+    pub fn apply(&self, sets: &mut [StudySet]) -> bool {
+        let Some(set) = sets.iter_mut().find(|s| s.name() == self.set_name()) else { return false };
+        match self {
+            EditCommand::AddCard { index, card, .. } => {
+                set.insert_flashcard(*index, card.clone());
+                true
+            }
+            EditCommand::AddCards { index, cards, .. } => {
+                for (i, card) in cards.iter().enumerate() {
+                    set.insert_flashcard(index + i, card.clone());
+                }
+                true
+            }
+            EditCommand::EditCard { index, after, .. } => copy_card_text(set, *index, after),
+            EditCommand::DeleteCard { index, .. } => set.remove_flashcard(*index).is_some(),
+            EditCommand::AddHint { card, hint, .. } => match set.get_flashcard_mut(*card) {
+                Some(c) => {
+                    c.add_hint(hint.clone());
+                    true
+                }
+                None => false,
+            },
+            EditCommand::RemoveHint { card, index, .. } => set.get_flashcard_mut(*card).and_then(|c| c.remove_hint(*index)).is_some(),
+            EditCommand::CreateQuiz { index, quiz, .. } => {
+                set.insert_quiz(*index, quiz.clone());
+                true
+            }
+            EditCommand::EditQuiz { index, after, .. } => match set.get_quiz_mut(*index) {
+                Some(q) => {
+                    *q = after.clone();
+                    true
+                }
+                None => false,
+            },
+            EditCommand::DeleteQuiz { index, .. } => set.remove_quiz(*index).is_some(),
+        }
+    }
+
+    /// Undo the command. Returns false if its target no longer exists.
+    pub fn revert(&self, sets: &mut [StudySet]) -> bool {
+        let Some(set) = sets.iter_mut().find(|s| s.name() == self.set_name()) else { return false };
+        match self {
+            EditCommand::AddCard { index, .. } => set.remove_flashcard(*index).is_some(),
+            EditCommand::AddCards { index, cards, .. } => {
+                if set.get_all_flashcards().len() < index + cards.len() {
+                    return false;
+                }
+                for _ in cards {
+                    set.remove_flashcard(*index);
+                }
+                true
+            }
+            EditCommand::EditCard { index, before, .. } => copy_card_text(set, *index, before),
+            EditCommand::DeleteCard { index, card, .. } => {
+                set.insert_flashcard(*index, card.clone());
+                true
+            }
+            EditCommand::AddHint { card, .. } => match set.get_flashcard_mut(*card) {
+                Some(c) => {
+                    let last = c.get_hints().len().saturating_sub(1);
+                    c.remove_hint(last).is_some()
+                }
+                None => false,
+            },
+            EditCommand::RemoveHint { card, index, hint, .. } => match set.get_flashcard_mut(*card) {
+                Some(c) => {
+                    c.insert_hint(*index, hint.clone());
+                    true
+                }
+                None => false,
+            },
+            EditCommand::CreateQuiz { index, .. } => set.remove_quiz(*index).is_some(),
+            EditCommand::EditQuiz { index, before, .. } => match set.get_quiz_mut(*index) {
+                Some(q) => {
+                    *q = before.clone();
+                    true
+                }
+                None => false,
+            },
+            EditCommand::DeleteQuiz { index, quiz, .. } => {
+                set.insert_quiz(*index, quiz.clone());
+                true
+            }
+        }
+    }
+}
+
+/// Give the card at `index` the text of `text`; reviews made since the edit stay.
+fn copy_card_text(set: &mut StudySet, index: usize, text: &Flashcard) -> bool {
+    match set.get_flashcard_mut(index) {
+        Some(card) => {
+            card.set_text(text.question().to_string(), text.answer().to_string(), text.get_hints().clone());
+            true
+        }
+        None => false,
+    }
+}
+
+/// Multi-level undo/redo stacks for the sets of one class.
+#[derive(Default)]
+pub struct EditHistory {
+    class_name: String,
+    undo: Vec<EditCommand>,
+    redo: Vec<EditCommand>,
+}
+
+impl EditHistory {
+    /// Record a command that has already been applied. Clears the redo stack.
+    pub fn record(&mut self, command: EditCommand) {
+        self.undo.push(command);
+        if self.undo.len() > HISTORY_LIMIT {
+            self.undo.remove(0);
+        }
+        self.redo.clear();
+    }
+
+    /// Revert the most recent command. Returns it (now on the redo stack) or an
+    /// error when its set is gone, in which case the command is dropped.
+    pub fn undo(&mut self, sets: &mut [StudySet]) -> Result<Option<&mut EditCommand>, String> {
+        let Some(command) = self.undo.pop() else { return Ok(None) };
+        if !command.revert(sets) {
+            return Err(format!("Cannot undo '{}': set '{}' has changed", command.description(), command.set_name()));
+        }
+        self.redo.push(command);
+        Ok(self.redo.last_mut())
+    }
+
+    /// Re-apply the most recently undone command.
+    pub fn redo(&mut self, sets: &mut [StudySet]) -> Result<Option<&mut EditCommand>, String> {
+        let Some(command) = self.redo.pop() else { return Ok(None) };
+        if !command.apply(sets) {
+            return Err(format!("Cannot redo '{}': set '{}' has changed", command.description(), command.set_name()));
+        }
+        self.undo.push(command);
+        Ok(self.undo.last_mut())
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Undo stack, most recent first.
+    pub fn undo_entries(&self) -> impl Iterator<Item = &EditCommand> {
+        self.undo.iter().rev()
+    }
+
+    /// Redo stack, next redo first.
+    pub fn redo_entries(&self) -> impl Iterator<Item = &EditCommand> {
+        self.redo.iter().rev()
+    }
+
+    /// Drop every step of `set_name`, e.g. after a change the steps do not know about
+    /// (a card restored from the trash would otherwise be re-inserted by undoing its delete).
+    pub fn forget_set(&mut self, set_name: &str) {
+        self.undo.retain(|c| c.set_name() != set_name);
+        self.redo.retain(|c| c.set_name() != set_name);
+    }
+
+    /// Forget all history when the loaded class changes (commands refer to its sets).
+    pub fn sync_class(&mut self, class_name: &str) {
+        if self.class_name != class_name {
+            self.class_name = class_name.to_string();
+            self.undo.clear();
+            self.redo.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card(question: &str) -> Flashcard {
+        Flashcard::new(question.to_string(), "answer".to_string(), Vec::new())
+    }
+
+    #[test]
+    fn card_commands_affect_the_cards_they_change_or_move() {
+        let set = "Set".to_string();
+        let add = EditCommand::AddCard { set: set.clone(), index: 1, card: card("new") };
+        assert!(!add.affects_card(0) && add.affects_card(1) && add.affects_card(5));
+        let hint = EditCommand::AddHint { set: set.clone(), card: 2, hint: "h".to_string() };
+        assert!(hint.affects_card(2) && !hint.affects_card(3) && !hint.affects_card(1));
+        let edit = EditCommand::EditCard { set: set.clone(), index: 0, before: card("a"), after: card("b") };
+        assert!(edit.affects_card(0) && !edit.affects_card(1));
+        let quiz = EditCommand::CreateQuiz { set, index: 0, quiz: Quiz::new("Q".to_string()) };
+        assert!(!quiz.affects_card(0));
+    }
+
+    #[test]
+    fn undoing_an_edit_keeps_later_reviews() {
+        let mut sets = vec![StudySet::new("Set".to_string())];
+        sets[0].add_flashcard(card("after"));
+        let edit = EditCommand::EditCard { set: "Set".to_string(), index: 0, before: card("before"), after: card("after") };
+        let review = crate::models::schedule_sm2(None, crate::models::Grade::Good, 1_000);
+        sets[0].get_flashcard_mut(0).unwrap().set_review(crate::models::DEFAULT_PROFILE, Some(review.clone()));
+
+        assert!(edit.revert(&mut sets));
+        let reverted = &sets[0].get_all_flashcards()[0];
+        assert_eq!(reverted.question(), "before");
+        assert_eq!(reverted.review(crate::models::DEFAULT_PROFILE), Some(&review));
+    }
+
+    #[test]
+    fn forgetting_a_set_keeps_the_other_sets_steps() {
+        let mut history = EditHistory::default();
+        let trash = TrashSlot::default();
+        history.record(EditCommand::DeleteCard { set: "A".to_string(), index: 0, card: card("a"), trash });
+        history.record(EditCommand::AddCard { set: "B".to_string(), index: 0, card: card("b") });
+        history.record(EditCommand::AddHint { set: "A".to_string(), card: 0, hint: "h".to_string() });
+        history.forget_set("A");
+        assert_eq!(history.undo_entries().map(EditCommand::set_name).collect::<Vec<_>>(), ["B"]);
+    }
+}
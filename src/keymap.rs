@@ -0,0 +1,243 @@
+// src/keymap.rs
+
+/*
+Keyboard shortcuts. Every remappable command is an `Action`; `Keymap` maps each
+action to one key combination and is stored in the config as a map from action
+name to a combination such as "Ctrl+S". Entries that are missing or cannot be
+read fall back to the defaults, so an edited config never loses a shortcut.
+*/
+use std::collections::BTreeMap;
+use std::fmt;
+use eframe::egui::{Key, KeyboardShortcut, Modifiers};
+use serde::{Serialize, Deserialize};
+
+/// A command that can be triggered from the keyboard.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Action {
+    Flip,
+    PreviousCard,
+    NextCard,
+    ToggleHint,
+    GradeAgain,
+    GradeHard,
+    GradeGood,
+    GradeEasy,
+    Submit,
+    Save,
+    NewCard,
+    Search,
+    Undo,
+    Redo,
+    ShowShortcuts,
+}
+
+impl Action {
+    pub const ALL: [Action; 15] = [
+        Action::Flip, Action::PreviousCard, Action::NextCard, Action::ToggleHint,
+        Action::GradeAgain, Action::GradeHard, Action::GradeGood, Action::GradeEasy,
+        Action::Submit, Action::Save, Action::NewCard, Action::Search,
+        Action::Undo, Action::Redo, Action::ShowShortcuts,
+    ];
+
+    /// Name used in the config file.
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::Flip => "flip",
+            Action::PreviousCard => "previous_card",
+            Action::NextCard => "next_card",
+            Action::ToggleHint => "toggle_hint",
+            Action::GradeAgain => "grade_again",
+            Action::GradeHard => "grade_hard",
+            Action::GradeGood => "grade_good",
+            Action::GradeEasy => "grade_easy",
+            Action::Submit => "submit_answer",
+            Action::Save => "save",
+            Action::NewCard => "new_card",
+            Action::Search => "search",
+            Action::Undo => "undo",
+            Action::Redo => "redo",
+            Action::ShowShortcuts => "show_shortcuts",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Action::Flip => "Flip card",
+            Action::PreviousCard => "Previous card",
+            Action::NextCard => "Next card",
+            Action::ToggleHint => "Show / hide hint",
+            Action::GradeAgain => "Grade: again",
+            Action::GradeHard => "Grade: hard",
+            Action::GradeGood => "Grade: good",
+            Action::GradeEasy => "Grade: easy",
+            Action::Submit => "Submit answer / next question",
+            Action::Save => "Save",
+            Action::NewCard => "New card",
+            Action::Search => "Search cards",
+            Action::Undo => "Undo",
+            Action::Redo => "Redo",
+            Action::ShowShortcuts => "Show this cheat sheet",
+        }
+    }
+
+    /// Heading the action is listed under in the cheat sheet.
+    pub fn group(self) -> &'static str {
+        match self {
+            Action::Flip | Action::PreviousCard | Action::NextCard | Action::ToggleHint
+            | Action::GradeAgain | Action::GradeHard | Action::GradeGood | Action::GradeEasy => "Studying flashcards",
+            Action::Submit => "Quiz session",
+            Action::Save | Action::NewCard | Action::Search | Action::Undo | Action::Redo => "Editing",
+            Action::ShowShortcuts => "General",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Action> {
+        Action::ALL.into_iter().find(|a| a.name() == name)
+    }
+
+    fn default_binding(self) -> Binding {
+        let (modifiers, key) = match self {
+            Action::Flip => (Modifiers::NONE, Key::Space),
+            Action::PreviousCard => (Modifiers::NONE, Key::ArrowLeft),
+            Action::NextCard => (Modifiers::NONE, Key::ArrowRight),
+            Action::ToggleHint => (Modifiers::NONE, Key::H),
+            Action::GradeAgain => (Modifiers::NONE, Key::Num1),
+            Action::GradeHard => (Modifiers::NONE, Key::Num2),
+            Action::GradeGood => (Modifiers::NONE, Key::Num3),
+            Action::GradeEasy => (Modifiers::NONE, Key::Num4),
+            Action::Submit => (Modifiers::NONE, Key::Enter),
+            Action::Save => (Modifiers::COMMAND, Key::S),
+            Action::NewCard => (Modifiers::COMMAND, Key::N),
+            Action::Search => (Modifiers::COMMAND, Key::F),
+            Action::Undo => (Modifiers::COMMAND, Key::Z),
+            Action::Redo => (Modifiers::COMMAND | Modifiers::SHIFT, Key::Z),
+            Action::ShowShortcuts => (Modifiers::NONE, Key::F1),
+        };
+        Binding { key, modifiers }
+    }
+}
+
+/// A key with optional Ctrl (Cmd on macOS), Shift and Alt.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Binding {
+    pub key: Key,
+    pub modifiers: Modifiers,
+}
+
+impl Binding {
+    /// Keep only the modifiers a binding can use; Ctrl and Cmd both mean "command".
+    pub fn new(key: Key, pressed: Modifiers) -> Binding {
+        let mut modifiers = Modifiers::NONE;
+        if pressed.command || pressed.ctrl {
+            modifiers |= Modifiers::COMMAND;
+        }
+        if pressed.shift {
+            modifiers |= Modifiers::SHIFT;
+        }
+        if pressed.alt {
+            modifiers |= Modifiers::ALT;
+        }
+        Binding { key, modifiers }
+    }
+
+    pub fn shortcut(self) -> KeyboardShortcut {
+        KeyboardShortcut::new(self.modifiers, self.key)
+    }
+
+    /// Number of modifiers; more specific bindings are matched first.
+    pub fn modifier_count(self) -> usize {
+        [self.modifiers.command, self.modifiers.shift, self.modifiers.alt].iter().filter(|m| **m).count()
+    }
+
+    /// Parse "Ctrl+Shift+S", "Space", "Alt+Left", ...
+    pub fn parse(text: &str) -> Option<Binding> {
+        let mut parts: Vec<&str> = text.split('+').map(str::trim).collect();
+        // "Ctrl++" binds the plus key
+        if text.ends_with("++") {
+            parts.truncate(parts.len() - 2);
+            parts.push("+");
+        }
+        let key = Key::from_name(parts.pop()?)?;
+        let mut modifiers = Modifiers::NONE;
+        for part in parts {
+            modifiers |= match part.to_ascii_lowercase().as_str() {
+                "ctrl" | "cmd" | "command" => Modifiers::COMMAND,
+                "shift" => Modifiers::SHIFT,
+                "alt" | "option" => Modifiers::ALT,
+                _ => return None,
+            };
+        }
+        Some(Binding { key, modifiers })
+    }
+}
+
+impl fmt::Display for Binding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.command {
+            f.write_str("Ctrl+")?;
+        }
+        if self.modifiers.shift {
+            f.write_str("Shift+")?;
+        }
+        if self.modifiers.alt {
+            f.write_str("Alt+")?;
+        }
+        f.write_str(self.key.name())
+    }
+}
+
+/// The key combination of every action.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(from = "BTreeMap<String, String>", into = "BTreeMap<String, String>")]
+pub struct Keymap {
+    bindings: BTreeMap<Action, Binding>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Keymap { bindings: Action::ALL.into_iter().map(|a| (a, a.default_binding())).collect() }
+    }
+}
+
+impl Keymap {
+    pub fn binding(&self, action: Action) -> Binding {
+        self.bindings.get(&action).copied().unwrap_or_else(|| action.default_binding())
+    }
+
+    pub fn set(&mut self, action: Action, binding: Binding) {
+        self.bindings.insert(action, binding);
+    }
+
+    /// Other actions bound to the same combination as `action`.
+    pub fn conflicts(&self, action: Action) -> Vec<Action> {
+        let binding = self.binding(action);
+        Action::ALL.into_iter().filter(|a| *a != action && self.binding(*a) == binding).collect()
+    }
+
+    /// All actions, the ones with the most modifiers first, so that a Ctrl+Shift
+    /// binding is checked before a Ctrl binding of the same key.
+    pub fn by_specificity(&self) -> Vec<(Action, Binding)> {
+        let mut all: Vec<(Action, Binding)> = Action::ALL.into_iter().map(|a| (a, self.binding(a))).collect();
+        all.sort_by_key(|(_, b)| std::cmp::Reverse(b.modifier_count()));
+        all
+    }
+}
+
+impl From<BTreeMap<String, String>> for Keymap {
+    fn from(stored: BTreeMap<String, String>) -> Self {
+        let mut keymap = Keymap::default();
+        for (name, text) in stored {
+            match (Action::from_name(&name), Binding::parse(&text)) {
+                (Some(action), Some(binding)) => keymap.set(action, binding),
+                _ => log::warn!("Ignoring unknown shortcut '{}' = '{}' in config", name, text),
+            }
+        }
+        keymap
+    }
+}
+
+impl From<Keymap> for BTreeMap<String, String> {
+    fn from(keymap: Keymap) -> Self {
+        Action::ALL.into_iter().map(|a| (a.name().to_string(), keymap.binding(a).to_string())).collect()
+    }
+}
@@ -3,13 +3,53 @@ mod app;
 mod models;
 mod gui;
 mod storage;
+mod history;
+mod formats;
+mod keymap;
+mod theme;
+
+const USAGE: &str = "Usage: study_helper [--config <path>]
+
+Options:
+  --config <path>  Read and save settings in <path> instead of the default location
+  -h, --help       Show this help
+
+The STUDY_HELPER_CONFIG environment variable also sets the settings file; --config wins.";
+
+/// Apply the command-line options. Returns false if the app should not start;
+/// exits with status 2 on a bad argument.
+fn parse_args() -> bool {
+    let mut args = std::env::args_os().skip(1);
+    while let Some(arg) = args.next() {
+        let text = arg.to_string_lossy();
+        if text == "-h" || text == "--help" {
+            println!("{}", USAGE);
+            return false;
+        } else if text == "--config" {
+            let Some(path) = args.next() else {
+                eprintln!("--config needs a path\n\n{}", USAGE);
+                std::process::exit(2);
+            };
+            storage::set_config_path(path.into());
+        } else if let Some(path) = text.strip_prefix("--config=") {
+            storage::set_config_path(path.into());
+        } else {
+            eprintln!("Unknown argument '{}'\n\n{}", text, USAGE);
+            std::process::exit(2);
+        }
+    }
+    true
+}
 
 /// Application entry point. Initializes logging and starts the eframe GUI.
 fn main() {
     // Configure logging from RUST_LOG environment variable (default to info)
     env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
 
-    log::info!("Starting Study Helper");
+    if !parse_args() {
+        return;
+    }
+    log::info!("Starting Study Helper (settings in {})", storage::config_path().display());
 
     eframe::run_native(
         "Study Helper",
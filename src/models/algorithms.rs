@@ -0,0 +1,214 @@
+// src/models/algorithms.rs
+
+/*
+Spaced-repetition scheduling. `Grade` is how well a card was remembered and
+`schedule_sm2` computes the card's next `ReviewState` with a variant of the
+SM-2 algorithm: "again" restarts the card, the other grades multiply the
+interval by the card's ease factor, which they also nudge down or up.
+`schedule_leitner` is the simpler alternative: a remembered card moves up
+one box, a forgotten one goes back to box 1, and each box has a fixed interval.
+*/
+use serde::{Serialize, Deserialize};
+use super::flashcard::ReviewState;
+
+const SECS_PER_DAY: u64 = 24 * 60 * 60;
+/// A forgotten card comes back after this many seconds.
+const RELEARN_SECS: u64 = 10 * 60;
+const MIN_EASE: f32 = 1.3;
+
+/// How well a card was remembered, from 1 (forgotten) to 4 (easy).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Grade {
+    Again,
+    Hard,
+    Good,
+    Easy,
+}
+
+impl Grade {
+    pub fn label(self) -> &'static str {
+        match self {
+            Grade::Again => "Again",
+            Grade::Hard => "Hard",
+            Grade::Good => "Good",
+            Grade::Easy => "Easy",
+        }
+    }
+}
+
+/// How graded cards are scheduled.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReviewAlgorithm {
+    #[default]
+    Sm2,
+    Leitner,
+}
+
+impl ReviewAlgorithm {
+    pub const ALL: [ReviewAlgorithm; 2] = [ReviewAlgorithm::Sm2, ReviewAlgorithm::Leitner];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ReviewAlgorithm::Sm2 => "SM-2 (adapts to each card)",
+            ReviewAlgorithm::Leitner => "Leitner boxes",
+        }
+    }
+}
+
+/// Days until the next review for cards in box 1, 2, … of the Leitner system.
+pub const DEFAULT_LEITNER_INTERVALS: [u32; 5] = [1, 2, 4, 8, 16];
+/// Allowed number of Leitner boxes.
+pub const LEITNER_BOXES: std::ops::RangeInclusive<usize> = 2..=10;
+
+/// Box (from 1) a card is in when there are `boxes` boxes. Cards never graded
+/// with the Leitner system start in box 1; cards beyond the last box sit in it.
+pub fn leitner_box(review: Option<&ReviewState>, boxes: usize) -> usize {
+    review.map_or(1, |r| r.leitner_box as usize).clamp(1, boxes.max(1))
+}
+
+/// Next review state with the Leitner system, where `intervals[n]` is the number
+/// of days a card waits in box n + 1. Only `Again` counts as forgotten.
+pub fn schedule_leitner(previous: Option<&ReviewState>, grade: Grade, intervals: &[u32], now: u64) -> ReviewState {
+    let mut state = previous.cloned().unwrap_or_default();
+    let boxes = intervals.len().max(1);
+    let current = leitner_box(previous, boxes);
+    state.last_reviewed_at = now;
+    let next = if grade == Grade::Again {
+        state.repetitions = 0;
+        state.lapses += 1;
+        1
+    } else {
+        state.repetitions += 1;
+        (current + 1).min(boxes)
+    };
+    state.leitner_box = next as u32;
+    state.interval_days = intervals.get(next - 1).copied().unwrap_or(1);
+    state.due_at = now + state.interval_days as u64 * SECS_PER_DAY;
+    state
+}
+
+/// Next review state after grading a card at `now` (Unix seconds).
+pub fn schedule_sm2(previous: Option<&ReviewState>, grade: Grade, now: u64) -> ReviewState {
+    let mut state = previous.cloned().unwrap_or_default();
+    state.last_reviewed_at = now;
+    if grade == Grade::Again {
+        state.repetitions = 0;
+        state.lapses += 1;
+        state.interval_days = 0;
+        state.ease = (state.ease - 0.2).max(MIN_EASE);
+        state.due_at = now + RELEARN_SECS;
+        return state;
+    }
+    let previous_interval = state.interval_days.max(1) as f32;
+    let interval = match (grade, state.repetitions) {
+        (Grade::Hard, 0) => 1.0,
+        (Grade::Hard, _) => previous_interval * 1.2,
+        (Grade::Good, 0) => 1.0,
+        (Grade::Good, 1) => 6.0,
+        (Grade::Good, _) => previous_interval * state.ease,
+        (Grade::Easy, 0) => 4.0,
+        (Grade::Easy, _) => previous_interval * state.ease * 1.3,
+        (Grade::Again, _) => unreachable!("handled above"),
+    };
+    state.ease = match grade {
+        Grade::Hard => (state.ease - 0.15).max(MIN_EASE),
+        Grade::Easy => state.ease + 0.15,
+        _ => state.ease,
+    };
+    state.repetitions += 1;
+    // never shorter than the last interval unless the card was hard
+    state.interval_days = (interval.round() as u32).max(if grade == Grade::Hard { 1 } else { state.interval_days.max(1) });
+    state.due_at = now + state.interval_days as u64 * SECS_PER_DAY;
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::flashcard::DEFAULT_EASE;
+
+    const NOW: u64 = 1_700_000_000;
+
+    /// Grade a new card with every grade in turn, a day after each due date.
+    fn review_all(grades: &[Grade]) -> ReviewState {
+        let mut state: Option<ReviewState> = None;
+        let mut now = NOW;
+        for grade in grades {
+            let next = schedule_sm2(state.as_ref(), *grade, now);
+            now = next.due_at + SECS_PER_DAY;
+            state = Some(next);
+        }
+        state.unwrap()
+    }
+
+    #[test]
+    fn good_reviews_grow_the_interval() {
+        let intervals: Vec<u32> = (1..=5).map(|n| review_all(&vec![Grade::Good; n]).interval_days).collect();
+        assert_eq!(intervals, [1, 6, 15, 38, 95]);
+        let state = review_all(&[Grade::Good, Grade::Good]);
+        assert_eq!((state.repetitions, state.lapses, state.ease), (2, 0, DEFAULT_EASE));
+        assert_eq!(state.due_at, state.last_reviewed_at + 6 * SECS_PER_DAY);
+
+        assert!(review_all(&[Grade::Good, Grade::Good, Grade::Easy]).interval_days > review_all(&[Grade::Good; 3]).interval_days);
+        let hard = review_all(&[Grade::Good, Grade::Good, Grade::Hard]);
+        assert_eq!(hard.interval_days, 7);
+        assert!((hard.ease - (DEFAULT_EASE - 0.15)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn again_restarts_the_card() {
+        let before = review_all(&[Grade::Good, Grade::Good, Grade::Good]);
+        let now = before.due_at;
+        let state = schedule_sm2(Some(&before), Grade::Again, now);
+        assert_eq!((state.repetitions, state.lapses, state.interval_days), (0, 1, 0));
+        assert_eq!(state.due_at, now + RELEARN_SECS);
+        assert!((state.ease - (DEFAULT_EASE - 0.2)).abs() < 1e-6);
+
+        // relearning starts over from the first interval
+        let next = schedule_sm2(Some(&state), Grade::Good, state.due_at);
+        assert_eq!((next.repetitions, next.interval_days, next.lapses), (1, 1, 1));
+    }
+
+    #[test]
+    fn ease_never_drops_below_the_floor() {
+        let state = review_all(&[Grade::Again; 10]);
+        assert_eq!(state.ease, MIN_EASE);
+        assert_eq!(state.lapses, 10);
+        let state = review_all(&[Grade::Hard; 10]);
+        assert_eq!(state.ease, MIN_EASE);
+        assert!(state.interval_days >= 1);
+    }
+
+    #[test]
+    fn leitner_moves_cards_up_one_box_and_back_to_the_first() {
+        let intervals = DEFAULT_LEITNER_INTERVALS;
+        let first = schedule_leitner(None, Grade::Hard, &intervals, NOW);
+        assert_eq!((first.leitner_box, first.interval_days), (2, 2));
+        assert_eq!(first.due_at, NOW + 2 * SECS_PER_DAY);
+        let third = schedule_leitner(Some(&first), Grade::Easy, &intervals, first.due_at);
+        assert_eq!((third.leitner_box, third.interval_days, third.repetitions), (3, 4, 2));
+
+        let again = schedule_leitner(Some(&third), Grade::Again, &intervals, third.due_at);
+        assert_eq!((again.leitner_box, again.interval_days, again.repetitions, again.lapses), (1, 1, 0, 1));
+        assert_eq!(again.due_at, third.due_at + SECS_PER_DAY);
+        // the SM-2 ease is left alone
+        assert_eq!(again.ease, first.ease);
+    }
+
+    #[test]
+    fn leitner_stays_in_the_last_box() {
+        let intervals = [1, 3, 7];
+        let mut state: Option<ReviewState> = None;
+        for _ in 0..5 {
+            state = Some(schedule_leitner(state.as_ref(), Grade::Good, &intervals, NOW));
+        }
+        let state = state.unwrap();
+        assert_eq!((state.leitner_box, state.interval_days), (3, 7));
+
+        // fewer boxes than a card has reached: it counts as being in the last one
+        assert_eq!(leitner_box(Some(&state), 2), 2);
+        let fewer = schedule_leitner(Some(&state), Grade::Good, &[1, 5], NOW);
+        assert_eq!((fewer.leitner_box, fewer.interval_days), (2, 5));
+        assert_eq!(leitner_box(None, 5), 1);
+    }
+}
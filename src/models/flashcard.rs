@@ -1,25 +1,152 @@
+use std::collections::BTreeMap;
 use serde::{Serialize, Deserialize};
 
+/// Profile whose progress is kept in the card's `review` field, as before profiles existed.
+pub const DEFAULT_PROFILE: &str = "Default";
+
 #[derive(Clone, Serialize, Deserialize)]
 pub struct Flashcard {
+    /// Tells the card apart from its neighbours while it is reordered or edited, so a
+    /// store can update the card's own record; not written to set files.
+    #[serde(skip, default = "new_card_id")]
+    id: u64,
     question: String,
     answer: String,
     hints: Vec<String>,
+    /// Study progress of the default profile; absent until the card has been reviewed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    review: Option<ReviewState>,
+    /// Study progress of every other profile, by profile name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    profile_reviews: BTreeMap<String, ReviewState>,
+}
+
+/// Spaced-repetition state of a card for the person studying it.
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+pub struct ReviewState {
+    /// Next review, as Unix seconds.
+    pub due_at: u64,
+    pub last_reviewed_at: u64,
+    pub interval_days: u32,
+    pub repetitions: u32,
+    pub lapses: u32,
+    /// SM-2 ease factor the interval grows by after a successful review.
+    #[serde(default = "default_ease")]
+    pub ease: f32,
+    /// Leitner box the card sits in, from 1; 0 until it is graded with the Leitner system.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub leitner_box: u32,
+}
+
+/// Starting ease factor of the SM-2 algorithm.
+pub const DEFAULT_EASE: f32 = 2.5;
+
+fn default_ease() -> f32 {
+    DEFAULT_EASE
+}
+
+fn is_zero(value: &u32) -> bool {
+    *value == 0
+}
+
+fn new_card_id() -> u64 {
+    fastrand::u64(..)
+}
+
+/// Cards are equal when their content and progress are; the id only tracks where a card went.
+impl PartialEq for Flashcard {
+    fn eq(&self, other: &Self) -> bool {
+        self.question == other.question && self.answer == other.answer && self.hints == other.hints
+            && self.review == other.review && self.profile_reviews == other.profile_reviews
+    }
+}
+
+impl Default for ReviewState {
+    fn default() -> Self {
+        ReviewState { due_at: 0, last_reviewed_at: 0, interval_days: 0, repetitions: 0, lapses: 0, ease: DEFAULT_EASE, leitner_box: 0 }
+    }
 }
 
 impl Flashcard {
     pub fn new(question: String, answer: String, hints: Vec<String>) -> Self {
-        Flashcard { question, answer, hints }
+        Flashcard { id: new_card_id(), question, answer, hints, review: None, profile_reviews: BTreeMap::new() }
+    }
+
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Give the card the id it was stored with.
+    pub fn set_id(&mut self, id: u64) {
+        self.id = id;
+    }
+
+    /// Study progress of `profile`.
+    pub fn review(&self, profile: &str) -> Option<&ReviewState> {
+        if profile == DEFAULT_PROFILE { self.review.as_ref() } else { self.profile_reviews.get(profile) }
+    }
+
+    pub fn set_review(&mut self, profile: &str, review: Option<ReviewState>) {
+        match (profile == DEFAULT_PROFILE, review) {
+            (true, review) => self.review = review,
+            (false, Some(review)) => { self.profile_reviews.insert(profile.to_string(), review); }
+            (false, None) => { self.profile_reviews.remove(profile); }
+        }
+    }
+
+    /// Progress of the profiles other than the default one.
+    pub fn profile_reviews(&self) -> &BTreeMap<String, ReviewState> {
+        &self.profile_reviews
+    }
+
+    pub fn set_profile_reviews(&mut self, reviews: BTreeMap<String, ReviewState>) {
+        self.profile_reviews = reviews;
+    }
+
+    /// Forget the progress of every profile.
+    pub fn clear_reviews(&mut self) {
+        self.review = None;
+        self.profile_reviews.clear();
+    }
+
+    /// Replace the card's text; its id and the progress of every profile stay.
+    pub fn set_text(&mut self, question: String, answer: String, hints: Vec<String>) {
+        self.question = question;
+        self.answer = answer;
+        self.hints = hints;
     }
 
-    pub fn check_answer(&self, user_answer: &str) -> bool {
-        self.answer.eq_ignore_ascii_case(user_answer)
+    /// Whether both cards have the same question, answer and hints.
+    pub fn same_text(&self, other: &Flashcard) -> bool {
+        self.question == other.question && self.answer == other.answer && self.hints == other.hints
+    }
+
+    pub fn check_answer(&self, user_answer: &str, rules: &super::AnswerMatching) -> bool {
+        rules.matches(&self.answer, user_answer)
     }
 
     pub fn get_hints(&self) -> &Vec<String> {
         &self.hints
     }
 
+    pub fn add_hint(&mut self, hint: String) {
+        self.hints.push(hint);
+    }
+
+    /// Insert a hint at `index` (clamped to the end); used to undo a removal.
+    pub fn insert_hint(&mut self, index: usize, hint: String) {
+        let index = index.min(self.hints.len());
+        self.hints.insert(index, hint);
+    }
+
+    pub fn remove_hint(&mut self, index: usize) -> Option<String> {
+        if index < self.hints.len() {
+            Some(self.hints.remove(index))
+        } else {
+            None
+        }
+    }
+
     pub fn question(&self) -> &str {
         &self.question
     }
@@ -27,4 +154,29 @@ impl Flashcard {
     pub fn answer(&self) -> &str {
         &self.answer
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{schedule_leitner, schedule_sm2, Grade, DEFAULT_LEITNER_INTERVALS};
+
+    #[test]
+    fn editing_the_text_keeps_the_id_and_progress() {
+        let mut card = Flashcard::new("Capital of Frnace".to_string(), "Paris".to_string(), Vec::new());
+        card.set_review(DEFAULT_PROFILE, Some(schedule_sm2(None, Grade::Good, 1_000)));
+        card.set_review("Sam", Some(schedule_leitner(None, Grade::Good, &DEFAULT_LEITNER_INTERVALS, 1_000)));
+        let before = card.clone();
+
+        card.set_text("Capital of France".to_string(), "Paris".to_string(), vec!["on the Seine".to_string()]);
+        assert_eq!(card.id(), before.id());
+        assert_eq!(card.review(DEFAULT_PROFILE), before.review(DEFAULT_PROFILE));
+        assert_eq!(card.profile_reviews(), before.profile_reviews());
+        assert_eq!(card.review("Sam").unwrap().leitner_box, 2);
+        assert!(!card.same_text(&before));
+
+        card.set_text(before.question().to_string(), before.answer().to_string(), Vec::new());
+        assert!(card.same_text(&before));
+        card.set_review(DEFAULT_PROFILE, None);
+        assert!(card.same_text(&before), "progress is not part of the text");
+    }
+}
@@ -0,0 +1,254 @@
+// src/models/learn.rs
+
+/*
+A guided Learn session over some cards of a study set. Every card is first
+asked as a multiple-choice question whose wrong options are other cards'
+answers; once chosen correctly it comes back as typed recall, and once typed
+correctly it is mastered. Missed cards are asked again a few questions later.
+The session works on copies of the cards, so editing the set meanwhile is safe.
+*/
+use std::collections::VecDeque;
+use super::{AnswerMatching, Flashcard};
+
+/// Wrong options offered next to the right answer in a multiple-choice question.
+const DISTRACTORS: usize = 3;
+/// A missed card is asked again after this many other questions (or at the end).
+const REQUEUE_GAP: usize = 3;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LearnStage {
+    /// Pick the answer from a few options.
+    Choice,
+    /// Type the answer.
+    Typed,
+    Mastered,
+}
+
+/// Progress of one card in the session.
+#[derive(Clone)]
+pub struct LearnCard {
+    pub card: Flashcard,
+    /// Position of the card in its set when the session started.
+    pub set_index: usize,
+    pub stage: LearnStage,
+    pub misses: u32,
+}
+
+/// The outcome of the last answer, shown until the session moves on.
+#[derive(Clone)]
+pub struct LearnFeedback {
+    pub correct: bool,
+    pub given: String,
+}
+
+#[derive(Clone)]
+pub struct LearnSession {
+    cards: Vec<LearnCard>,
+    /// Cards still to ask, as indexes into `cards`; the front one is being asked.
+    queue: VecDeque<usize>,
+    /// Options of the current multiple-choice question.
+    options: Vec<String>,
+    feedback: Option<LearnFeedback>,
+    answers_given: u32,
+}
+
+impl LearnSession {
+    /// Session over `cards` (set index and card), asked in the given order.
+    pub fn new(cards: Vec<(usize, Flashcard)>) -> Self {
+        let cards: Vec<LearnCard> = cards.into_iter()
+            .map(|(set_index, card)| LearnCard { card, set_index, stage: LearnStage::Choice, misses: 0 })
+            .collect();
+        let mut session = LearnSession { queue: (0..cards.len()).collect(), cards, options: Vec::new(), feedback: None, answers_given: 0 };
+        session.prepare_question();
+        session
+    }
+
+    /// The card being asked, or `None` once every card is mastered.
+    pub fn current(&self) -> Option<&LearnCard> {
+        self.queue.front().map(|i| &self.cards[*i])
+    }
+
+    /// Options to choose from when the current card is in the `Choice` stage.
+    pub fn options(&self) -> &[String] {
+        &self.options
+    }
+
+    pub fn feedback(&self) -> Option<&LearnFeedback> {
+        self.feedback.as_ref()
+    }
+
+    pub fn cards(&self) -> &[LearnCard] {
+        &self.cards
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    pub fn mastered_count(&self) -> usize {
+        self.cards.iter().filter(|c| c.stage == LearnStage::Mastered).count()
+    }
+
+    /// Share of answers that were right, from 0 to 1.
+    pub fn accuracy(&self) -> f32 {
+        let misses: u32 = self.cards.iter().map(|c| c.misses).sum();
+        if self.answers_given == 0 { 1.0 } else { 1.0 - misses as f32 / self.answers_given as f32 }
+    }
+
+    /// Check `given` against the current card. A chosen option must be the exact
+    /// answer; typed answers go through `Flashcard::check_answer` with `rules`.
+    /// Returns whether it was right; ignored while feedback is showing.
+    pub fn answer(&mut self, given: &str, rules: &AnswerMatching) -> bool {
+        let Some(&i) = self.queue.front() else { return false };
+        if self.feedback.is_some() {
+            return false;
+        }
+        let card = &mut self.cards[i];
+        let correct = match card.stage {
+            LearnStage::Choice => given == card.card.answer(),
+            _ => card.card.check_answer(given, rules),
+        };
+        if correct {
+            card.stage = if card.stage == LearnStage::Choice { LearnStage::Typed } else { LearnStage::Mastered };
+        } else {
+            card.misses += 1;
+        }
+        self.answers_given += 1;
+        self.feedback = Some(LearnFeedback { correct, given: given.to_string() });
+        correct
+    }
+
+    /// Count the last typed answer as right after all (e.g. a synonym).
+    pub fn override_correct(&mut self) {
+        let Some(&i) = self.queue.front() else { return };
+        if let Some(feedback) = self.feedback.as_mut().filter(|f| !f.correct) {
+            feedback.correct = true;
+            self.cards[i].misses -= 1;
+            self.cards[i].stage = LearnStage::Mastered;
+        }
+    }
+
+    /// Move on after the feedback: mastered cards leave the queue, others are asked again later.
+    pub fn advance(&mut self) {
+        let Some(feedback) = self.feedback.take() else { return };
+        let Some(i) = self.queue.pop_front() else { return };
+        if self.cards[i].stage != LearnStage::Mastered {
+            // a card just moved on to typing waits until the end, a missed one comes back soon
+            let at = if feedback.correct { self.queue.len() } else { REQUEUE_GAP.min(self.queue.len()) };
+            self.queue.insert(at, i);
+        }
+        self.prepare_question();
+    }
+
+    /// Pick the options of the next card; a card without distinct wrong answers
+    /// to offer skips straight to typing.
+    fn prepare_question(&mut self) {
+        self.options.clear();
+        let Some(&i) = self.queue.front() else { return };
+        if self.cards[i].stage != LearnStage::Choice {
+            return;
+        }
+        let answer = self.cards[i].card.answer().to_string();
+        let mut others: Vec<String> = Vec::new();
+        for card in &self.cards {
+            let other = card.card.answer();
+            if other.trim() != answer.trim() && !others.iter().any(|o| o == other) {
+                others.push(other.to_string());
+            }
+        }
+        if others.is_empty() {
+            self.cards[i].stage = LearnStage::Typed;
+            return;
+        }
+        fastrand::shuffle(&mut others);
+        others.truncate(DISTRACTORS);
+        others.push(answer);
+        fastrand::shuffle(&mut others);
+        self.options = others;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(count: usize) -> LearnSession {
+        LearnSession::new((0..count).map(|i| (i, Flashcard::new(format!("q{}", i), format!("a{}", i), Vec::new()))).collect())
+    }
+
+    /// Set index of the card being asked.
+    fn asked(session: &LearnSession) -> usize {
+        session.current().unwrap().set_index
+    }
+
+    /// Answer the current card correctly and move on.
+    fn answer_right(session: &mut LearnSession) {
+        let i = asked(session);
+        assert!(session.answer(&format!("a{}", i), &AnswerMatching::default()));
+        session.advance();
+    }
+
+    #[test]
+    fn cards_go_from_choice_to_typed_to_mastered() {
+        let rules = AnswerMatching::default();
+        let mut s = session(2);
+        assert_eq!((asked(&s), s.current().unwrap().stage), (0, LearnStage::Choice));
+        let mut options = s.options().to_vec();
+        options.sort();
+        assert_eq!(options, ["a0", "a1"]);
+
+        assert!(s.answer("a0", &rules));
+        assert_eq!(s.current().unwrap().stage, LearnStage::Typed);
+        assert!(!s.answer("a1", &rules), "ignored while the feedback shows");
+        s.advance();
+        answer_right(&mut s);
+
+        // both cards come back to be typed, in the order they were chosen
+        assert_eq!((asked(&s), s.current().unwrap().stage), (0, LearnStage::Typed));
+        assert!(s.options().is_empty());
+        assert!(s.answer("  A0 ", &rules));
+        assert_eq!(s.current().unwrap().stage, LearnStage::Mastered);
+        s.advance();
+        assert_eq!(s.mastered_count(), 1);
+        answer_right(&mut s);
+
+        assert!(s.is_finished() && s.current().is_none());
+        assert_eq!(s.mastered_count(), 2);
+        assert_eq!(s.accuracy(), 1.0);
+    }
+
+    #[test]
+    fn missed_cards_come_back_after_a_few_questions() {
+        let mut s = session(6);
+        let wrong = s.options().iter().find(|o| *o != "a0").unwrap().clone();
+        assert!(!s.answer(&wrong, &AnswerMatching::default()));
+        assert_eq!(s.current().unwrap().stage, LearnStage::Choice);
+        s.advance();
+
+        let mut order = Vec::new();
+        for _ in 0..REQUEUE_GAP + 1 {
+            order.push(asked(&s));
+            answer_right(&mut s);
+        }
+        assert_eq!(order, [1, 2, 3, 0]);
+        assert_eq!(s.cards()[0].misses, 1);
+        assert_eq!(s.accuracy(), 0.8);
+        // the cards now typed wait behind the ones not asked yet
+        assert_eq!(asked(&s), 4);
+    }
+
+    #[test]
+    fn a_card_without_distractors_is_typed_and_can_be_overridden() {
+        let rules = AnswerMatching::default();
+        let mut s = session(1);
+        assert_eq!(s.current().unwrap().stage, LearnStage::Typed);
+        assert!(s.options().is_empty());
+
+        assert!(!s.answer("something else", &rules));
+        s.override_correct();
+        assert!(s.feedback().unwrap().correct);
+        assert_eq!((s.current().unwrap().stage, s.cards()[0].misses), (LearnStage::Mastered, 0));
+        s.advance();
+        assert!(s.is_finished());
+    }
+}
@@ -0,0 +1,226 @@
+// src/models/match_game.rs
+
+/*
+The Match game: question and answer tiles of some cards are shuffled into one
+grid, and clicking a question and its answer (in either order) clears both.
+The clock runs until every pair is cleared; every wrong pair adds a penalty.
+Finished games go on the set's leaderboard (`StudySet::record_match_score`).
+*/
+use std::time::{Duration, Instant};
+use serde::{Serialize, Deserialize};
+use super::Flashcard;
+
+/// Pairs in one game, fewer if the set has fewer usable cards.
+pub const MATCH_PAIRS: usize = 6;
+/// Time added to the result for every wrong pair.
+pub const MATCH_PENALTY: Duration = Duration::from_secs(1);
+/// Best results kept per set for every number of pairs.
+pub const MATCH_LEADERBOARD_SIZE: usize = 10;
+
+/// A finished game on a set's leaderboard.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MatchScore {
+    pub profile: String,
+    /// Time including penalties, in milliseconds.
+    pub time_ms: u64,
+    pub pairs: u32,
+    pub mistakes: u32,
+    /// When the game was played, as Unix seconds.
+    pub played_at: u64,
+}
+
+#[derive(Clone)]
+pub struct MatchTile {
+    pub text: String,
+    /// Which pair of the game the tile belongs to.
+    pair: usize,
+    pub is_question: bool,
+    pub cleared: bool,
+}
+
+/// What a click on a tile did.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MatchClick {
+    Selected,
+    Deselected,
+    Matched,
+    /// The two tiles do not belong together; the penalty was added.
+    Wrong(usize, usize),
+    Ignored,
+}
+
+pub struct MatchGame {
+    tiles: Vec<MatchTile>,
+    selected: Option<usize>,
+    mistakes: u32,
+    started: Instant,
+    /// Time on the clock when the last pair was cleared.
+    finished: Option<Duration>,
+}
+
+impl MatchGame {
+    /// Game over up to `MATCH_PAIRS` random cards of the set.
+    pub fn new(cards: &[Flashcard]) -> Self {
+        let mut order: Vec<usize> = (0..cards.len()).collect();
+        fastrand::shuffle(&mut order);
+        let mut tiles: Vec<MatchTile> = Vec::new();
+        for (pair, (question, answer)) in pick_pairs(cards, order).into_iter().enumerate() {
+            tiles.push(MatchTile { text: question, pair, is_question: true, cleared: false });
+            tiles.push(MatchTile { text: answer, pair, is_question: false, cleared: false });
+        }
+        fastrand::shuffle(&mut tiles);
+        MatchGame { tiles, selected: None, mistakes: 0, started: Instant::now(), finished: None }
+    }
+
+    /// Pairs a game over `cards` has, which is what its leaderboard is kept by.
+    pub fn pair_count(cards: &[Flashcard]) -> usize {
+        pick_pairs(cards, 0..cards.len()).len()
+    }
+
+    pub fn tiles(&self) -> &[MatchTile] {
+        &self.tiles
+    }
+
+    pub fn pairs(&self) -> usize {
+        self.tiles.len() / 2
+    }
+
+    pub fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    pub fn mistakes(&self) -> u32 {
+        self.mistakes
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished.is_some()
+    }
+
+    /// Time on the clock so far (frozen once finished), penalties included.
+    pub fn elapsed(&self) -> Duration {
+        self.finished.unwrap_or_else(|| self.started.elapsed()) + MATCH_PENALTY * self.mistakes
+    }
+
+    pub fn click(&mut self, index: usize) -> MatchClick {
+        if self.finished.is_some() || self.tiles.get(index).is_none_or(|t| t.cleared) {
+            return MatchClick::Ignored;
+        }
+        let Some(first) = self.selected.take() else {
+            self.selected = Some(index);
+            return MatchClick::Selected;
+        };
+        if first == index {
+            return MatchClick::Deselected;
+        }
+        let (a, b) = (&self.tiles[first], &self.tiles[index]);
+        if a.pair != b.pair || a.is_question == b.is_question {
+            self.mistakes += 1;
+            return MatchClick::Wrong(first, index);
+        }
+        self.tiles[first].cleared = true;
+        self.tiles[index].cleared = true;
+        if self.tiles.iter().all(|t| t.cleared) {
+            self.finished = Some(self.started.elapsed());
+        }
+        MatchClick::Matched
+    }
+
+    /// Leaderboard entry for the finished game.
+    pub fn score(&self, profile: &str, played_at: u64) -> Option<MatchScore> {
+        self.finished?;
+        Some(MatchScore {
+            profile: profile.to_string(),
+            time_ms: self.elapsed().as_millis() as u64,
+            pairs: self.pairs() as u32,
+            mistakes: self.mistakes,
+            played_at,
+        })
+    }
+}
+
+/// Question and answer of up to `MATCH_PAIRS` cards, taken in `order`. Cards whose
+/// question or answer repeats an earlier tile are left out, so every tile has one partner.
+fn pick_pairs(cards: &[Flashcard], order: impl IntoIterator<Item = usize>) -> Vec<(String, String)> {
+    let mut pairs: Vec<(String, String)> = Vec::new();
+    for i in order {
+        let (question, answer) = (cards[i].question().trim(), cards[i].answer().trim());
+        let taken = |text: &str| pairs.iter().any(|(q, a)| q == text || a == text);
+        if question.is_empty() || answer.is_empty() || question == answer || taken(question) || taken(answer) {
+            continue;
+        }
+        pairs.push((question.to_string(), answer.to_string()));
+        if pairs.len() == MATCH_PAIRS {
+            break;
+        }
+    }
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cards(pairs: &[(&str, &str)]) -> Vec<Flashcard> {
+        pairs.iter().map(|(q, a)| Flashcard::new(q.to_string(), a.to_string(), Vec::new())).collect()
+    }
+
+    fn tile(game: &MatchGame, text: &str) -> usize {
+        game.tiles().iter().position(|t| t.text == text).unwrap()
+    }
+
+    #[test]
+    fn pairs_skip_blank_and_repeated_text() {
+        let set = cards(&[("a", "1"), ("b", ""), ("c", "c"), ("d", "1"), ("1", "e"), ("f", "2")]);
+        assert_eq!(MatchGame::pair_count(&set), 2);
+        let many: Vec<Flashcard> = (0..10).map(|i| Flashcard::new(format!("q{}", i), format!("a{}", i), Vec::new())).collect();
+        let game = MatchGame::new(&many);
+        assert_eq!((game.pairs(), game.tiles().len()), (MATCH_PAIRS, 2 * MATCH_PAIRS));
+    }
+
+    #[test]
+    fn clicks_select_match_and_count_mistakes() {
+        let mut game = MatchGame::new(&cards(&[("one", "1"), ("two", "2")]));
+        let (one, two, a1, a2) = (tile(&game, "one"), tile(&game, "two"), tile(&game, "1"), tile(&game, "2"));
+
+        assert_eq!(game.click(one), MatchClick::Selected);
+        assert_eq!(game.selected(), Some(one));
+        assert_eq!(game.click(one), MatchClick::Deselected);
+        assert_eq!(game.selected(), None);
+
+        game.click(one);
+        assert_eq!(game.click(two), MatchClick::Wrong(one, two), "two questions are no pair");
+        game.click(one);
+        assert_eq!(game.click(a2), MatchClick::Wrong(one, a2));
+        assert_eq!((game.mistakes(), game.selected()), (2, None));
+
+        game.click(a1);
+        assert_eq!(game.click(one), MatchClick::Matched);
+        assert!(game.tiles()[one].cleared && game.tiles()[a1].cleared);
+        assert_eq!(game.click(one), MatchClick::Ignored);
+        assert!(!game.is_finished() && game.score("me", 0).is_none());
+    }
+
+    #[test]
+    fn finishing_stops_the_clock_and_adds_the_penalty() {
+        let mut game = MatchGame::new(&cards(&[("one", "1"), ("two", "2")]));
+        let (one, two, a1, a2) = (tile(&game, "one"), tile(&game, "two"), tile(&game, "1"), tile(&game, "2"));
+        game.click(two);
+        game.click(a1);
+        game.click(one);
+        game.click(a1);
+        game.click(a2);
+        assert_eq!(game.click(two), MatchClick::Matched);
+        assert!(game.is_finished());
+        assert_eq!(game.click(one), MatchClick::Ignored);
+
+        let elapsed = game.elapsed();
+        assert_eq!(elapsed, game.finished.unwrap() + MATCH_PENALTY);
+        std::thread::sleep(Duration::from_millis(20));
+        assert_eq!(game.elapsed(), elapsed, "the clock stops with the last pair");
+
+        let score = game.score("me", 42).unwrap();
+        assert_eq!(score, MatchScore { profile: "me".to_string(), time_ms: elapsed.as_millis() as u64, pairs: 2, mistakes: 1, played_at: 42 });
+        assert!(score.time_ms >= MATCH_PENALTY.as_millis() as u64);
+    }
+}
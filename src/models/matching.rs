@@ -0,0 +1,55 @@
+// src/models/matching.rs
+
+/*
+Rules for comparing a typed answer with the expected one. Both sides are
+trimmed and runs of whitespace collapsed; case and punctuation are ignored
+when the rules say so, and a few typos can be forgiven in longer answers.
+*/
+use serde::{Serialize, Deserialize};
+
+/// Answers shorter than this (in characters) must be exact even when typos are allowed.
+const MIN_TYPO_ANSWER_CHARS: usize = 4;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AnswerMatching {
+    pub case_sensitive: bool,
+    /// Ignore punctuation and symbols, e.g. accept "its" for "it's".
+    pub ignore_punctuation: bool,
+    /// Number of wrong, missing or extra characters still accepted.
+    pub allowed_typos: u32,
+}
+
+impl AnswerMatching {
+    pub fn matches(&self, expected: &str, given: &str) -> bool {
+        let expected = self.normalize(expected);
+        let given = self.normalize(given);
+        if expected == given {
+            return true;
+        }
+        let expected: Vec<char> = expected.chars().collect();
+        self.allowed_typos > 0
+            && expected.len() >= MIN_TYPO_ANSWER_CHARS
+            && edit_distance(&expected, &given.chars().collect::<Vec<char>>()) <= self.allowed_typos as usize
+    }
+
+    fn normalize(&self, text: &str) -> String {
+        let text = if self.case_sensitive { text.to_string() } else { text.to_lowercase() };
+        let kept = text.chars().filter(|c| !self.ignore_punctuation || c.is_alphanumeric() || c.is_whitespace());
+        kept.collect::<String>().split_whitespace().collect::<Vec<&str>>().join(" ")
+    }
+}
+
+/// Levenshtein distance between two character sequences.
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
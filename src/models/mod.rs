@@ -6,9 +6,28 @@ This module defines the core data models for the Study Helper application.
 mod flashcard;
 mod study_set;
 mod quiz;
+mod algorithms;
+mod matching;
+mod learn;
+mod match_game;
 
 pub use flashcard::Flashcard;
+pub use flashcard::DEFAULT_PROFILE;
 pub use study_set::StudySet;
 pub use quiz::Quiz;
 pub use quiz::QuestionType;
-pub use quiz::QuestionData;
\ No newline at end of file
+pub use quiz::QuestionData;
+pub use algorithms::Grade;
+pub use algorithms::schedule_sm2;
+pub use algorithms::schedule_leitner;
+pub use algorithms::leitner_box;
+pub use algorithms::ReviewAlgorithm;
+pub use algorithms::DEFAULT_LEITNER_INTERVALS;
+pub use algorithms::LEITNER_BOXES;
+pub use matching::AnswerMatching;
+pub use learn::LearnSession;
+pub use learn::LearnStage;
+pub use match_game::MatchGame;
+pub use match_game::MatchClick;
+pub use match_game::MatchScore;
+pub use match_game::MATCH_PENALTY;
\ No newline at end of file
@@ -1,12 +1,12 @@
 use serde::{Serialize, Deserialize};
 
-#[derive(Clone, Serialize, Deserialize, Debug)]
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
 pub struct Quiz {
     title: String,
     questions: Vec<Question>,
 }
 
-#[derive(Clone, Serialize, Deserialize, Debug)]
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
 struct Question {
     prompt: String,
     options: Vec<String>,
@@ -94,11 +94,12 @@ impl Quiz {
         }
     }
 
-    pub fn check_answer(&self, question_index: usize, user_answer: &str) -> bool {
+    /// Multiple-choice answers must be the exact option; typed answers are compared with `rules`.
+    pub fn check_answer(&self, question_index: usize, user_answer: &str, rules: &super::AnswerMatching) -> bool {
         if let Some(question) = self.questions.get(question_index) {
             match question.question_type {
                 QuestionType::MultipleChoice => question.answer == user_answer,
-                QuestionType::FillInTheBlank => question.answer.trim().eq_ignore_ascii_case(user_answer.trim()),
+                QuestionType::FillInTheBlank => rules.matches(&question.answer, user_answer),
             }
         } else {
             false
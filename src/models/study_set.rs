@@ -1,12 +1,16 @@
 use super::flashcard::Flashcard;
 use super::quiz::Quiz;
+use super::match_game::{MatchScore, MATCH_LEADERBOARD_SIZE};
 use serde::{Serialize, Deserialize};
 
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub struct StudySet {
     name: String,
     flashcards: Vec<Flashcard>,
     quizzes: Vec<Quiz>,
+    /// Best Match game results, fastest first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    match_scores: Vec<MatchScore>,
 }
 
 impl StudySet {
@@ -15,6 +19,7 @@ impl StudySet {
             name,
             flashcards: Vec::new(),
             quizzes: Vec::new(),
+            match_scores: Vec::new(),
         }
     }
 
@@ -22,6 +27,17 @@ impl StudySet {
         self.flashcards.push(flashcard);
     }
 
+    /// Insert a flashcard at `index` (clamped to the end); used to undo a removal.
+    pub fn insert_flashcard(&mut self, index: usize, flashcard: Flashcard) {
+        let index = index.min(self.flashcards.len());
+        self.flashcards.insert(index, flashcard);
+    }
+
+    /// Mutable access to a single flashcard (e.g. to edit its hints).
+    pub fn get_flashcard_mut(&mut self, index: usize) -> Option<&mut Flashcard> {
+        self.flashcards.get_mut(index)
+    }
+
     pub fn remove_flashcard(&mut self, index: usize) -> Option<Flashcard> {
         if index < self.flashcards.len() {
             Some(self.flashcards.remove(index))
@@ -38,11 +54,52 @@ impl StudySet {
         &self.flashcards
     }
 
+    /// Drop the review progress of every card and the Match leaderboard, for all
+    /// profiles (e.g. before sharing the set).
+    pub fn strip_review_history(&mut self) {
+        for card in &mut self.flashcards {
+            card.clear_reviews();
+        }
+        self.match_scores.clear();
+    }
+
+    pub fn match_scores(&self) -> &[MatchScore] {
+        &self.match_scores
+    }
+
+    pub fn set_match_scores(&mut self, scores: Vec<MatchScore>) {
+        self.match_scores = scores;
+    }
+
+    /// Add a finished Match game to the leaderboard, keeping the best
+    /// `MATCH_LEADERBOARD_SIZE` results for each number of pairs. Returns the
+    /// game's place among games with as many pairs, if it made the board.
+    pub fn record_match_score(&mut self, score: MatchScore) -> Option<usize> {
+        let pairs = score.pairs;
+        self.match_scores.push(score.clone());
+        self.match_scores.sort_by_key(|s| s.time_ms);
+        let mut kept = 0;
+        self.match_scores.retain(|s| {
+            if s.pairs != pairs {
+                return true;
+            }
+            kept += 1;
+            kept <= MATCH_LEADERBOARD_SIZE
+        });
+        self.match_scores.iter().filter(|s| s.pairs == pairs).position(|s| *s == score).map(|i| i + 1)
+    }
+
     /// Quiz related helpers
     pub fn add_quiz(&mut self, quiz: Quiz) {
         self.quizzes.push(quiz);
     }
 
+    /// Insert a quiz at `index` (clamped to the end); used to undo a removal.
+    pub fn insert_quiz(&mut self, index: usize, quiz: Quiz) {
+        let index = index.min(self.quizzes.len());
+        self.quizzes.insert(index, quiz);
+    }
+
     pub fn remove_quiz(&mut self, index: usize) -> Option<Quiz> {
         if index < self.quizzes.len() {
             Some(self.quizzes.remove(index))
@@ -71,4 +128,10 @@ impl StudySet {
     pub fn name(&self) -> &str {
         &self.name
     }
+
+    /// Rename the set. Callers that persist sets should use the storage rename
+    /// helpers so the old file doesn't linger on disk.
+    pub fn set_name(&mut self, name: String) {
+        self.name = name;
+    }
 }
\ No newline at end of file
@@ -0,0 +1,256 @@
+// src/storage/archive.rs
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::Path;
+use serde::{Serialize, Deserialize};
+use zip::write::SimpleFileOptions;
+use crate::models::StudySet;
+use super::atomic::write_atomic;
+use super::backend::SetStore;
+use super::bundle::{invalid, media_files, read_entry, set_media_dir, zip_err, MediaEntries};
+use super::json_store::{unique_set_name, unix_timestamp, validate_class_name};
+
+/// File extension of class and library archives (a zip archive).
+pub const ARCHIVE_EXTENSION: &str = "studylib";
+/// Archive layout version written by this build; newer archives are rejected.
+pub const ARCHIVE_VERSION: u32 = 1;
+
+const ARCHIVE_FORMAT: &str = "study-helper-library";
+const MANIFEST_ENTRY: &str = "manifest.json";
+
+/// What to do with an archived set whose name is already taken in its class.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ConflictPolicy {
+    /// Leave the existing set alone.
+    Skip,
+    /// Replace the existing set with the archived one.
+    Overwrite,
+    /// Add the archived set under a "(copy)" name.
+    #[default]
+    KeepBoth,
+}
+
+impl ConflictPolicy {
+    pub const ALL: [ConflictPolicy; 3] = [ConflictPolicy::Skip, ConflictPolicy::Overwrite, ConflictPolicy::KeepBoth];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ConflictPolicy::Skip => "Skip",
+            ConflictPolicy::Overwrite => "Overwrite",
+            ConflictPolicy::KeepBoth => "Keep both",
+        }
+    }
+}
+
+/// `manifest.json` of an archive. Sets are stored as
+/// `classes/<class>/<file>.json`, their media below `classes/<class>/media/<file>/`.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+struct ArchiveManifest {
+    format: String,
+    version: u32,
+    exported_at: u64,
+    app_version: String,
+    classes: Vec<ArchivedClass>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+struct ArchivedClass {
+    name: String,
+    sets: Vec<ArchivedSetFile>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+struct ArchivedSetFile {
+    name: String,
+    /// Entry name stem, unique within the class.
+    file: String,
+}
+
+/// A set in an archive, as listed before importing it.
+#[derive(Clone, Debug)]
+pub struct ArchivedSet {
+    pub class_name: String,
+    pub set_name: String,
+    /// The class already has a set with this name (or file name).
+    pub conflict: bool,
+}
+
+/// What `export_archive` wrote.
+#[derive(Clone, Debug, Default)]
+pub struct ArchiveSummary {
+    pub classes: usize,
+    pub sets: usize,
+}
+
+/// What `import_archive` did, per outcome.
+#[derive(Clone, Debug, Default)]
+pub struct ArchiveImportReport {
+    pub added: usize,
+    pub overwritten: usize,
+    pub kept_both: usize,
+    pub skipped: usize,
+    /// "class/set: error" of sets that could not be imported.
+    pub failed: Vec<String>,
+}
+
+/// Write the stored sets of `class_names` (with their media) to one archive at `dst_file`.
+pub fn export_archive(store: &dyn SetStore, base_dir: &Path, class_names: &[String], dst_file: &Path) -> io::Result<ArchiveSummary> {
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    let mut zip = zip::ZipWriter::new(io::Cursor::new(Vec::new()));
+    let mut manifest = ArchiveManifest {
+        format: ARCHIVE_FORMAT.to_string(),
+        version: ARCHIVE_VERSION,
+        exported_at: unix_timestamp(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        classes: Vec::new(),
+    };
+    let mut summary = ArchiveSummary::default();
+    for class_name in class_names {
+        let mut class = ArchivedClass { name: class_name.clone(), sets: Vec::new() };
+        for set in store.load_sets(class_name)? {
+            // different set names can sanitize to the same file name
+            let stem = sanitize_filename::sanitize(set.name());
+            let file = unique_set_name(&stem, |n| class.sets.iter().any(|s| s.file == n));
+            zip.start_file(format!("classes/{}/{}.json", class_name, file), options).map_err(zip_err)?;
+            zip.write_all(&serde_json::to_vec_pretty(&set).map_err(io::Error::other)?)?;
+            for (media_name, path) in media_files(&set_media_dir(base_dir, class_name, set.name()))? {
+                zip.start_file(format!("classes/{}/media/{}/{}", class_name, file, media_name), options).map_err(zip_err)?;
+                zip.write_all(&fs::read(path)?)?;
+            }
+            class.sets.push(ArchivedSetFile { name: set.name().to_string(), file });
+            summary.sets += 1;
+        }
+        manifest.classes.push(class);
+        summary.classes += 1;
+    }
+    zip.start_file(MANIFEST_ENTRY, options).map_err(zip_err)?;
+    zip.write_all(&serde_json::to_vec_pretty(&manifest).map_err(io::Error::other)?)?;
+    let bytes = zip.finish().map_err(zip_err)?.into_inner();
+
+    if let Some(parent) = dst_file.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    write_atomic(dst_file, &bytes)?;
+    log::info!("Exported {} sets in {} classes to {}", summary.sets, summary.classes, dst_file.display());
+    Ok(summary)
+}
+
+fn open_archive(src_file: &Path) -> io::Result<(zip::ZipArchive<fs::File>, ArchiveManifest)> {
+    let mut archive = zip::ZipArchive::new(fs::File::open(src_file)?).map_err(|_| invalid(format!("{} is not an archive", src_file.display())))?;
+    let manifest: ArchiveManifest = serde_json::from_slice(&read_entry(&mut archive, MANIFEST_ENTRY)?)
+        .map_err(|e| invalid(format!("bad archive manifest: {}", e)))?;
+    if manifest.format != ARCHIVE_FORMAT {
+        return Err(invalid(format!("{} is not a Study Helper class or library archive", src_file.display())));
+    }
+    if manifest.version > ARCHIVE_VERSION {
+        return Err(invalid(format!("archive version {} needs a newer version of Study Helper", manifest.version)));
+    }
+    for class in &manifest.classes {
+        // class names become folder names, and set files entry names
+        validate_class_name(&class.name)?;
+        if let Some(set) = class.sets.iter().find(|s| sanitize_filename::sanitize(&s.file) != s.file) {
+            return Err(invalid(format!("archive contains an unsafe file name '{}'", set.file)));
+        }
+    }
+    Ok((archive, manifest))
+}
+
+/// Sanitized names of the sets stored in a class, for conflict checks.
+fn taken_names(store: &dyn SetStore, class_name: &str) -> io::Result<Vec<String>> {
+    Ok(store.load_sets(class_name)?.iter().map(|s| sanitize_filename::sanitize(s.name())).collect())
+}
+
+/// List the sets in an archive and whether each one clashes with a stored set.
+pub fn preview_archive(store: &dyn SetStore, src_file: &Path) -> io::Result<Vec<ArchivedSet>> {
+    let (_, manifest) = open_archive(src_file)?;
+    let existing = store.list_classes()?;
+    let mut sets = Vec::new();
+    for class in manifest.classes {
+        let taken = if existing.contains(&class.name) { taken_names(store, &class.name)? } else { Vec::new() };
+        for set in class.sets {
+            let conflict = taken.contains(&sanitize_filename::sanitize(&set.name));
+            sets.push(ArchivedSet { class_name: class.name.clone(), set_name: set.name, conflict });
+        }
+    }
+    Ok(sets)
+}
+
+/// Merge an archive into the store: missing classes are created and each set
+/// that clashes with a stored one is handled by `policy(class, set)`. A set
+/// that fails is reported and the import carries on with the next one.
+pub fn import_archive(
+    store: &dyn SetStore,
+    base_dir: &Path,
+    src_file: &Path,
+    policy: impl Fn(&str, &str) -> ConflictPolicy,
+) -> io::Result<ArchiveImportReport> {
+    let (mut archive, manifest) = open_archive(src_file)?;
+    // media entries by "classes/<class>/media/<file>/" prefix
+    let mut media: HashMap<String, MediaEntries> = HashMap::new();
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i).map_err(zip_err)?;
+        if !entry.is_file() {
+            continue;
+        }
+        let Some((dir, name)) = entry.name().rsplit_once('/').map(|(d, n)| (d.to_string(), n.to_string())) else { continue };
+        if !dir.contains("/media/") {
+            continue;
+        }
+        if name.is_empty() || sanitize_filename::sanitize(&name) != name {
+            return Err(invalid(format!("archive contains an unsafe media name '{}'", name)));
+        }
+        let mut data = Vec::new();
+        entry.read_to_end(&mut data)?;
+        media.entry(dir).or_default().push((name, data));
+    }
+
+    let mut report = ArchiveImportReport::default();
+    let mut existing = store.list_classes()?;
+    for class in &manifest.classes {
+        if !existing.contains(&class.name) {
+            store.create_class(&class.name)?;
+            existing.push(class.name.clone());
+        }
+        let mut taken = taken_names(store, &class.name)?;
+        for entry in &class.sets {
+            // None when the name was free, else the policy that was applied
+            let result = read_entry(&mut archive, &format!("classes/{}/{}.json", class.name, entry.file))
+                .and_then(|data| serde_json::from_slice::<StudySet>(&data).map_err(|e| invalid(format!("bad set: {}", e))))
+                .and_then(|mut set| {
+                    let clash = taken.contains(&sanitize_filename::sanitize(set.name()));
+                    let applied = clash.then(|| policy(&class.name, set.name()));
+                    match applied {
+                        Some(ConflictPolicy::Skip) => return Ok(applied),
+                        Some(ConflictPolicy::KeepBoth) => {
+                            set.set_name(unique_set_name(set.name(), |n| taken.contains(&sanitize_filename::sanitize(n))));
+                        }
+                        _ => {}
+                    }
+                    store.save_set(&class.name, &set)?;
+                    taken.push(sanitize_filename::sanitize(set.name()));
+                    if let Some(files) = media.get(&format!("classes/{}/media/{}", class.name, entry.file)) {
+                        let dir = set_media_dir(base_dir, &class.name, set.name());
+                        fs::create_dir_all(&dir)?;
+                        for (name, data) in files {
+                            write_atomic(&dir.join(name), data)?;
+                        }
+                    }
+                    Ok(applied)
+                });
+            match result {
+                Ok(None) => report.added += 1,
+                Ok(Some(ConflictPolicy::Skip)) => report.skipped += 1,
+                Ok(Some(ConflictPolicy::Overwrite)) => report.overwritten += 1,
+                Ok(Some(ConflictPolicy::KeepBoth)) => report.kept_both += 1,
+                Err(e) => {
+                    log::warn!("Failed to import set '{}' into class '{}': {}", entry.name, class.name, e);
+                    report.failed.push(format!("{}/{}: {}", class.name, entry.name, e));
+                }
+            }
+        }
+    }
+    log::info!("Imported archive {}: {:?}", src_file.display(), report);
+    Ok(report)
+}
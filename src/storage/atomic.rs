@@ -0,0 +1,55 @@
+// src/storage/atomic.rs
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Write `contents` to a hidden temp file next to `path` and fsync it.
+/// Returns the temp path; pass it to `commit_temp_file` to move it into place.
+pub fn write_temp_file(path: &Path, contents: &[u8]) -> io::Result<PathBuf> {
+    let parent = path.parent().filter(|p| !p.as_os_str().is_empty()).unwrap_or_else(|| Path::new("."));
+    fs::create_dir_all(parent)?;
+    let file_name = path.file_name().ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "target has no filename"))?;
+    // leading '.' and a non-.json extension keep the loaders from picking it up
+    let tmp = parent.join(format!(".{}.{}.tmp", file_name.to_string_lossy(), std::process::id()));
+    let result = (|| {
+        let mut f = fs::File::create(&tmp)?;
+        f.write_all(contents)?;
+        f.sync_all()
+    })();
+    if let Err(e) = result {
+        let _ = fs::remove_file(&tmp);
+        return Err(e);
+    }
+    Ok(tmp)
+}
+
+/// Atomically replace `path` with a temp file produced by `write_temp_file`.
+pub fn commit_temp_file(tmp: &Path, path: &Path) -> io::Result<()> {
+    if let Err(e) = fs::rename(tmp, path) {
+        let _ = fs::remove_file(tmp);
+        return Err(e);
+    }
+    if let Some(parent) = path.parent() {
+        sync_dir(parent);
+    }
+    Ok(())
+}
+
+/// Crash-safe replacement for `fs::write`: the target either keeps its old
+/// contents or gets the complete new contents, never a truncated file.
+pub fn write_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let tmp = write_temp_file(path, contents)?;
+    commit_temp_file(&tmp, path)
+}
+
+/// Flush the directory entry so the rename itself survives a crash.
+#[cfg(unix)]
+fn sync_dir(dir: &Path) {
+    if let Ok(d) = fs::File::open(dir) {
+        let _ = d.sync_all();
+    }
+}
+
+#[cfg(not(unix))]
+fn sync_dir(_dir: &Path) {}
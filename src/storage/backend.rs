@@ -0,0 +1,237 @@
+// src/storage/backend.rs
+
+use std::io;
+use std::path::{Path, PathBuf};
+use serde::{Serialize, Deserialize};
+use crate::models::StudySet;
+use super::json_store::*;
+use super::sqlite_store::SqliteStore;
+
+/// Where and how classes and sets are stored below the base folder.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+pub enum StorageBackend {
+    /// One folder per class with one pretty-printed JSON file per set.
+    #[default]
+    Json,
+    /// A single SQLite database (`SQLITE_DB_FILE`) in the base folder.
+    Sqlite,
+}
+
+impl StorageBackend {
+    pub fn label(self) -> &'static str {
+        match self {
+            StorageBackend::Json => "JSON folders",
+            StorageBackend::Sqlite => "SQLite database",
+        }
+    }
+
+    /// The backend a migration copies from when this one is active.
+    pub fn other(self) -> StorageBackend {
+        match self {
+            StorageBackend::Json => StorageBackend::Sqlite,
+            StorageBackend::Sqlite => StorageBackend::Json,
+        }
+    }
+}
+
+/// A card found by `SetStore::search_cards`.
+#[derive(Clone, Debug)]
+pub struct CardMatch {
+    pub set_name: String,
+    /// Position of the card in its set.
+    pub index: usize,
+    pub question: String,
+}
+
+/// Operations the app needs from a storage backend. Sets are identified by
+/// class and set name; every call may touch the disk, so the GUI only calls
+/// these from storage worker jobs.
+pub trait SetStore: Send {
+    fn list_classes(&self) -> io::Result<Vec<String>>;
+    /// Fails with `AlreadyExists` if the class exists.
+    fn create_class(&self, class_name: &str) -> io::Result<()>;
+    fn rename_class(&self, old_name: &str, new_name: &str) -> io::Result<()>;
+    /// With `to_trash` the class is kept below `base/.trash` so it can be recovered by hand.
+    fn delete_class(&self, class_name: &str, to_trash: bool) -> io::Result<()>;
+    fn summarize_class(&self, class_name: &str) -> io::Result<ClassSummary>;
+
+    fn load_sets(&self, class_name: &str) -> io::Result<Vec<StudySet>>;
+    fn load_set(&self, class_name: &str, set_name: &str) -> io::Result<Option<StudySet>>;
+    /// Create or replace the set called `study_set.name()`.
+    fn save_set(&self, class_name: &str, study_set: &StudySet) -> io::Result<()>;
+    /// Store `study_set` under `new_name` and drop the old name; `study_set` gets the new name.
+    fn rename_set(&self, class_name: &str, study_set: &mut StudySet, new_name: &str) -> io::Result<()>;
+    fn delete_set(&self, class_name: &str, set_name: &str) -> io::Result<()>;
+    /// Save a copy named `new_name` into `target_class`. Never overwrites an existing set.
+    fn copy_set(&self, study_set: &StudySet, target_class: &str, new_name: &str) -> io::Result<StudySet>;
+    /// Copy the set into `target_class`, then remove it from `from_class`.
+    fn move_set(&self, study_set: &StudySet, from_class: &str, target_class: &str) -> io::Result<()>;
+
+    /// Add the set stored in a JSON file to a class. Never overwrites an existing set.
+    fn import_set_file(&self, class_name: &str, src_file: &Path) -> io::Result<StudySet>;
+    /// Write the stored version of a set to a JSON file.
+    fn export_set_file(&self, class_name: &str, set_name: &str, dst_file: &Path) -> io::Result<()>;
+
+    /// Cards in a class where every word of `query` starts a word of the question
+    /// or answer (case-insensitive), at most `limit` of them.
+    fn search_cards(&self, class_name: &str, query: &str, limit: usize) -> io::Result<Vec<CardMatch>>;
+}
+
+/// Backend plus base folder; cheap to clone into storage jobs, which open the store themselves.
+#[derive(Clone, Debug)]
+pub struct StorageLocation {
+    pub backend: StorageBackend,
+    pub base_dir: PathBuf,
+}
+
+impl StorageLocation {
+    pub fn new(backend: StorageBackend, base_dir: impl Into<PathBuf>) -> Self {
+        StorageLocation { backend, base_dir: base_dir.into() }
+    }
+
+    /// Open the store. The SQLite database is created (with its schema) on first use.
+    pub fn open(&self) -> io::Result<Box<dyn SetStore>> {
+        Ok(match self.backend {
+            StorageBackend::Json => Box::new(JsonFolderStore::new(&self.base_dir)),
+            StorageBackend::Sqlite => Box::new(SqliteStore::open(&self.base_dir)?),
+        })
+    }
+}
+
+/// The original layout: base_dir / class_name / set_name.json, with rotating
+/// backups and atomic writes (see `json_store`).
+pub struct JsonFolderStore {
+    base_dir: PathBuf,
+}
+
+impl JsonFolderStore {
+    pub fn new(base_dir: &Path) -> Self {
+        JsonFolderStore { base_dir: base_dir.to_path_buf() }
+    }
+}
+
+impl SetStore for JsonFolderStore {
+    fn list_classes(&self) -> io::Result<Vec<String>> {
+        list_class_folders(&self.base_dir)
+    }
+
+    fn create_class(&self, class_name: &str) -> io::Result<()> {
+        create_class_folder(&self.base_dir, class_name).map(|_| ())
+    }
+
+    fn rename_class(&self, old_name: &str, new_name: &str) -> io::Result<()> {
+        rename_class_folder(&self.base_dir, old_name, new_name).map(|_| ())
+    }
+
+    fn delete_class(&self, class_name: &str, to_trash: bool) -> io::Result<()> {
+        delete_class_folder(&self.base_dir, class_name, to_trash)
+    }
+
+    fn summarize_class(&self, class_name: &str) -> io::Result<ClassSummary> {
+        summarize_class_folder(&self.base_dir, class_name)
+    }
+
+    fn load_sets(&self, class_name: &str) -> io::Result<Vec<StudySet>> {
+        load_sets_from_class_folder(&self.base_dir, class_name)
+    }
+
+    fn load_set(&self, class_name: &str, set_name: &str) -> io::Result<Option<StudySet>> {
+        let path = set_file_path(&self.base_dir, class_name, set_name);
+        if !path.exists() {
+            return Ok(None);
+        }
+        // a different set may share the sanitized file name
+        Ok(Some(load_study_set_from_file(&path)?).filter(|s| s.name() == set_name))
+    }
+
+    fn save_set(&self, class_name: &str, study_set: &StudySet) -> io::Result<()> {
+        save_set_into_class_folder(&self.base_dir, class_name, study_set.name(), study_set).map(|_| ())
+    }
+
+    fn rename_set(&self, class_name: &str, study_set: &mut StudySet, new_name: &str) -> io::Result<()> {
+        rename_set_in_class_folder(&self.base_dir, class_name, study_set, new_name).map(|_| ())
+    }
+
+    fn delete_set(&self, class_name: &str, set_name: &str) -> io::Result<()> {
+        delete_set_file(&self.base_dir, class_name, set_name)
+    }
+
+    fn copy_set(&self, study_set: &StudySet, target_class: &str, new_name: &str) -> io::Result<StudySet> {
+        copy_set_to_class(&self.base_dir, study_set, target_class, new_name).map(|(copy, _)| copy)
+    }
+
+    fn move_set(&self, study_set: &StudySet, from_class: &str, target_class: &str) -> io::Result<()> {
+        move_set_to_class(&self.base_dir, study_set, from_class, target_class).map(|_| ())
+    }
+
+    fn import_set_file(&self, class_name: &str, src_file: &Path) -> io::Result<StudySet> {
+        // parse first so a file that isn't a study set never lands in the class folder
+        let set = load_study_set_from_file(src_file)?;
+        import_set_file_to_class(&self.base_dir, class_name, src_file)?;
+        Ok(set)
+    }
+
+    fn export_set_file(&self, class_name: &str, set_name: &str, dst_file: &Path) -> io::Result<()> {
+        export_set_file(&set_file_path(&self.base_dir, class_name, set_name), dst_file)
+    }
+
+    fn search_cards(&self, class_name: &str, query: &str, limit: usize) -> io::Result<Vec<CardMatch>> {
+        let wanted = words(query);
+        let mut matches = Vec::new();
+        if wanted.is_empty() {
+            return Ok(matches);
+        }
+        let mut sets = load_sets_from_class_folder(&self.base_dir, class_name)?;
+        sets.sort_by(|a, b| a.name().cmp(b.name()));
+        for set in sets {
+            for (index, card) in set.get_all_flashcards().iter().enumerate() {
+                let text = words(&format!("{} {}", card.question(), card.answer()));
+                if wanted.iter().all(|w| text.iter().any(|t| t.starts_with(w.as_str()))) {
+                    matches.push(CardMatch { set_name: set.name().to_string(), index, question: card.question().to_string() });
+                    if matches.len() >= limit {
+                        return Ok(matches);
+                    }
+                }
+            }
+        }
+        Ok(matches)
+    }
+}
+
+/// Lowercased words of `text`, split the way the SQLite full-text index splits them.
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty()).map(str::to_lowercase).collect()
+}
+
+/// What `migrate_sets` copied.
+#[derive(Clone, Debug, Default)]
+pub struct MigrationReport {
+    pub classes: usize,
+    pub sets: usize,
+    /// "class/set" of sets left alone because the target already had them.
+    pub skipped: Vec<String>,
+}
+
+/// Copy every class and set from one store into another. Sets that already
+/// exist in the target are skipped (and reported), never overwritten, so the
+/// migration can be re-run safely.
+pub fn migrate_sets(from: &dyn SetStore, to: &dyn SetStore) -> io::Result<MigrationReport> {
+    let mut report = MigrationReport::default();
+    let existing = to.list_classes()?;
+    for class_name in from.list_classes()? {
+        if !existing.contains(&class_name) {
+            to.create_class(&class_name)?;
+        }
+        report.classes += 1;
+        for set in from.load_sets(&class_name)? {
+            if to.load_set(&class_name, set.name())?.is_some() {
+                report.skipped.push(format!("{}/{}", class_name, set.name()));
+                continue;
+            }
+            to.save_set(&class_name, &set)?;
+            report.sets += 1;
+        }
+    }
+    log::info!("Migrated {} sets in {} classes ({} skipped)", report.sets, report.classes, report.skipped.len());
+    Ok(report)
+}
@@ -0,0 +1,116 @@
+// src/storage/backup.rs
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use crate::models::StudySet;
+use super::json_store::{load_study_set_from_file, save_set_into_class_folder, set_file_path, unix_timestamp};
+
+/// Folder inside each class folder that holds per-set backups:
+/// base_dir / class_name / .backups / sanitized(set_name) / <timestamp>.json
+pub const BACKUP_DIR: &str = ".backups";
+/// Number of backups kept per set; the oldest ones are removed first.
+pub const MAX_BACKUPS_PER_SET: usize = 10;
+/// Minimum age of the newest backup before a regular save takes another one,
+/// so a burst of edits doesn't rotate every useful backup away.
+pub const BACKUP_MIN_INTERVAL_SECS: u64 = 5 * 60;
+
+/// One backup file of a set, as shown in the restore browser.
+#[derive(Clone, Debug)]
+pub struct SetBackup {
+    pub path: PathBuf,
+    pub created_at: u64,
+    pub card_count: usize,
+    pub quiz_count: usize,
+}
+
+/// Backup folder for a set inside its class folder.
+pub fn set_backup_dir(base_dir: &Path, class_name: &str, set_name: &str) -> PathBuf {
+    base_dir.join(class_name).join(BACKUP_DIR).join(sanitize_filename::sanitize(set_name))
+}
+
+/// Backup files of a set as (timestamp, path), newest first.
+fn backup_files(dir: &Path) -> io::Result<Vec<(u64, PathBuf)>> {
+    let mut files = Vec::new();
+    if !dir.exists() {
+        return Ok(files);
+    }
+    for entry in fs::read_dir(dir)? {
+        let p = entry?.path();
+        if p.extension().and_then(|s| s.to_str()) != Some("json") {
+            continue;
+        }
+        if let Some(ts) = p.file_stem().and_then(|s| s.to_str()).and_then(|s| s.parse::<u64>().ok()) {
+            files.push((ts, p));
+        }
+    }
+    files.sort_by_key(|f| std::cmp::Reverse(f.0));
+    Ok(files)
+}
+
+/// Copy the current file of a set into its backup folder and rotate old
+/// backups. Unless `force` is set, nothing happens when the newest backup is
+/// younger than `BACKUP_MIN_INTERVAL_SECS`. Returns the new backup, if any.
+pub fn backup_set_file(base_dir: &Path, class_name: &str, set_name: &str, force: bool) -> io::Result<Option<PathBuf>> {
+    let src = set_file_path(base_dir, class_name, set_name);
+    if !src.exists() {
+        return Ok(None);
+    }
+    let dir = set_backup_dir(base_dir, class_name, set_name);
+    let existing = backup_files(&dir)?;
+    let now = unix_timestamp();
+    let mut ts = now;
+    if let Some((newest, _)) = existing.first() {
+        if !force && now.saturating_sub(*newest) < BACKUP_MIN_INTERVAL_SECS {
+            return Ok(None);
+        }
+        // keep file names unique when forced twice within one second
+        ts = ts.max(newest + 1);
+    }
+    fs::create_dir_all(&dir)?;
+    let dst = dir.join(format!("{}.json", ts));
+    fs::copy(&src, &dst)?;
+    for (_, old) in backup_files(&dir)?.into_iter().skip(MAX_BACKUPS_PER_SET) {
+        if let Err(e) = fs::remove_file(&old) {
+            log::warn!("Failed to remove old backup {}: {}", old.display(), e);
+        }
+    }
+    log::debug!("Backed up set '{}' to {}", set_name, dst.display());
+    Ok(Some(dst))
+}
+
+/// List the backups of a set, newest first. Unreadable backups are listed with zero counts.
+pub fn list_set_backups(base_dir: &Path, class_name: &str, set_name: &str) -> io::Result<Vec<SetBackup>> {
+    let dir = set_backup_dir(base_dir, class_name, set_name);
+    Ok(backup_files(&dir)?
+        .into_iter()
+        .map(|(created_at, path)| {
+            let (card_count, quiz_count) = load_study_set_from_file(&path)
+                .map(|s| (s.get_all_flashcards().len(), s.get_all_quizzes().len()))
+                .unwrap_or((0, 0));
+            SetBackup { path, created_at, card_count, quiz_count }
+        })
+        .collect())
+}
+
+/// Replace a set with one of its backups. The current file is backed up
+/// first, so a restore can itself be undone from the browser.
+pub fn restore_set_backup(base_dir: &Path, class_name: &str, set_name: &str, backup: &Path) -> io::Result<StudySet> {
+    let mut restored = load_study_set_from_file(backup)?;
+    // the backup may predate a rename; keep the set under its current name
+    restored.set_name(set_name.to_string());
+    backup_set_file(base_dir, class_name, set_name, true)?;
+    save_set_into_class_folder(base_dir, class_name, set_name, &restored)?;
+    log::info!("Restored set '{}' in class '{}' from {}", set_name, class_name, backup.display());
+    Ok(restored)
+}
+
+/// Move a set's backups along with it when the set is renamed.
+pub fn rename_set_backups(base_dir: &Path, class_name: &str, old_name: &str, new_name: &str) -> io::Result<()> {
+    let old_dir = set_backup_dir(base_dir, class_name, old_name);
+    let new_dir = set_backup_dir(base_dir, class_name, new_name);
+    if old_dir == new_dir || !old_dir.exists() || new_dir.exists() {
+        return Ok(());
+    }
+    fs::rename(old_dir, new_dir)
+}
@@ -0,0 +1,190 @@
+// src/storage/bundle.rs
+
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use serde::{Serialize, Deserialize};
+use zip::write::SimpleFileOptions;
+use crate::models::StudySet;
+use super::atomic::write_atomic;
+use super::backend::SetStore;
+use super::json_store::{unique_set_name, unix_timestamp};
+
+/// File extension of set bundles (a zip archive).
+pub const BUNDLE_EXTENSION: &str = "studyset";
+/// Bundle layout version written by this build; newer bundles are rejected.
+pub const BUNDLE_VERSION: u32 = 1;
+/// Folder inside each class folder holding files attached to sets:
+/// base_dir / class_name / .media / sanitized(set_name) / <file>
+pub const MEDIA_DIR: &str = ".media";
+
+const BUNDLE_FORMAT: &str = "study-helper-set";
+const MANIFEST_ENTRY: &str = "manifest.json";
+const SET_ENTRY: &str = "set.json";
+const MEDIA_ENTRY_PREFIX: &str = "media/";
+
+/// `manifest.json` of a bundle, readable without parsing the set itself.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct BundleManifest {
+    pub format: String,
+    pub version: u32,
+    pub set_name: String,
+    pub card_count: usize,
+    pub quiz_count: usize,
+    pub exported_at: u64,
+    pub app_version: String,
+    /// False when the cards' review progress was stripped on export.
+    pub includes_review_history: bool,
+    /// File names below `media/`.
+    #[serde(default)]
+    pub media: Vec<String>,
+}
+
+/// What `import_set_bundle` added to the class.
+#[derive(Clone)]
+pub struct BundleImport {
+    pub set: StudySet,
+    /// Name in the bundle when it had to be changed to avoid a clash.
+    pub renamed_from: Option<String>,
+    pub media_files: usize,
+}
+
+pub(super) fn zip_err(e: zip::result::ZipError) -> io::Error {
+    match e {
+        zip::result::ZipError::Io(e) => e,
+        e => io::Error::new(io::ErrorKind::InvalidData, e),
+    }
+}
+
+pub(super) fn invalid(msg: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.into())
+}
+
+/// (file name, contents) of media files read from a zip archive.
+pub(super) type MediaEntries = Vec<(String, Vec<u8>)>;
+
+/// Media folder of a set inside its class folder.
+pub fn set_media_dir(base_dir: &Path, class_name: &str, set_name: &str) -> PathBuf {
+    base_dir.join(class_name).join(MEDIA_DIR).join(sanitize_filename::sanitize(set_name))
+}
+
+/// Files directly inside a set's media folder, sorted by name.
+pub(super) fn media_files(dir: &Path) -> io::Result<Vec<(String, PathBuf)>> {
+    let mut files = Vec::new();
+    if !dir.is_dir() {
+        return Ok(files);
+    }
+    for entry in fs::read_dir(dir)? {
+        let p = entry?.path();
+        if let Some(name) = p.file_name().and_then(|n| n.to_str()).filter(|_| p.is_file()) {
+            files.push((name.to_string(), p.clone()));
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Write `study_set` and its media as a bundle to `dst_file`. Unless
+/// `include_review_history` is set, the cards' review progress is left out.
+pub fn export_set_bundle(base_dir: &Path, class_name: &str, study_set: &StudySet, dst_file: &Path, include_review_history: bool) -> io::Result<BundleManifest> {
+    let mut set = study_set.clone();
+    if !include_review_history {
+        set.strip_review_history();
+    }
+    let media = media_files(&set_media_dir(base_dir, class_name, set.name()))?;
+    let manifest = BundleManifest {
+        format: BUNDLE_FORMAT.to_string(),
+        version: BUNDLE_VERSION,
+        set_name: set.name().to_string(),
+        card_count: set.get_all_flashcards().len(),
+        quiz_count: set.get_all_quizzes().len(),
+        exported_at: unix_timestamp(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        includes_review_history: include_review_history,
+        media: media.iter().map(|(name, _)| name.clone()).collect(),
+    };
+
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    let mut zip = zip::ZipWriter::new(io::Cursor::new(Vec::new()));
+    zip.start_file(MANIFEST_ENTRY, options).map_err(zip_err)?;
+    zip.write_all(&serde_json::to_vec_pretty(&manifest).map_err(io::Error::other)?)?;
+    zip.start_file(SET_ENTRY, options).map_err(zip_err)?;
+    zip.write_all(&serde_json::to_vec_pretty(&set).map_err(io::Error::other)?)?;
+    for (name, path) in &media {
+        zip.start_file(format!("{}{}", MEDIA_ENTRY_PREFIX, name), options).map_err(zip_err)?;
+        zip.write_all(&fs::read(path)?)?;
+    }
+    let bytes = zip.finish().map_err(zip_err)?.into_inner();
+
+    if let Some(parent) = dst_file.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    write_atomic(dst_file, &bytes)?;
+    log::info!("Exported set '{}' with {} media files to {}", set.name(), media.len(), dst_file.display());
+    Ok(manifest)
+}
+
+pub(super) fn read_entry(archive: &mut zip::ZipArchive<fs::File>, name: &str) -> io::Result<Vec<u8>> {
+    let mut entry = archive.by_name(name).map_err(|e| match e {
+        zip::result::ZipError::FileNotFound => invalid(format!("bundle has no {}", name)),
+        e => zip_err(e),
+    })?;
+    let mut data = Vec::new();
+    entry.read_to_end(&mut data)?;
+    Ok(data)
+}
+
+/// Read and check a bundle: its set and its media.
+/// Fails with `InvalidData` for files that are not set bundles or were written
+/// by a newer bundle version.
+fn read_set_bundle(src_file: &Path) -> io::Result<(StudySet, MediaEntries)> {
+    let mut archive = zip::ZipArchive::new(fs::File::open(src_file)?).map_err(|_| invalid(format!("{} is not a set bundle", src_file.display())))?;
+    let manifest: BundleManifest = serde_json::from_slice(&read_entry(&mut archive, MANIFEST_ENTRY)?)
+        .map_err(|e| invalid(format!("bad bundle manifest: {}", e)))?;
+    if manifest.format != BUNDLE_FORMAT {
+        return Err(invalid(format!("{} is not a Study Helper set bundle", src_file.display())));
+    }
+    if manifest.version > BUNDLE_VERSION {
+        return Err(invalid(format!("bundle version {} needs a newer version of Study Helper", manifest.version)));
+    }
+    let set: StudySet = serde_json::from_slice(&read_entry(&mut archive, SET_ENTRY)?)
+        .map_err(|e| invalid(format!("bad set in bundle: {}", e)))?;
+
+    let mut media = Vec::new();
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i).map_err(zip_err)?;
+        let Some(name) = entry.name().strip_prefix(MEDIA_ENTRY_PREFIX).map(str::to_string) else { continue };
+        if !entry.is_file() {
+            continue;
+        }
+        // only plain file names; anything else could escape the media folder
+        if name.is_empty() || sanitize_filename::sanitize(&name) != name {
+            return Err(invalid(format!("bundle contains an unsafe media name '{}'", name)));
+        }
+        let mut data = Vec::new();
+        entry.read_to_end(&mut data)?;
+        media.push((name, data));
+    }
+    Ok((set, media))
+}
+
+/// Add the set in a bundle to `class_name`, with its media. A set name that is
+/// taken in the class (or would share its file name) gets a " (copy)" suffix.
+pub fn import_set_bundle(store: &dyn SetStore, base_dir: &Path, class_name: &str, src_file: &Path) -> io::Result<BundleImport> {
+    let (mut set, media) = read_set_bundle(src_file)?;
+    let taken: Vec<String> = store.load_sets(class_name)?.iter().map(|s| sanitize_filename::sanitize(s.name())).collect();
+    let original = set.name().to_string();
+    let name = unique_set_name(&original, |n| taken.contains(&sanitize_filename::sanitize(n)));
+    set.set_name(name.clone());
+    store.save_set(class_name, &set)?;
+
+    if !media.is_empty() {
+        let dir = set_media_dir(base_dir, class_name, &name);
+        fs::create_dir_all(&dir)?;
+        for (file_name, data) in &media {
+            write_atomic(&dir.join(file_name), data)?;
+        }
+    }
+    log::info!("Imported bundle {} into class '{}' as set '{}'", src_file.display(), class_name, name);
+    Ok(BundleImport { set, renamed_from: (name != original).then_some(original), media_files: media.len() })
+}
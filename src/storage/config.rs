@@ -3,57 +3,294 @@ use std::path::{Path, PathBuf};
 use std::fs;
 use std::io;
 
-#[derive(Clone, Serialize, Deserialize, Debug)]
+/// Version of the config layout written by this build. Files without a version
+/// are from before versioning and are read as version 1.
+pub const CONFIG_VERSION: u32 = 2;
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
 pub struct Config {
+    #[serde(default = "legacy_config_version")]
+    pub version: u32,
     pub storage_base_path: String,
     pub storage_class_name: String,
+    /// Days deleted cards, quizzes and sets stay in a class's trash before being purged.
+    #[serde(default = "default_trash_retention_days")]
+    pub trash_retention_days: u32,
+    /// Save edited sets automatically after a pause in editing and when the window loses focus.
+    #[serde(default = "default_autosave_enabled")]
+    pub autosave_enabled: bool,
+    /// Seconds without further edits before an autosave runs.
+    #[serde(default = "default_autosave_delay_secs")]
+    pub autosave_delay_secs: u32,
+    /// How classes and sets are stored in the base folder.
+    #[serde(default)]
+    pub storage_backend: super::StorageBackend,
+    /// Layout of printed flashcards.
+    #[serde(default)]
+    pub card_sheet: crate::formats::CardSheetOptions,
+    /// Style and separator last used to import or export card outlines.
+    #[serde(default)]
+    pub outline: crate::formats::OutlineOptions,
+    /// Separators last used to paste cards copied from Quizlet.
+    #[serde(default)]
+    pub quizlet: crate::formats::QuizletOptions,
+    /// Keyboard shortcuts, by action name.
+    #[serde(default)]
+    pub shortcuts: crate::keymap::Keymap,
+    /// Theme, fonts, text size and zoom.
+    #[serde(default)]
+    pub appearance: crate::theme::Appearance,
+    /// Defaults for studying cards and taking quizzes.
+    #[serde(default)]
+    pub study: StudyOptions,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StudyOptions {
+    /// Most cards never studied before that a study session introduces per day.
+    pub new_cards_per_day: u32,
+    /// Ask quiz and Learn questions in random order.
+    pub shuffle: bool,
+    /// How typed answers are compared with the expected answer.
+    pub answer_matching: crate::models::AnswerMatching,
+    /// Scheduler used when cards are graded.
+    pub algorithm: crate::models::ReviewAlgorithm,
+    /// Days a card waits in each Leitner box before its next review, box 1 first.
+    pub leitner_intervals: Vec<u32>,
+}
+
+impl Default for StudyOptions {
+    fn default() -> Self {
+        StudyOptions {
+            new_cards_per_day: 20,
+            shuffle: false,
+            answer_matching: Default::default(),
+            algorithm: Default::default(),
+            leitner_intervals: crate::models::DEFAULT_LEITNER_INTERVALS.to_vec(),
+        }
+    }
+}
+
+/// Default pause (in seconds) after the last edit before dirty sets are autosaved.
+pub const DEFAULT_AUTOSAVE_DELAY_SECS: u32 = 3;
+
+fn legacy_config_version() -> u32 {
+    1
+}
+
+fn default_trash_retention_days() -> u32 {
+    super::DEFAULT_TRASH_RETENTION_DAYS
+}
+
+fn default_autosave_enabled() -> bool {
+    true
+}
+
+fn default_autosave_delay_secs() -> u32 {
+    DEFAULT_AUTOSAVE_DELAY_SECS
 }
 
 impl Default for Config {
     fn default() -> Self {
         Config {
+            version: CONFIG_VERSION,
             storage_base_path: String::new(),
             storage_class_name: String::new(),
+            trash_retention_days: default_trash_retention_days(),
+            autosave_enabled: default_autosave_enabled(),
+            autosave_delay_secs: default_autosave_delay_secs(),
+            storage_backend: Default::default(),
+            card_sheet: Default::default(),
+            outline: Default::default(),
+            quizlet: Default::default(),
+            shortcuts: Default::default(),
+            appearance: Default::default(),
+            study: Default::default(),
         }
     }
 }
 
-/// Path to the config file. Currently placed next to the executable / working directory as `config.json`.
-fn config_path() -> PathBuf {
-    // Prefer a config file located next to the running executable. When the app
-    // is launched via a Windows shortcut the working directory can differ
-    // from the executable directory ("Start in" setting), so using the
-    // executable directory avoids "config not found" issues.
-    if let Ok(exe_path) = std::env::current_exe() {
-        if let Some(dir) = exe_path.parent() { 
-            return dir.join("config.json");
+impl Config {
+    /// Bring settings read from disk up to date and back into their allowed ranges.
+    /// Returns a message for every value that had to be changed.
+    pub fn validate(&mut self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.version > CONFIG_VERSION {
+            problems.push(format!("The settings file is from a newer version of Study Helper (version {}); settings this version does not know will be lost when it saves", self.version));
+        }
+        // version 1 had no version field; every setting added since has a default
+        self.version = CONFIG_VERSION;
+
+        fn clamp<T: PartialOrd + Copy + std::fmt::Display>(problems: &mut Vec<String>, name: &str, value: &mut T, min: T, max: T) {
+            if *value < min || *value > max {
+                let fixed = if *value < min { min } else { max };
+                problems.push(format!("{} was {}; using {} (allowed {} to {})", name, value, fixed, min, max));
+                *value = fixed;
+            }
+        }
+        clamp(&mut problems, "Trash retention (days)", &mut self.trash_retention_days, 1, 3650);
+        clamp(&mut problems, "Autosave delay (seconds)", &mut self.autosave_delay_secs, 1, 600);
+        clamp(&mut problems, "New cards per day", &mut self.study.new_cards_per_day, 0, 9999);
+        clamp(&mut problems, "Allowed typos", &mut self.study.answer_matching.allowed_typos, 0, 5);
+        let boxes = &mut self.study.leitner_intervals;
+        if !crate::models::LEITNER_BOXES.contains(&boxes.len()) {
+            problems.push(format!("The Leitner system had {} boxes; using the default {} boxes", boxes.len(), crate::models::DEFAULT_LEITNER_INTERVALS.len()));
+            *boxes = crate::models::DEFAULT_LEITNER_INTERVALS.to_vec();
+        }
+        for (n, days) in boxes.iter_mut().enumerate() {
+            clamp(&mut problems, &format!("Leitner box {} interval (days)", n + 1), days, 1, 3650);
         }
+        let (min_size, max_size) = crate::theme::FONT_SIZE_RANGE.into_inner();
+        // NaN compares false with everything, so replace it before clamping
+        if !self.appearance.font_size.is_finite() {
+            self.appearance.font_size = crate::theme::DEFAULT_FONT_SIZE;
+        }
+        clamp(&mut problems, "Text size", &mut self.appearance.font_size, min_size, max_size);
+        if let Some(zoom) = &mut self.appearance.zoom {
+            if !zoom.is_finite() {
+                *zoom = 1.0;
+            }
+            let (min_zoom, max_zoom) = crate::theme::ZOOM_RANGE.into_inner();
+            clamp(&mut problems, "Zoom", zoom, min_zoom, max_zoom);
+        }
+        for (label, path) in [("Font", &mut self.appearance.font_path), ("CJK font", &mut self.appearance.cjk_font_path)] {
+            if !path.trim().is_empty() && !Path::new(path.trim()).is_file() {
+                problems.push(format!("{} file '{}' was not found; using the built-in font", label, path));
+                path.clear();
+            }
+        }
+        problems
     }
+}
+
+/// Environment variable naming the config file to use instead of the default location.
+pub const CONFIG_ENV_VAR: &str = "STUDY_HELPER_CONFIG";
 
-    // Fallback: use current working directory (preserves previous behavior if
-    // current_exe() fails for some reason).
+/// Where the path of the config file came from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConfigSource {
+    /// `--config <path>` on the command line.
+    CommandLine,
+    /// The `STUDY_HELPER_CONFIG` environment variable.
+    Environment,
+    /// The platform's per-user config directory.
+    Platform,
+}
+
+impl ConfigSource {
+    pub fn label(self) -> &'static str {
+        match self {
+            ConfigSource::CommandLine => "set with --config",
+            ConfigSource::Environment => "set by STUDY_HELPER_CONFIG",
+            ConfigSource::Platform => "default location",
+        }
+    }
+}
+
+static CONFIG_OVERRIDE: std::sync::OnceLock<PathBuf> = std::sync::OnceLock::new();
+
+/// Use `path` as the config file for the rest of the run (the `--config` flag).
+/// Must be called before the config is first read; later calls are ignored.
+pub fn set_config_path(path: PathBuf) {
+    if CONFIG_OVERRIDE.set(path).is_err() {
+        log::warn!("Config path was already set; ignoring the new one");
+    }
+}
+
+/// Path to the config file and where it came from: `--config`, then the
+/// environment variable, then `config.json` in the platform config directory.
+pub fn config_location() -> (PathBuf, ConfigSource) {
+    if let Some(path) = CONFIG_OVERRIDE.get() {
+        return (path.clone(), ConfigSource::CommandLine);
+    }
+    if let Some(path) = std::env::var_os(CONFIG_ENV_VAR).filter(|p| !p.is_empty()) {
+        return (PathBuf::from(path), ConfigSource::Environment);
+    }
+    (platform_config_dir().join("config.json"), ConfigSource::Platform)
+}
+
+pub fn config_path() -> PathBuf {
+    config_location().0
+}
+
+/// Per-user config directory: `$XDG_CONFIG_HOME/study-helper` (or `~/.config/study-helper`)
+/// on Linux and BSD, `~/Library/Application Support/Study Helper` on macOS and
+/// `%APPDATA%\Study Helper` on Windows. Falls back to the legacy location when the
+/// home directory is unknown.
+fn platform_config_dir() -> PathBuf {
+    let env_dir = |name: &str| std::env::var_os(name).filter(|v| !v.is_empty()).map(PathBuf::from);
+    let dir = if cfg!(windows) {
+        env_dir("APPDATA").map(|d| d.join("Study Helper"))
+    } else if cfg!(target_os = "macos") {
+        env_dir("HOME").map(|h| h.join("Library").join("Application Support").join("Study Helper"))
+    } else {
+        env_dir("XDG_CONFIG_HOME")
+            .filter(|d| d.is_absolute())
+            .or_else(|| env_dir("HOME").map(|h| h.join(".config")))
+            .map(|d| d.join("study-helper"))
+    };
+    dir.unwrap_or_else(|| legacy_config_path().parent().map(Path::to_path_buf).unwrap_or_default())
+}
+
+/// Where versions before the platform config directory kept `config.json`: next to
+/// the executable, or in the working directory if the executable path is unknown.
+fn legacy_config_path() -> PathBuf {
+    // When the app is launched via a Windows shortcut the working directory can
+    // differ from the executable directory ("Start in" setting), so the executable
+    // directory was preferred.
+    if let Ok(exe_path) = std::env::current_exe()
+        && let Some(dir) = exe_path.parent()
+    {
+        return dir.join("config.json");
+    }
     std::env::current_dir().unwrap_or_else(|_| Path::new(".").to_path_buf()).join("config.json")
 }
 
-/// Load configuration from disk. If the file doesn't exist, returns `Config::default()`.
-pub fn load_config() -> io::Result<Config> {
-    let p = config_path();
+/// Copy a config left next to the executable by an older version to the platform
+/// location, once, so existing settings survive the move. The old file is left
+/// alone (its folder may be read-only).
+fn migrate_legacy_config(target: &Path) {
+    let legacy = legacy_config_path();
+    if target.exists() || legacy == target || !legacy.is_file() {
+        return;
+    }
+    let copied = fs::read(&legacy).and_then(|bytes| super::write_atomic(target, &bytes));
+    match copied {
+        Ok(()) => log::info!("Copied settings from {} to {}", legacy.display(), target.display()),
+        Err(e) => log::warn!("Could not copy settings from {} to {}: {}", legacy.display(), target.display(), e),
+    }
+}
+
+/// Load the configuration of `profile` from disk. If the file doesn't exist, returns
+/// `Config::default()`. A file that cannot be parsed is copied aside (see
+/// `INVALID_CONFIG_SUFFIX`) so the defaults saved in its place don't destroy it.
+pub fn load_config(profile: &str) -> io::Result<Config> {
+    let p = super::profile_config_path(profile);
+    if profile == crate::models::DEFAULT_PROFILE && config_location().1 == ConfigSource::Platform {
+        migrate_legacy_config(&p);
+    }
     if !p.exists() {
         return Ok(Config::default());
     }
     let s = fs::read_to_string(&p)?;
-    let cfg: Config = serde_json::from_str(&s).map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
-    Ok(cfg)
+    serde_json::from_str(&s).map_err(|e| {
+        let copy = p.with_extension(INVALID_CONFIG_SUFFIX);
+        match fs::copy(&p, &copy) {
+            Ok(_) => io::Error::new(io::ErrorKind::InvalidData, format!("{} (a copy was kept as {})", e, copy.display())),
+            Err(_) => io::Error::new(io::ErrorKind::InvalidData, e),
+        }
+    })
 }
 
-/// Save the provided config to disk (overwrites).
-pub fn save_config(cfg: &Config) -> io::Result<()> {
-    let s = serde_json::to_string_pretty(cfg).map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
-    let p = config_path();
-    if let Some(parent) = p.parent() {
-        // parent is typically the current directory; attempt to create if needed
-        let _ = fs::create_dir_all(parent);
-    }
-    fs::write(&p, s)?;
+/// Extension of the copy kept of a config file that could not be read.
+pub const INVALID_CONFIG_SUFFIX: &str = "invalid.json";
+
+/// Save the config of `profile` to disk (atomically replaces the old file).
+pub fn save_config(profile: &str, cfg: &Config) -> io::Result<()> {
+    let s = serde_json::to_string_pretty(cfg).map_err(io::Error::other)?;
+    let p = super::profile_config_path(profile);
+    // write_atomic creates the parent folder and never leaves a truncated file behind
+    super::write_atomic(&p, s.as_bytes())?;
     Ok(())
 }
@@ -1,18 +1,16 @@
 // src/storage/json_store.rs
 
 use std::fs;
-use std::io::{self, Write};
+use std::io;
 use std::path::{Path, PathBuf};
 use crate::models::StudySet;
+use super::atomic::{commit_temp_file, write_atomic, write_temp_file};
+use super::backup::{backup_set_file, rename_set_backups};
 
-/// Save a single study set to the given file path (overwrites).
+/// Save a single study set to the given file path (overwrites atomically).
 pub fn save_study_set_to_file(study_set: &StudySet, file_path: &Path) -> io::Result<()> {
-    let json = serde_json::to_string_pretty(study_set).map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
-    if let Some(parent) = file_path.parent() {
-        fs::create_dir_all(parent)?;
-    }
-    let mut f = fs::File::create(file_path)?;
-    f.write_all(json.as_bytes())?;
+    let json = serde_json::to_string_pretty(study_set).map_err(io::Error::other)?;
+    write_atomic(file_path, json.as_bytes())?;
     // Log the save operation for debugging; the caller controls log level.
     log::debug!("Saved study set '{}' to {}", study_set.name(), file_path.display());
     Ok(())
@@ -26,11 +24,41 @@ pub fn load_study_set_from_file(file_path: &Path) -> io::Result<StudySet> {
     Ok(set)
 }
 
+/// File path of a set inside a class folder: base_dir / class_name / sanitized(set_name).json
+pub fn set_file_path(base_dir: &Path, class_name: &str, set_name: &str) -> PathBuf {
+    base_dir.join(class_name).join(format!("{}.json", sanitize_filename::sanitize(set_name)))
+}
+
+/// Make sure `path` is free to hold the set called `set_name`. Different set
+/// names can sanitize to the same file name ("A/B" and "A_B"), so an existing
+/// file is only accepted when it already holds a set with exactly that name.
+fn ensure_set_file_available(path: &Path, set_name: &str) -> io::Result<()> {
+    if !path.exists() {
+        return Ok(());
+    }
+    match load_study_set_from_file(path) {
+        Ok(existing) if existing.name() == set_name => Ok(()),
+        Ok(existing) => Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("'{}' would overwrite set '{}' stored in {}", set_name, existing.name(), path.display()),
+        )),
+        Err(e) => Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists and could not be read ({}); refusing to overwrite it", path.display(), e),
+        )),
+    }
+}
+
 /// Save a study set into a class folder under base_dir: base_dir / class_name / set_name.json
+/// Fails with `AlreadyExists` if the file belongs to a different set.
 pub fn save_set_into_class_folder(base_dir: &Path, class_name: &str, set_name: &str, study_set: &StudySet) -> io::Result<PathBuf> {
-    let mut path = base_dir.join(class_name);
-    fs::create_dir_all(&path)?;
-    path.push(format!("{}.json", sanitize_filename::sanitize(set_name)));
+    fs::create_dir_all(base_dir.join(class_name))?;
+    let path = set_file_path(base_dir, class_name, set_name);
+    ensure_set_file_available(&path, set_name)?;
+    // a failed backup shouldn't block the save itself
+    if let Err(e) = backup_set_file(base_dir, class_name, set_name, false) {
+        log::warn!("Backup of set '{}' failed: {}", set_name, e);
+    }
     save_study_set_to_file(study_set, &path)?;
     log::info!("Persisted set '{}' into class '{}' at {}", set_name, class_name, path.display());
     Ok(path)
@@ -60,6 +88,9 @@ pub fn import_set_file_to_class(base_dir: &Path, class_name: &str, src_file: &Pa
     fs::create_dir_all(&class_dir)?;
     let file_name = src_file.file_name().ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "source has no filename"))?;
     let dst = class_dir.join(file_name);
+    if dst.exists() {
+        return Err(io::Error::new(io::ErrorKind::AlreadyExists, format!("{} already exists in class '{}'", dst.display(), class_name)));
+    }
     fs::copy(src_file, &dst)?;
     Ok(dst)
 }
@@ -73,7 +104,128 @@ pub fn export_set_file(src_file: &Path, dst_file: &Path) -> io::Result<()> {
     Ok(())
 }
 
-/// List class folders (subdirectories) under the given base directory.
+/// Rename a set on disk: the set is written under its new file name and the
+/// old file is removed only after the new one is in place. `study_set` is
+/// updated to carry the new name.
+pub fn rename_set_in_class_folder(base_dir: &Path, class_name: &str, study_set: &mut StudySet, new_name: &str) -> io::Result<PathBuf> {
+    let new_name = new_name.trim();
+    if new_name.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "set name cannot be empty"));
+    }
+    let old_name = study_set.name().to_string();
+    let old_path = set_file_path(base_dir, class_name, &old_name);
+    let new_path = set_file_path(base_dir, class_name, new_name);
+    // the target file may be our own file (same sanitized name, or a
+    // case-only change on a case-insensitive filesystem)
+    if new_path != old_path && ensure_set_file_available(&new_path, &old_name).is_err() {
+        return Err(io::Error::new(io::ErrorKind::AlreadyExists, format!("another set is already stored as {}", new_path.display())));
+    }
+
+    let mut renamed = study_set.clone();
+    renamed.set_name(new_name.to_string());
+    let json = serde_json::to_string_pretty(&renamed).map_err(io::Error::other)?;
+    let case_only = new_path != old_path && new_path.to_string_lossy().to_lowercase() == old_path.to_string_lossy().to_lowercase();
+    if case_only && old_path.exists() {
+        // on a case-insensitive filesystem both names are the same file: rename it
+        // first, so committing the new contents below never leaves the set without a file
+        fs::rename(&old_path, &new_path)?;
+    }
+    // the set is written under its new name before the old file goes away
+    let tmp = write_temp_file(&new_path, json.as_bytes())?;
+    commit_temp_file(&tmp, &new_path)?;
+    // after a case-only rename the old name is either gone or the same file
+    if new_path != old_path && !case_only && old_path.exists() && !same_file(&old_path, &new_path)
+        && let Err(e) = fs::remove_file(&old_path)
+    {
+        // the set is safe under its new name; the stale copy shows up as a second set
+        log::warn!("Renamed set '{}' but could not remove {}: {}", new_name, old_path.display(), e);
+    }
+    if let Err(e) = rename_set_backups(base_dir, class_name, &old_name, new_name) {
+        log::warn!("Failed to move backups of set '{}' to '{}': {}", old_name, new_name, e);
+    }
+    *study_set = renamed;
+    log::info!("Renamed set '{}' to '{}' in class '{}'", old_name, new_name, class_name);
+    Ok(new_path)
+}
+
+/// Whether two existing paths name the same file (e.g. differently cased names
+/// on a case-insensitive filesystem).
+#[cfg(unix)]
+fn same_file(a: &Path, b: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    match (fs::metadata(a), fs::metadata(b)) {
+        (Ok(a), Ok(b)) => a.dev() == b.dev() && a.ino() == b.ino(),
+        _ => false,
+    }
+}
+
+#[cfg(not(unix))]
+fn same_file(a: &Path, b: &Path) -> bool {
+    match (fs::canonicalize(a), fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+/// Remove a set's file from its class folder (its backups are kept).
+pub fn delete_set_file(base_dir: &Path, class_name: &str, set_name: &str) -> io::Result<()> {
+    let path = set_file_path(base_dir, class_name, set_name);
+    if path.exists() {
+        fs::remove_file(&path)?;
+        log::info!("Deleted set file {}", path.display());
+    }
+    Ok(())
+}
+
+/// Pick a set name for which `taken` is false: `desired` itself, then
+/// "Name (copy)", "Name (copy 2)", ...
+pub fn unique_set_name(desired: &str, taken: impl Fn(&str) -> bool) -> String {
+    if !taken(desired) {
+        return desired.to_string();
+    }
+    let mut n = 1;
+    loop {
+        let candidate = if n == 1 { format!("{} (copy)", desired) } else { format!("{} (copy {})", desired, n) };
+        if !taken(&candidate) {
+            return candidate;
+        }
+        n += 1;
+    }
+}
+
+/// Save a copy of `study_set` named `new_name` into `target_class`. Never
+/// overwrites an existing file. Returns the copy and where it was written.
+pub fn copy_set_to_class(base_dir: &Path, study_set: &StudySet, target_class: &str, new_name: &str) -> io::Result<(StudySet, PathBuf)> {
+    let new_name = new_name.trim();
+    if new_name.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "set name cannot be empty"));
+    }
+    let path = set_file_path(base_dir, target_class, new_name);
+    if path.exists() {
+        return Err(io::Error::new(io::ErrorKind::AlreadyExists, format!("{} already exists in class '{}'", path.display(), target_class)));
+    }
+    let mut copy = study_set.clone();
+    copy.set_name(new_name.to_string());
+    let path = save_set_into_class_folder(base_dir, target_class, new_name, &copy)?;
+    Ok((copy, path))
+}
+
+/// Move a set file from one class folder to another. The source file is
+/// removed only after the copy has been written.
+pub fn move_set_to_class(base_dir: &Path, study_set: &StudySet, from_class: &str, target_class: &str) -> io::Result<PathBuf> {
+    if from_class == target_class {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "set is already in that class"));
+    }
+    let (_, path) = copy_set_to_class(base_dir, study_set, target_class, study_set.name())?;
+    let old_path = set_file_path(base_dir, from_class, study_set.name());
+    if old_path.exists() {
+        fs::remove_file(&old_path)?;
+    }
+    log::info!("Moved set '{}' from class '{}' to '{}'", study_set.name(), from_class, target_class);
+    Ok(path)
+}
+
+/// List class folders (subdirectories) under the given base directory, sorted by name.
 pub fn list_class_folders(base_dir: &Path) -> io::Result<Vec<String>> {
     let mut classes = Vec::new();
     if !base_dir.exists() {
@@ -83,10 +235,197 @@ pub fn list_class_folders(base_dir: &Path) -> io::Result<Vec<String>> {
         let entry = entry?;
         let p = entry.path();
         if p.is_dir() {
-            if let Some(name) = p.file_name().and_then(|n| n.to_str()) {
+            // skip internal folders such as `.trash`
+            if let Some(name) = p.file_name().and_then(|n| n.to_str()).filter(|n| !n.starts_with('.')) {
                 classes.push(name.to_string());
             }
         }
     }
+    classes.sort_by_key(|c| c.to_lowercase());
     Ok(classes)
-}
\ No newline at end of file
+}
+
+/// Per-class counts shown in the class manager.
+#[derive(Clone, Debug, Default)]
+pub struct ClassSummary {
+    pub name: String,
+    pub set_count: usize,
+    pub card_count: usize,
+}
+
+/// Name of the base-level folder that receives deleted classes when the user
+/// chooses "move to trash" instead of a permanent delete.
+pub const CLASS_TRASH_DIR: &str = ".trash";
+
+/// Seconds since the Unix epoch; used to stamp trashed folders and backups.
+pub fn unix_timestamp() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Format a Unix timestamp in local time for display.
+pub fn format_timestamp(secs: u64) -> String {
+    chrono::DateTime::from_timestamp(secs as i64, 0)
+        .map(|d| d.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_else(|| secs.to_string())
+}
+
+/// Validate a user-supplied class name and return the trimmed version.
+/// Class names become folder names, so they must survive `sanitize_filename`
+/// unchanged and must not start with '.' (reserved for internal folders).
+pub fn validate_class_name(class_name: &str) -> io::Result<String> {
+    let name = class_name.trim();
+    if name.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "class name cannot be empty"));
+    }
+    if name.starts_with('.') {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "class name cannot start with '.'"));
+    }
+    if sanitize_filename::sanitize(name) != name {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("class name '{}' contains characters that are not allowed in folder names", name)));
+    }
+    Ok(name.to_string())
+}
+
+/// Create a new, empty class folder under base_dir. Fails if it already exists.
+pub fn create_class_folder(base_dir: &Path, class_name: &str) -> io::Result<PathBuf> {
+    let name = validate_class_name(class_name)?;
+    let dir = base_dir.join(&name);
+    if dir.exists() {
+        return Err(io::Error::new(io::ErrorKind::AlreadyExists, format!("class '{}' already exists", name)));
+    }
+    fs::create_dir_all(&dir)?;
+    log::info!("Created class folder '{}' at {}", name, dir.display());
+    Ok(dir)
+}
+
+/// Rename a class folder, moving every set file with it. Falls back to a
+/// recursive copy + delete when the platform refuses a direct rename.
+pub fn rename_class_folder(base_dir: &Path, old_name: &str, new_name: &str) -> io::Result<PathBuf> {
+    let old_name = validate_class_name(old_name)?;
+    let new_name = validate_class_name(new_name)?;
+    let src = base_dir.join(&old_name);
+    let dst = base_dir.join(&new_name);
+    if !src.is_dir() {
+        return Err(io::Error::new(io::ErrorKind::NotFound, format!("class '{}' does not exist", old_name)));
+    }
+    if dst.exists() {
+        return Err(io::Error::new(io::ErrorKind::AlreadyExists, format!("class '{}' already exists", new_name)));
+    }
+    if let Err(e) = fs::rename(&src, &dst) {
+        log::warn!("Direct rename of class '{}' failed ({}); copying instead", old_name, e);
+        copy_dir_recursive(&src, &dst)?;
+        fs::remove_dir_all(&src)?;
+    }
+    log::info!("Renamed class '{}' to '{}'", old_name, new_name);
+    Ok(dst)
+}
+
+/// Delete a class folder. With `move_to_trash` the folder is moved into
+/// `base_dir/.trash/<class>-<timestamp>` so it can be recovered by hand;
+/// otherwise it is removed permanently.
+pub fn delete_class_folder(base_dir: &Path, class_name: &str, move_to_trash: bool) -> io::Result<()> {
+    // the name is joined onto base_dir, so "..", ".trash" or "a/b" must not get through
+    let class_name = &validate_class_name(class_name)?;
+    let dir = base_dir.join(class_name);
+    if !dir.is_dir() {
+        return Err(io::Error::new(io::ErrorKind::NotFound, format!("class '{}' does not exist", class_name)));
+    }
+    if move_to_trash {
+        let trash = base_dir.join(CLASS_TRASH_DIR);
+        fs::create_dir_all(&trash)?;
+        let dst = trash.join(format!("{}-{}", class_name, unix_timestamp()));
+        if let Err(e) = fs::rename(&dir, &dst) {
+            log::warn!("Direct move of class '{}' to trash failed ({}); copying instead", class_name, e);
+            copy_dir_recursive(&dir, &dst)?;
+            fs::remove_dir_all(&dir)?;
+        }
+        log::info!("Moved class '{}' to trash at {}", class_name, dst.display());
+    } else {
+        fs::remove_dir_all(&dir)?;
+        log::info!("Permanently deleted class '{}'", class_name);
+    }
+    Ok(())
+}
+
+/// Count the sets and cards in a class folder.
+pub fn summarize_class_folder(base_dir: &Path, class_name: &str) -> io::Result<ClassSummary> {
+    let sets = load_sets_from_class_folder(base_dir, class_name)?;
+    Ok(ClassSummary {
+        name: class_name.to_string(),
+        set_count: sets.len(),
+        card_count: sets.iter().map(|s| s.get_all_flashcards().len()).sum(),
+    })
+}
+
+fn copy_dir_recursive(src: &Path, dst: &Path) -> io::Result<()> {
+    fs::create_dir_all(dst)?;
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let target = dst.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir_recursive(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_base(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("study_helper_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn rename_moves_the_set_to_its_new_file() {
+        let base = temp_base("rename");
+        let mut set = StudySet::new("Old".to_string());
+        save_set_into_class_folder(&base, "C", "Old", &set).unwrap();
+        let path = rename_set_in_class_folder(&base, "C", &mut set, "New").unwrap();
+        assert_eq!(set.name(), "New");
+        assert_eq!(load_study_set_from_file(&path).unwrap().name(), "New");
+        assert!(!set_file_path(&base, "C", "Old").exists());
+        fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[test]
+    fn case_only_rename_keeps_a_single_file() {
+        let base = temp_base("rename_case");
+        let mut set = StudySet::new("biology".to_string());
+        save_set_into_class_folder(&base, "C", "biology", &set).unwrap();
+        rename_set_in_class_folder(&base, "C", &mut set, "Biology").unwrap();
+        let sets = load_sets_from_class_folder(&base, "C").unwrap();
+        assert_eq!(sets.len(), 1);
+        assert_eq!(sets[0].name(), "Biology");
+        fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[test]
+    fn class_names_cannot_leave_the_base_folder() {
+        let base = temp_base("delete_class");
+        let outside = base.join("outside");
+        let inner = outside.join("base");
+        fs::create_dir_all(inner.join(CLASS_TRASH_DIR)).unwrap();
+        fs::create_dir_all(inner.join("Real")).unwrap();
+        for name in ["..", ".", "../base", "Real/../..", ".trash", "  "] {
+            for to_trash in [true, false] {
+                let err = delete_class_folder(&inner, name, to_trash).unwrap_err();
+                assert_eq!(err.kind(), io::ErrorKind::InvalidInput, "{:?}", name);
+            }
+            assert!(rename_class_folder(&inner, name, "Moved").is_err(), "{:?}", name);
+        }
+        assert!(outside.is_dir() && inner.join(CLASS_TRASH_DIR).is_dir());
+
+        delete_class_folder(&inner, "Real", false).unwrap();
+        assert!(!inner.join("Real").exists());
+        fs::remove_dir_all(&base).unwrap();
+    }
+}
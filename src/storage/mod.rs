@@ -1,5 +1,26 @@
 mod json_store;
 mod config;
+mod atomic;
+mod backup;
+mod trash;
+mod watcher;
+mod worker;
+mod backend;
+mod sqlite_store;
+mod bundle;
+mod archive;
+mod profiles;
 
 pub use json_store::*;
-pub use config::*;
\ No newline at end of file
+pub use config::*;
+pub use atomic::*;
+pub use backup::*;
+pub use trash::*;
+pub use watcher::*;
+pub use worker::*;
+pub use backend::*;
+pub use sqlite_store::*;
+pub use bundle::*;
+pub use archive::*;
+pub use profiles::*;
+
@@ -0,0 +1,89 @@
+// src/storage/profiles.rs
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use serde::{Serialize, Deserialize};
+use crate::models::DEFAULT_PROFILE;
+use super::config::config_path;
+
+/// File next to the config that lists the profiles and the one in use.
+pub const PROFILES_FILE: &str = "profiles.json";
+/// Folder next to the config holding the config of every profile but the default one:
+/// profiles / sanitized(name).json
+pub const PROFILE_CONFIG_DIR: &str = "profiles";
+
+/// The people using this installation. Each profile has its own config (and so its
+/// own settings, storage folder and last class); review progress is kept per
+/// profile on the cards themselves, so profiles can share the same sets.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Profiles {
+    pub active: String,
+    pub names: Vec<String>,
+}
+
+impl Default for Profiles {
+    fn default() -> Self {
+        Profiles { active: DEFAULT_PROFILE.to_string(), names: vec![DEFAULT_PROFILE.to_string()] }
+    }
+}
+
+impl Profiles {
+    /// Whether `name` is empty or would share a config file with an existing profile.
+    pub fn name_taken(&self, name: &str) -> bool {
+        let file = sanitize_filename::sanitize(name.trim()).to_lowercase();
+        file.is_empty() || self.names.iter().any(|n| sanitize_filename::sanitize(n).to_lowercase() == file)
+    }
+}
+
+fn profiles_path() -> PathBuf {
+    config_path().with_file_name(PROFILES_FILE)
+}
+
+/// Config file of `profile`. The default profile keeps using the main config file.
+pub fn profile_config_path(profile: &str) -> PathBuf {
+    if profile == DEFAULT_PROFILE {
+        return config_path();
+    }
+    config_path().with_file_name(PROFILE_CONFIG_DIR).join(format!("{}.json", sanitize_filename::sanitize(profile)))
+}
+
+/// Read the profile list. A missing file means only the default profile exists;
+/// an unreadable one is logged and treated the same way.
+pub fn load_profiles() -> Profiles {
+    let p = profiles_path();
+    let mut profiles = match fs::read_to_string(&p) {
+        Ok(text) => serde_json::from_str(&text).unwrap_or_else(|e| {
+            log::warn!("Ignoring unreadable profile list {}: {}", p.display(), e);
+            Profiles::default()
+        }),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Profiles::default(),
+        Err(e) => {
+            log::warn!("Failed to read profile list {}: {}", p.display(), e);
+            Profiles::default()
+        }
+    };
+    if !profiles.names.iter().any(|n| n == DEFAULT_PROFILE) {
+        profiles.names.insert(0, DEFAULT_PROFILE.to_string());
+    }
+    if !profiles.names.contains(&profiles.active) {
+        profiles.active = DEFAULT_PROFILE.to_string();
+    }
+    profiles
+}
+
+pub fn save_profiles(profiles: &Profiles) -> io::Result<()> {
+    let s = serde_json::to_string_pretty(profiles).map_err(io::Error::other)?;
+    super::write_atomic(&profiles_path(), s.as_bytes())
+}
+
+/// Delete the config file of `profile`. Its progress stays on the cards.
+pub fn delete_profile_config(profile: &str) -> io::Result<()> {
+    if profile == DEFAULT_PROFILE {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "The default profile cannot be deleted"));
+    }
+    match fs::remove_file(profile_config_path(profile)) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
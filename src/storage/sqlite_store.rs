@@ -0,0 +1,662 @@
+// src/storage/sqlite_store.rs
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use rusqlite::{params, Connection, OptionalExtension};
+use crate::models::{Flashcard, Quiz, StudySet, DEFAULT_PROFILE};
+use super::backend::{CardMatch, SetStore};
+use super::json_store::{load_study_set_from_file, save_study_set_to_file, unix_timestamp, validate_class_name, ClassSummary, CLASS_TRASH_DIR};
+
+/// Database file created in the base folder when the SQLite backend is selected.
+pub const SQLITE_DB_FILE: &str = "study_helper.db";
+
+/// Bumped whenever `SCHEMA` changes; `open` upgrades older databases.
+const SCHEMA_VERSION: i32 = 5;
+
+const SCHEMA: &str = "
+CREATE TABLE classes (
+    id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE
+);
+CREATE TABLE sets (
+    id INTEGER PRIMARY KEY,
+    class_id INTEGER NOT NULL REFERENCES classes(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    -- the Match game leaderboard as a JSON array; NULL while empty
+    match_scores TEXT,
+    UNIQUE (class_id, name)
+);
+-- one row per card, so adding or editing a card rewrites only that row
+CREATE TABLE cards (
+    id INTEGER PRIMARY KEY,
+    set_id INTEGER NOT NULL REFERENCES sets(id) ON DELETE CASCADE,
+    -- the card's own id (Flashcard::id), which a save matches rows by
+    card_id INTEGER NOT NULL,
+    position INTEGER NOT NULL,
+    question TEXT NOT NULL,
+    answer TEXT NOT NULL,
+    hints TEXT NOT NULL,
+    -- next review as Unix seconds; NULL until the card is scheduled
+    due_at INTEGER,
+    -- the card's ReviewState as JSON; NULL until the card is reviewed
+    review TEXT,
+    -- ReviewStates of the other profiles as a JSON object by profile name; NULL if none
+    profile_reviews TEXT,
+    UNIQUE (set_id, position)
+);
+CREATE INDEX cards_due ON cards (due_at) WHERE due_at IS NOT NULL;
+CREATE TABLE quizzes (
+    id INTEGER PRIMARY KEY,
+    set_id INTEGER NOT NULL REFERENCES sets(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    data TEXT NOT NULL,
+    UNIQUE (set_id, position)
+);
+-- full-text index over the card text, kept in step by the triggers below
+CREATE VIRTUAL TABLE cards_fts USING fts5(question, answer, content='cards', content_rowid='id');
+CREATE TRIGGER cards_ai AFTER INSERT ON cards BEGIN
+    INSERT INTO cards_fts (rowid, question, answer) VALUES (new.id, new.question, new.answer);
+END;
+CREATE TRIGGER cards_ad AFTER DELETE ON cards BEGIN
+    INSERT INTO cards_fts (cards_fts, rowid, question, answer) VALUES ('delete', old.id, old.question, old.answer);
+END;
+CREATE TRIGGER cards_au AFTER UPDATE OF question, answer ON cards BEGIN
+    INSERT INTO cards_fts (cards_fts, rowid, question, answer) VALUES ('delete', old.id, old.question, old.answer);
+    INSERT INTO cards_fts (rowid, question, answer) VALUES (new.id, new.question, new.answer);
+END;
+";
+
+/// id, position, question, answer, hints, review and profile_reviews of a stored card row.
+type ExistingCard = (i64, i64, String, String, String, Option<String>, Option<String>);
+
+fn sql_err(e: rusqlite::Error) -> io::Error {
+    io::Error::other(e)
+}
+
+fn json_err(e: serde_json::Error) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, e)
+}
+
+/// All classes and sets in one database file (`base_dir/SQLITE_DB_FILE`).
+pub struct SqliteStore {
+    base_dir: PathBuf,
+    conn: Connection,
+}
+
+impl SqliteStore {
+    /// Open (or create) the database in `base_dir` and bring its schema up to date.
+    pub fn open(base_dir: &Path) -> io::Result<Self> {
+        fs::create_dir_all(base_dir)?;
+        let conn = Connection::open(base_dir.join(SQLITE_DB_FILE)).map_err(sql_err)?;
+        conn.pragma_update(None, "foreign_keys", true).map_err(sql_err)?;
+        conn.busy_timeout(std::time::Duration::from_secs(5)).map_err(sql_err)?;
+        let version: i32 = conn.pragma_query_value(None, "user_version", |row| row.get(0)).map_err(sql_err)?;
+        if version > SCHEMA_VERSION {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, format!(
+                "{} was written by a newer version of Study Helper (schema {})", SQLITE_DB_FILE, version)));
+        }
+        if version < 1 {
+            conn.execute_batch(SCHEMA).map_err(sql_err)?;
+            conn.pragma_update(None, "user_version", SCHEMA_VERSION).map_err(sql_err)?;
+            log::info!("Created SQLite store in {}", base_dir.display());
+        } else {
+            if version < 2 {
+                conn.execute_batch("ALTER TABLE cards ADD COLUMN review TEXT;").map_err(sql_err)?;
+                conn.pragma_update(None, "user_version", 2).map_err(sql_err)?;
+                log::info!("Upgraded SQLite store in {} to schema 2", base_dir.display());
+            }
+            if version < 3 {
+                conn.execute_batch("ALTER TABLE cards ADD COLUMN profile_reviews TEXT;").map_err(sql_err)?;
+                conn.pragma_update(None, "user_version", 3).map_err(sql_err)?;
+                log::info!("Upgraded SQLite store in {} to schema 3", base_dir.display());
+            }
+            if version < 4 {
+                conn.execute_batch("ALTER TABLE sets ADD COLUMN match_scores TEXT;").map_err(sql_err)?;
+                conn.pragma_update(None, "user_version", 4).map_err(sql_err)?;
+                log::info!("Upgraded SQLite store in {} to schema 4", base_dir.display());
+            }
+            if version < 5 {
+                conn.execute_batch("ALTER TABLE cards ADD COLUMN card_id INTEGER NOT NULL DEFAULT 0; UPDATE cards SET card_id = id;").map_err(sql_err)?;
+                conn.pragma_update(None, "user_version", 5).map_err(sql_err)?;
+                log::info!("Upgraded SQLite store in {} to schema 5", base_dir.display());
+            }
+        }
+        Ok(SqliteStore { base_dir: base_dir.to_path_buf(), conn })
+    }
+
+    fn class_id(&self, class_name: &str) -> io::Result<Option<i64>> {
+        self.conn.query_row("SELECT id FROM classes WHERE name = ?1", [class_name], |row| row.get(0))
+            .optional().map_err(sql_err)
+    }
+
+    /// Id of `class_name`, creating the class if needed (saving into a new class
+    /// creates it, like the JSON backend creates the folder).
+    fn ensure_class(&self, class_name: &str) -> io::Result<i64> {
+        self.conn.execute("INSERT OR IGNORE INTO classes (name) VALUES (?1)", [class_name]).map_err(sql_err)?;
+        self.class_id(class_name)?.ok_or_else(|| io::Error::other("class row missing after insert"))
+    }
+
+    fn set_id(&self, class_id: i64, set_name: &str) -> io::Result<Option<i64>> {
+        self.conn.query_row("SELECT id FROM sets WHERE class_id = ?1 AND name = ?2", params![class_id, set_name], |row| row.get(0))
+            .optional().map_err(sql_err)
+    }
+
+    fn read_set(&self, set_id: i64, name: String) -> io::Result<StudySet> {
+        let mut set = StudySet::new(name);
+        let scores: Option<String> = self.conn.query_row("SELECT match_scores FROM sets WHERE id = ?1", [set_id], |row| row.get(0)).map_err(sql_err)?;
+        if let Some(scores) = scores {
+            set.set_match_scores(serde_json::from_str(&scores).map_err(json_err)?);
+        }
+        let mut stmt = self.conn.prepare_cached("SELECT card_id, question, answer, hints, review, profile_reviews FROM cards WHERE set_id = ?1 ORDER BY position").map_err(sql_err)?;
+        let rows = stmt.query_map([set_id], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?, row.get::<_, String>(3)?, row.get::<_, Option<String>>(4)?, row.get::<_, Option<String>>(5)?))
+        }).map_err(sql_err)?;
+        for row in rows {
+            let (card_id, question, answer, hints, review, profile_reviews) = row.map_err(sql_err)?;
+            let hints: Vec<String> = serde_json::from_str(&hints).map_err(json_err)?;
+            let mut card = Flashcard::new(question, answer, hints);
+            card.set_id(card_id as u64);
+            card.set_review(DEFAULT_PROFILE, review.map(|r| serde_json::from_str(&r)).transpose().map_err(json_err)?);
+            if let Some(reviews) = profile_reviews {
+                card.set_profile_reviews(serde_json::from_str(&reviews).map_err(json_err)?);
+            }
+            set.add_flashcard(card);
+        }
+        let mut stmt = self.conn.prepare_cached("SELECT data FROM quizzes WHERE set_id = ?1 ORDER BY position").map_err(sql_err)?;
+        let rows = stmt.query_map([set_id], |row| row.get::<_, String>(0)).map_err(sql_err)?;
+        for row in rows {
+            let quiz: Quiz = serde_json::from_str(&row.map_err(sql_err)?).map_err(json_err)?;
+            set.add_quiz(quiz);
+        }
+        Ok(set)
+    }
+
+    /// Write `study_set` into `class_id`, touching only the card and quiz rows
+    /// that changed. Must run inside a transaction.
+    fn write_set(&self, class_id: i64, study_set: &StudySet) -> io::Result<()> {
+        self.conn.execute("INSERT OR IGNORE INTO sets (class_id, name) VALUES (?1, ?2)", params![class_id, study_set.name()]).map_err(sql_err)?;
+        let set_id = self.set_id(class_id, study_set.name())?.ok_or_else(|| io::Error::other("set row missing after insert"))?;
+        let scores = Some(study_set.match_scores()).filter(|s| !s.is_empty()).map(serde_json::to_string).transpose().map_err(json_err)?;
+        self.conn.execute("UPDATE sets SET match_scores = ?1 WHERE id = ?2", params![scores, set_id]).map_err(sql_err)?;
+
+        // rows by the id of their card; a card copied within the set shares its id, so each takes the next row
+        let mut existing: HashMap<i64, Vec<ExistingCard>> = HashMap::new();
+        {
+            let mut stmt = self.conn.prepare_cached("SELECT card_id, id, position, question, answer, hints, review, profile_reviews FROM cards WHERE set_id = ?1 ORDER BY position DESC").map_err(sql_err)?;
+            let rows = stmt.query_map([set_id], |row| {
+                Ok((row.get::<_, i64>(0)?, (row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?, row.get(5)?, row.get(6)?, row.get(7)?)))
+            }).map_err(sql_err)?;
+            for row in rows {
+                let (card_id, card) = row.map_err(sql_err)?;
+                existing.entry(card_id).or_default().push(card);
+            }
+        }
+        let cards = study_set.get_all_flashcards();
+        let matched: Vec<Option<ExistingCard>> = cards.iter().map(|card| existing.get_mut(&(card.id() as i64)).and_then(Vec::pop)).collect();
+        for (id, ..) in existing.values().flatten() {
+            self.conn.execute("DELETE FROM cards WHERE id = ?1", [id]).map_err(sql_err)?;
+        }
+        // moved rows park at -1 - position until every row has moved, as positions are unique
+        for (position, (card, row)) in cards.iter().zip(matched).enumerate() {
+            let position = position as i64;
+            let hints = serde_json::to_string(card.get_hints()).map_err(json_err)?;
+            let review = card.review(DEFAULT_PROFILE).map(serde_json::to_string).transpose().map_err(json_err)?;
+            let due_at = card.review(DEFAULT_PROFILE).map(|r| r.due_at as i64);
+            let others = Some(card.profile_reviews()).filter(|r| !r.is_empty()).map(serde_json::to_string).transpose().map_err(json_err)?;
+            match row {
+                Some((id, p, q, a, h, r, o)) if q == card.question() && a == card.answer() && h == hints && r == review && o == others => {
+                    if p != position {
+                        self.conn.execute("UPDATE cards SET position = ?1 WHERE id = ?2", params![-1 - position, id]).map_err(sql_err)?;
+                    }
+                }
+                Some((id, p, ..)) => {
+                    let position = if p == position { position } else { -1 - position };
+                    self.conn.execute("UPDATE cards SET position = ?1, question = ?2, answer = ?3, hints = ?4, review = ?5, due_at = ?6, profile_reviews = ?7 WHERE id = ?8",
+                        params![position, card.question(), card.answer(), hints, review, due_at, others, id]).map_err(sql_err)?;
+                }
+                None => {
+                    self.conn.execute("INSERT INTO cards (set_id, card_id, position, question, answer, hints, review, due_at, profile_reviews) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                        params![set_id, card.id() as i64, -1 - position, card.question(), card.answer(), hints, review, due_at, others]).map_err(sql_err)?;
+                }
+            }
+        }
+        self.conn.execute("UPDATE cards SET position = -1 - position WHERE set_id = ?1 AND position < 0", [set_id]).map_err(sql_err)?;
+
+        let existing: Vec<(i64, String)> = {
+            let mut stmt = self.conn.prepare_cached("SELECT id, data FROM quizzes WHERE set_id = ?1 ORDER BY position").map_err(sql_err)?;
+            let rows = stmt.query_map([set_id], |row| Ok((row.get(0)?, row.get(1)?))).map_err(sql_err)?;
+            rows.collect::<Result<_, _>>().map_err(sql_err)?
+        };
+        let quizzes = study_set.get_all_quizzes();
+        for (position, quiz) in quizzes.iter().enumerate() {
+            let data = serde_json::to_string(quiz).map_err(json_err)?;
+            match existing.get(position) {
+                Some((_, d)) if *d == data => {}
+                Some((id, _)) => {
+                    self.conn.execute("UPDATE quizzes SET data = ?1 WHERE id = ?2", params![data, id]).map_err(sql_err)?;
+                }
+                None => {
+                    self.conn.execute("INSERT INTO quizzes (set_id, position, data) VALUES (?1, ?2, ?3)",
+                        params![set_id, position as i64, data]).map_err(sql_err)?;
+                }
+            }
+        }
+        self.conn.execute("DELETE FROM quizzes WHERE set_id = ?1 AND position >= ?2", params![set_id, quizzes.len() as i64]).map_err(sql_err)?;
+        Ok(())
+    }
+
+    fn already_exists(set_name: &str, class_name: &str) -> io::Error {
+        io::Error::new(io::ErrorKind::AlreadyExists, format!("set '{}' already exists in class '{}'", set_name, class_name))
+    }
+}
+
+/// FTS5 query matching rows that contain words starting with each word of `query`.
+fn fts_query(query: &str) -> String {
+    query.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| format!("\"{}\"*", w))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+impl SetStore for SqliteStore {
+    fn list_classes(&self) -> io::Result<Vec<String>> {
+        let mut stmt = self.conn.prepare("SELECT name FROM classes").map_err(sql_err)?;
+        let mut classes: Vec<String> = stmt.query_map([], |row| row.get(0)).map_err(sql_err)?
+            .collect::<Result<_, _>>().map_err(sql_err)?;
+        classes.sort_by_key(|c| c.to_lowercase());
+        Ok(classes)
+    }
+
+    fn create_class(&self, class_name: &str) -> io::Result<()> {
+        let name = validate_class_name(class_name)?;
+        if self.class_id(&name)?.is_some() {
+            return Err(io::Error::new(io::ErrorKind::AlreadyExists, format!("class '{}' already exists", name)));
+        }
+        self.conn.execute("INSERT INTO classes (name) VALUES (?1)", [&name]).map_err(sql_err)?;
+        log::info!("Created class '{}' in the SQLite store", name);
+        Ok(())
+    }
+
+    fn rename_class(&self, old_name: &str, new_name: &str) -> io::Result<()> {
+        let new_name = validate_class_name(new_name)?;
+        let Some(id) = self.class_id(old_name)? else {
+            return Err(io::Error::new(io::ErrorKind::NotFound, format!("class '{}' does not exist", old_name)));
+        };
+        if self.class_id(&new_name)?.is_some() {
+            return Err(io::Error::new(io::ErrorKind::AlreadyExists, format!("class '{}' already exists", new_name)));
+        }
+        self.conn.execute("UPDATE classes SET name = ?1 WHERE id = ?2", params![new_name, id]).map_err(sql_err)?;
+        // the class's trash still lives in a folder named after it
+        let old_dir = self.base_dir.join(old_name);
+        if old_dir.is_dir() && let Err(e) = fs::rename(&old_dir, self.base_dir.join(&new_name)) {
+            log::warn!("Failed to rename the trash folder of class '{}': {}", old_name, e);
+        }
+        log::info!("Renamed class '{}' to '{}'", old_name, new_name);
+        Ok(())
+    }
+
+    fn delete_class(&self, class_name: &str, to_trash: bool) -> io::Result<()> {
+        // its trash folder is named after it, like a JSON class folder
+        let class_name = &validate_class_name(class_name)?;
+        let Some(id) = self.class_id(class_name)? else {
+            return Err(io::Error::new(io::ErrorKind::NotFound, format!("class '{}' does not exist", class_name)));
+        };
+        let class_dir = self.base_dir.join(class_name);
+        if to_trash {
+            // same place the JSON backend moves class folders to, with the sets written out as JSON
+            let dst = self.base_dir.join(CLASS_TRASH_DIR).join(format!("{}-{}", class_name, unix_timestamp()));
+            fs::create_dir_all(self.base_dir.join(CLASS_TRASH_DIR))?;
+            if class_dir.is_dir() {
+                fs::rename(&class_dir, &dst)?;
+            } else {
+                fs::create_dir_all(&dst)?;
+            }
+            for set in self.load_sets(class_name)? {
+                save_study_set_to_file(&set, &dst.join(format!("{}.json", sanitize_filename::sanitize(set.name()))))?;
+            }
+            log::info!("Moved class '{}' to trash at {}", class_name, dst.display());
+        } else if class_dir.is_dir() {
+            fs::remove_dir_all(&class_dir)?;
+        }
+        self.conn.execute("DELETE FROM classes WHERE id = ?1", [id]).map_err(sql_err)?;
+        log::info!("Deleted class '{}' from the SQLite store", class_name);
+        Ok(())
+    }
+
+    fn summarize_class(&self, class_name: &str) -> io::Result<ClassSummary> {
+        let (set_count, card_count): (i64, i64) = self.conn.query_row(
+            "SELECT (SELECT COUNT(*) FROM sets s JOIN classes k ON k.id = s.class_id WHERE k.name = ?1),
+                    (SELECT COUNT(*) FROM cards c JOIN sets s ON s.id = c.set_id JOIN classes k ON k.id = s.class_id WHERE k.name = ?1)",
+            [class_name], |row| Ok((row.get(0)?, row.get(1)?))).map_err(sql_err)?;
+        Ok(ClassSummary { name: class_name.to_string(), set_count: set_count as usize, card_count: card_count as usize })
+    }
+
+    fn load_sets(&self, class_name: &str) -> io::Result<Vec<StudySet>> {
+        let Some(class_id) = self.class_id(class_name)? else { return Ok(Vec::new()) };
+        let ids: Vec<(i64, String)> = {
+            let mut stmt = self.conn.prepare("SELECT id, name FROM sets WHERE class_id = ?1 ORDER BY name").map_err(sql_err)?;
+            let rows = stmt.query_map([class_id], |row| Ok((row.get(0)?, row.get(1)?))).map_err(sql_err)?;
+            rows.collect::<Result<_, _>>().map_err(sql_err)?
+        };
+        let sets = ids.into_iter().map(|(id, name)| self.read_set(id, name)).collect::<io::Result<Vec<_>>>()?;
+        log::info!("Loaded {} sets of class '{}' from the SQLite store", sets.len(), class_name);
+        Ok(sets)
+    }
+
+    fn load_set(&self, class_name: &str, set_name: &str) -> io::Result<Option<StudySet>> {
+        let Some(class_id) = self.class_id(class_name)? else { return Ok(None) };
+        match self.set_id(class_id, set_name)? {
+            Some(id) => self.read_set(id, set_name.to_string()).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn save_set(&self, class_name: &str, study_set: &StudySet) -> io::Result<()> {
+        let tx = self.conn.unchecked_transaction().map_err(sql_err)?;
+        let class_id = self.ensure_class(class_name)?;
+        self.write_set(class_id, study_set)?;
+        tx.commit().map_err(sql_err)?;
+        log::debug!("Saved set '{}' into class '{}' (SQLite)", study_set.name(), class_name);
+        Ok(())
+    }
+
+    fn rename_set(&self, class_name: &str, study_set: &mut StudySet, new_name: &str) -> io::Result<()> {
+        let new_name = new_name.trim();
+        if new_name.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "set name cannot be empty"));
+        }
+        let tx = self.conn.unchecked_transaction().map_err(sql_err)?;
+        let class_id = self.ensure_class(class_name)?;
+        if new_name != study_set.name() && self.set_id(class_id, new_name)?.is_some() {
+            return Err(Self::already_exists(new_name, class_name));
+        }
+        self.conn.execute("UPDATE sets SET name = ?1 WHERE class_id = ?2 AND name = ?3", params![new_name, class_id, study_set.name()]).map_err(sql_err)?;
+        let mut renamed = study_set.clone();
+        renamed.set_name(new_name.to_string());
+        // the renamed set keeps the caller's pending edits, like the JSON backend
+        self.write_set(class_id, &renamed)?;
+        tx.commit().map_err(sql_err)?;
+        log::info!("Renamed set '{}' to '{}' in class '{}'", study_set.name(), new_name, class_name);
+        *study_set = renamed;
+        Ok(())
+    }
+
+    fn delete_set(&self, class_name: &str, set_name: &str) -> io::Result<()> {
+        let Some(class_id) = self.class_id(class_name)? else { return Ok(()) };
+        self.conn.execute("DELETE FROM sets WHERE class_id = ?1 AND name = ?2", params![class_id, set_name]).map_err(sql_err)?;
+        Ok(())
+    }
+
+    fn copy_set(&self, study_set: &StudySet, target_class: &str, new_name: &str) -> io::Result<StudySet> {
+        let new_name = new_name.trim();
+        if new_name.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "set name cannot be empty"));
+        }
+        let tx = self.conn.unchecked_transaction().map_err(sql_err)?;
+        let class_id = self.ensure_class(target_class)?;
+        if self.set_id(class_id, new_name)?.is_some() {
+            return Err(Self::already_exists(new_name, target_class));
+        }
+        let mut copy = study_set.clone();
+        copy.set_name(new_name.to_string());
+        self.write_set(class_id, &copy)?;
+        tx.commit().map_err(sql_err)?;
+        Ok(copy)
+    }
+
+    fn move_set(&self, study_set: &StudySet, from_class: &str, target_class: &str) -> io::Result<()> {
+        if from_class == target_class {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "set is already in that class"));
+        }
+        let tx = self.conn.unchecked_transaction().map_err(sql_err)?;
+        let target_id = self.ensure_class(target_class)?;
+        if self.set_id(target_id, study_set.name())?.is_some() {
+            return Err(Self::already_exists(study_set.name(), target_class));
+        }
+        self.write_set(target_id, study_set)?;
+        if let Some(from_id) = self.class_id(from_class)? {
+            self.conn.execute("DELETE FROM sets WHERE class_id = ?1 AND name = ?2", params![from_id, study_set.name()]).map_err(sql_err)?;
+        }
+        tx.commit().map_err(sql_err)?;
+        log::info!("Moved set '{}' from class '{}' to '{}'", study_set.name(), from_class, target_class);
+        Ok(())
+    }
+
+    fn import_set_file(&self, class_name: &str, src_file: &Path) -> io::Result<StudySet> {
+        let set = load_study_set_from_file(src_file)?;
+        if self.load_set(class_name, set.name())?.is_some() {
+            return Err(Self::already_exists(set.name(), class_name));
+        }
+        self.save_set(class_name, &set)?;
+        Ok(set)
+    }
+
+    fn export_set_file(&self, class_name: &str, set_name: &str, dst_file: &Path) -> io::Result<()> {
+        let set = self.load_set(class_name, set_name)?.ok_or_else(|| {
+            io::Error::new(io::ErrorKind::NotFound, format!("set '{}' is not saved in class '{}'", set_name, class_name))
+        })?;
+        save_study_set_to_file(&set, dst_file)
+    }
+
+    fn search_cards(&self, class_name: &str, query: &str, limit: usize) -> io::Result<Vec<CardMatch>> {
+        let fts = fts_query(query);
+        if fts.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut stmt = self.conn.prepare_cached(
+            "SELECT s.name, c.position, c.question FROM cards_fts f
+             JOIN cards c ON c.id = f.rowid
+             JOIN sets s ON s.id = c.set_id
+             JOIN classes k ON k.id = s.class_id
+             WHERE cards_fts MATCH ?1 AND k.name = ?2
+             ORDER BY s.name, c.position LIMIT ?3").map_err(sql_err)?;
+        let rows = stmt.query_map(params![fts, class_name, limit as i64], |row| {
+            Ok(CardMatch { set_name: row.get(0)?, index: row.get::<_, i64>(1)? as usize, question: row.get(2)? })
+        }).map_err(sql_err)?;
+        rows.collect::<Result<_, _>>().map_err(sql_err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{schedule_sm2, Grade, MatchScore, QuestionType};
+
+    fn temp_base(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("study_helper_sqlite_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn card(question: &str, answer: &str) -> Flashcard {
+        Flashcard::new(question.to_string(), answer.to_string(), Vec::new())
+    }
+
+    /// Row id of every card of `set_name`, in position order.
+    fn row_ids(store: &SqliteStore, set_name: &str) -> Vec<i64> {
+        let mut stmt = store.conn.prepare("SELECT c.id FROM cards c JOIN sets s ON s.id = c.set_id WHERE s.name = ?1 ORDER BY c.position").unwrap();
+        stmt.query_map([set_name], |row| row.get(0)).unwrap().collect::<Result<_, _>>().unwrap()
+    }
+
+    #[test]
+    fn saved_set_loads_back_unchanged() {
+        let base = temp_base("round_trip");
+        let store = SqliteStore::open(&base).unwrap();
+        let mut set = StudySet::new("Biology".to_string());
+        let mut reviewed = Flashcard::new("Cell powerhouse".to_string(), "Mitochondrion".to_string(), vec!["energy".to_string(), "organelle".to_string()]);
+        reviewed.set_review(DEFAULT_PROFILE, Some(schedule_sm2(None, Grade::Good, 1_000)));
+        reviewed.set_review("Sam", Some(schedule_sm2(None, Grade::Again, 2_000)));
+        set.add_flashcard(reviewed);
+        set.add_flashcard(card("Plant pigment", "Chlorophyll"));
+        let mut quiz = Quiz::new("Cells".to_string());
+        quiz.add_question("Has a nucleus?".to_string(), vec!["Yes".to_string(), "No".to_string()], "Yes".to_string(), QuestionType::MultipleChoice);
+        set.add_quiz(quiz);
+        set.record_match_score(MatchScore { profile: "Sam".to_string(), time_ms: 4_200, pairs: 2, mistakes: 1, played_at: 3_000 });
+
+        store.save_set("Science", &set).unwrap();
+        let loaded = store.load_set("Science", "Biology").unwrap().unwrap();
+        assert!(loaded == set);
+        let ids: Vec<u64> = loaded.get_all_flashcards().iter().map(Flashcard::id).collect();
+        assert_eq!(ids, set.get_all_flashcards().iter().map(Flashcard::id).collect::<Vec<_>>());
+        assert_eq!(store.list_classes().unwrap(), ["Science"]);
+        fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[test]
+    fn card_inserted_at_the_front_keeps_the_other_rows() {
+        let base = temp_base("insert_front");
+        let store = SqliteStore::open(&base).unwrap();
+        let mut set = StudySet::new("Words".to_string());
+        for word in ["alpha", "beta", "gamma"] {
+            set.add_flashcard(card(word, &word.to_uppercase()));
+        }
+        store.save_set("C", &set).unwrap();
+        let before = row_ids(&store, "Words");
+
+        set.insert_flashcard(0, card("omega", "OMEGA"));
+        set.get_flashcard_mut(2).unwrap().add_hint("second letter".to_string());
+        store.save_set("C", &set).unwrap();
+        let after = row_ids(&store, "Words");
+        assert_eq!(after.len(), 4);
+        assert_eq!(after[1..], before[..]);
+        assert!(store.load_set("C", "Words").unwrap().unwrap() == set);
+
+        set.remove_flashcard(1);
+        set.insert_flashcard(3, card("delta", "DELTA"));
+        store.save_set("C", &set).unwrap();
+        let last = row_ids(&store, "Words");
+        assert_eq!(last.len(), 4);
+        assert_eq!(last[..3], [after[0], before[1], before[2]]);
+        assert!(store.load_set("C", "Words").unwrap().unwrap() == set);
+        fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[test]
+    fn edited_card_keeps_its_row() {
+        let base = temp_base("edit_in_place");
+        let store = SqliteStore::open(&base).unwrap();
+        let mut set = StudySet::new("Words".to_string());
+        for word in ["alpha", "beta", "gamma"] {
+            set.add_flashcard(card(word, &word.to_uppercase()));
+        }
+        store.save_set("C", &set).unwrap();
+        let before = row_ids(&store, "Words");
+
+        set.get_flashcard_mut(1).unwrap().set_text("bêta".to_string(), "BETA".to_string(), vec!["second".to_string()]);
+        store.save_set("C", &set).unwrap();
+        assert_eq!(row_ids(&store, "Words"), before);
+        let (position, question): (i64, String) = store.conn
+            .query_row("SELECT position, question FROM cards WHERE id = ?1", [before[1]], |row| Ok((row.get(0)?, row.get(1)?))).unwrap();
+        assert_eq!((position, question.as_str()), (1, "bêta"));
+        assert!(store.load_set("C", "Words").unwrap().unwrap() == set);
+        fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[test]
+    fn search_follows_card_edits() {
+        let base = temp_base("search");
+        let store = SqliteStore::open(&base).unwrap();
+        let mut set = StudySet::new("Chemistry".to_string());
+        set.add_flashcard(card("Symbol of gold", "Au"));
+        set.add_flashcard(card("Symbol of silver", "Ag"));
+        store.save_set("Science", &set).unwrap();
+        store.save_set("Other", &set).unwrap();
+
+        let found = store.search_cards("Science", "silv", 10).unwrap();
+        assert_eq!(found.iter().map(|m| (m.set_name.as_str(), m.index)).collect::<Vec<_>>(), [("Chemistry", 1)]);
+        assert_eq!(store.search_cards("Science", "symbol", 1).unwrap().len(), 1);
+        assert!(store.search_cards("Science", "  --  ", 10).unwrap().is_empty());
+
+        set.get_flashcard_mut(1).unwrap().set_text("Symbol of copper".to_string(), "Cu".to_string(), Vec::new());
+        store.save_set("Science", &set).unwrap();
+        assert!(store.search_cards("Science", "silver", 10).unwrap().is_empty());
+        assert_eq!(store.search_cards("Science", "copper", 10).unwrap()[0].question, "Symbol of copper");
+        fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[test]
+    fn due_at_follows_the_default_profile_review() {
+        let base = temp_base("due");
+        let store = SqliteStore::open(&base).unwrap();
+        let mut set = StudySet::new("Dates".to_string());
+        let mut due = card("1066", "Hastings");
+        due.set_review(DEFAULT_PROFILE, Some(schedule_sm2(None, Grade::Again, 1_000)));
+        let mut later = card("1815", "Waterloo");
+        later.set_review(DEFAULT_PROFILE, Some(schedule_sm2(None, Grade::Easy, 1_000)));
+        let mut other_profile = card("1415", "Agincourt");
+        other_profile.set_review("Sam", Some(schedule_sm2(None, Grade::Again, 1_000)));
+        for c in [due, later, other_profile, card("1914", "Marne")] {
+            set.add_flashcard(c);
+        }
+        store.save_set("History", &set).unwrap();
+
+        let due_by = |now: i64| -> Vec<String> {
+            let mut stmt = store.conn.prepare("SELECT question FROM cards WHERE due_at <= ?1 ORDER BY due_at").unwrap();
+            stmt.query_map([now], |row| row.get(0)).unwrap().collect::<Result<_, _>>().unwrap()
+        };
+        assert_eq!(due_by(2_000), ["1066"]);
+        assert_eq!(due_by(1_000 + 30 * 86_400), ["1066", "1815"]);
+
+        set.get_flashcard_mut(0).unwrap().set_review(DEFAULT_PROFILE, None);
+        store.save_set("History", &set).unwrap();
+        assert!(due_by(2_000).is_empty());
+        fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[test]
+    fn opening_a_schema_1_database_upgrades_it() {
+        let base = temp_base("migrate");
+        fs::create_dir_all(&base).unwrap();
+        {
+            let conn = Connection::open(base.join(SQLITE_DB_FILE)).unwrap();
+            conn.execute_batch("
+                CREATE TABLE classes (id INTEGER PRIMARY KEY, name TEXT NOT NULL UNIQUE);
+                CREATE TABLE sets (id INTEGER PRIMARY KEY, class_id INTEGER NOT NULL REFERENCES classes(id) ON DELETE CASCADE, name TEXT NOT NULL, UNIQUE (class_id, name));
+                CREATE TABLE cards (id INTEGER PRIMARY KEY, set_id INTEGER NOT NULL REFERENCES sets(id) ON DELETE CASCADE, position INTEGER NOT NULL,
+                    question TEXT NOT NULL, answer TEXT NOT NULL, hints TEXT NOT NULL, due_at INTEGER, UNIQUE (set_id, position));
+                CREATE INDEX cards_due ON cards (due_at) WHERE due_at IS NOT NULL;
+                CREATE TABLE quizzes (id INTEGER PRIMARY KEY, set_id INTEGER NOT NULL REFERENCES sets(id) ON DELETE CASCADE, position INTEGER NOT NULL, data TEXT NOT NULL, UNIQUE (set_id, position));
+                CREATE VIRTUAL TABLE cards_fts USING fts5(question, answer, content='cards', content_rowid='id');
+                CREATE TRIGGER cards_ai AFTER INSERT ON cards BEGIN
+                    INSERT INTO cards_fts (rowid, question, answer) VALUES (new.id, new.question, new.answer);
+                END;
+                CREATE TRIGGER cards_ad AFTER DELETE ON cards BEGIN
+                    INSERT INTO cards_fts (cards_fts, rowid, question, answer) VALUES ('delete', old.id, old.question, old.answer);
+                END;
+                CREATE TRIGGER cards_au AFTER UPDATE OF question, answer ON cards BEGIN
+                    INSERT INTO cards_fts (cards_fts, rowid, question, answer) VALUES ('delete', old.id, old.question, old.answer);
+                    INSERT INTO cards_fts (rowid, question, answer) VALUES (new.id, new.question, new.answer);
+                END;
+                INSERT INTO classes (id, name) VALUES (1, 'Old class');
+                INSERT INTO sets (id, class_id, name) VALUES (1, 1, 'Old set');
+                INSERT INTO cards (set_id, position, question, answer, hints) VALUES (1, 0, 'First question', 'one', '[\"a hint\"]');
+                INSERT INTO cards (set_id, position, question, answer, hints) VALUES (1, 1, 'Second question', 'two', '[]');
+                PRAGMA user_version = 1;
+            ").unwrap();
+        }
+
+        let store = SqliteStore::open(&base).unwrap();
+        let version: i32 = store.conn.pragma_query_value(None, "user_version", |row| row.get(0)).unwrap();
+        assert_eq!(version, SCHEMA_VERSION);
+        let mut set = store.load_set("Old class", "Old set").unwrap().unwrap();
+        assert_eq!(set.get_all_flashcards().len(), 2);
+        assert_eq!(set.get_all_flashcards()[0].get_hints(), &["a hint"]);
+        assert!(set.match_scores().is_empty() && set.get_all_flashcards()[0].review(DEFAULT_PROFILE).is_none());
+
+        // the upgraded rows are matched by the ids they were given
+        let before = row_ids(&store, "Old set");
+        set.get_flashcard_mut(1).unwrap().set_review(DEFAULT_PROFILE, Some(schedule_sm2(None, Grade::Good, 1_000)));
+        set.insert_flashcard(0, card("New question", "three"));
+        store.save_set("Old class", &set).unwrap();
+        assert_eq!(row_ids(&store, "Old set")[1..], before[..]);
+        assert_eq!(store.search_cards("Old class", "second", 10).unwrap()[0].index, 2);
+        drop(store);
+
+        let store = SqliteStore::open(&base).unwrap();
+        assert!(store.load_set("Old class", "Old set").unwrap().unwrap() == set);
+        fs::remove_dir_all(&base).unwrap();
+    }
+}
@@ -0,0 +1,126 @@
+// src/storage/trash.rs
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use serde::{Serialize, Deserialize};
+use crate::models::{Flashcard, Quiz, StudySet};
+use super::atomic::write_atomic;
+use super::json_store::unix_timestamp;
+
+/// Folder inside each class folder holding deleted cards, quizzes and sets:
+/// base_dir / class_name / .trash / <deleted_at>-<n>.json
+pub const ITEM_TRASH_DIR: &str = ".trash";
+/// Default number of days deleted items are kept before being purged.
+pub const DEFAULT_TRASH_RETENTION_DAYS: u32 = 30;
+
+/// Something that was deleted from a set (or a whole set).
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "data")]
+pub enum TrashedItem {
+    Flashcard(Flashcard),
+    Quiz(Quiz),
+    Set(StudySet),
+}
+
+/// One entry in a class's trash.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TrashEntry {
+    pub deleted_at: u64,
+    /// Set the item was deleted from (the set itself for `TrashedItem::Set`).
+    pub set_name: String,
+    pub item: TrashedItem,
+    /// File backing this entry; filled in when listing.
+    #[serde(skip)]
+    pub path: PathBuf,
+}
+
+impl TrashEntry {
+    /// Short human-readable description for the Trash view.
+    pub fn label(&self) -> String {
+        match &self.item {
+            TrashedItem::Flashcard(c) => format!("Card: {}", c.question()),
+            TrashedItem::Quiz(q) => format!("Quiz: {}", q.title()),
+            TrashedItem::Set(s) => format!("Set: {} ({} cards)", s.name(), s.get_all_flashcards().len()),
+        }
+    }
+}
+
+fn trash_dir(base_dir: &Path, class_name: &str) -> PathBuf {
+    base_dir.join(class_name).join(ITEM_TRASH_DIR)
+}
+
+/// Deletion time encoded in a trash file name, so purging doesn't need to parse JSON.
+fn trash_file_timestamp(path: &Path) -> Option<u64> {
+    path.file_stem()?.to_str()?.split('-').next()?.parse().ok()
+}
+
+/// Store a deleted item in the class trash and return its file path.
+pub fn move_to_trash(base_dir: &Path, class_name: &str, set_name: &str, item: TrashedItem) -> io::Result<PathBuf> {
+    let dir = trash_dir(base_dir, class_name);
+    fs::create_dir_all(&dir)?;
+    let deleted_at = unix_timestamp();
+    let mut n = 0;
+    let path = loop {
+        let candidate = dir.join(format!("{}-{}.json", deleted_at, n));
+        if !candidate.exists() {
+            break candidate;
+        }
+        n += 1;
+    };
+    let entry = TrashEntry { deleted_at, set_name: set_name.to_string(), item, path: PathBuf::new() };
+    let json = serde_json::to_string_pretty(&entry).map_err(io::Error::other)?;
+    write_atomic(&path, json.as_bytes())?;
+    log::info!("Moved '{}' from set '{}' to trash at {}", entry.label(), set_name, path.display());
+    Ok(path)
+}
+
+/// List the trash of a class, newest first. Unreadable entries are skipped.
+pub fn list_trash(base_dir: &Path, class_name: &str) -> io::Result<Vec<TrashEntry>> {
+    let mut entries = Vec::new();
+    let dir = trash_dir(base_dir, class_name);
+    if !dir.exists() {
+        return Ok(entries);
+    }
+    for entry in fs::read_dir(dir)? {
+        let p = entry?.path();
+        if p.extension().and_then(|s| s.to_str()) != Some("json") {
+            continue;
+        }
+        match fs::read_to_string(&p).map_err(|e| e.to_string()).and_then(|s| serde_json::from_str::<TrashEntry>(&s).map_err(|e| e.to_string())) {
+            Ok(mut e) => {
+                e.path = p;
+                entries.push(e);
+            }
+            Err(e) => log::warn!("Skipping unreadable trash entry {}: {}", p.display(), e),
+        }
+    }
+    entries.sort_by_key(|e| std::cmp::Reverse(e.deleted_at));
+    Ok(entries)
+}
+
+/// Remove an entry from the trash (after restoring it, or to purge it for good).
+pub fn remove_trash_entry(entry: &TrashEntry) -> io::Result<()> {
+    fs::remove_file(&entry.path)
+}
+
+/// Permanently delete trash entries older than `retention_days`. Returns how many were removed.
+pub fn purge_expired_trash(base_dir: &Path, class_name: &str, retention_days: u32) -> io::Result<usize> {
+    let dir = trash_dir(base_dir, class_name);
+    if !dir.exists() {
+        return Ok(0);
+    }
+    let cutoff = unix_timestamp().saturating_sub(u64::from(retention_days) * 24 * 60 * 60);
+    let mut purged = 0;
+    for entry in fs::read_dir(&dir)? {
+        let p = entry?.path();
+        if trash_file_timestamp(&p).is_some_and(|ts| ts < cutoff) {
+            fs::remove_file(&p)?;
+            purged += 1;
+        }
+    }
+    if purged > 0 {
+        log::info!("Purged {} expired trash entries from class '{}'", purged, class_name);
+    }
+    Ok(purged)
+}
@@ -0,0 +1,102 @@
+// src/storage/watcher.rs
+
+use std::io;
+use std::path::{Component, Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+/// A change below the storage base folder, reduced to what the app reloads.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StorageChange {
+    /// A class folder was created, removed or renamed.
+    ClassList,
+    /// A set file in `class_name` was created or rewritten.
+    SetWritten { class_name: String, path: PathBuf },
+    /// A set file in `class_name` no longer exists.
+    SetRemoved { class_name: String, path: PathBuf },
+}
+
+/// Recursive filesystem watch on the storage base folder.
+pub struct StorageWatcher {
+    // dropping the watcher unregisters the OS watch, so keep it alive
+    _watcher: RecommendedWatcher,
+    changes: Receiver<StorageChange>,
+}
+
+impl StorageWatcher {
+    /// Start watching `base_dir`. Events are classified on the watcher thread (which
+    /// may stat the changed path) and `on_event` runs after each one; the GUI uses it
+    /// to request a repaint so changes are polled promptly.
+    pub fn new(base_dir: &Path, on_event: impl Fn() + Send + 'static) -> io::Result<Self> {
+        let (tx, rx) = channel();
+        let root = base_dir.to_path_buf();
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            let event = match res {
+                Ok(event) => event,
+                Err(e) => {
+                    log::warn!("Storage watcher error: {}", e);
+                    return;
+                }
+            };
+            if matches!(event.kind, EventKind::Access(_)) {
+                return;
+            }
+            let mut sent = false;
+            for path in &event.paths {
+                if let Some(change) = classify_change(&root, path) {
+                    sent |= tx.send(change).is_ok();
+                }
+            }
+            if sent {
+                on_event();
+            }
+        })
+        .map_err(io::Error::other)?;
+        watcher.watch(base_dir, RecursiveMode::Recursive).map_err(io::Error::other)?;
+        log::info!("Watching storage folder {}", base_dir.display());
+        Ok(StorageWatcher { _watcher: watcher, changes: rx })
+    }
+
+    /// Drain the pending changes without blocking or touching the disk.
+    /// Repeated changes to the same path are collapsed.
+    pub fn poll(&self) -> Vec<StorageChange> {
+        let mut changes = Vec::new();
+        while let Ok(change) = self.changes.try_recv() {
+            if !changes.contains(&change) {
+                changes.push(change);
+            }
+        }
+        changes
+    }
+}
+
+/// Map a changed path to a class-list or set-file change. Hidden entries (`.trash`,
+/// `.backups`, the temp files of atomic writes) and anything deeper than a set file are ignored.
+fn classify_change(base_dir: &Path, path: &Path) -> Option<StorageChange> {
+    let rel = path.strip_prefix(base_dir).ok()?;
+    let parts: Vec<&str> = rel.components().filter_map(|c| match c {
+        Component::Normal(s) => s.to_str(),
+        _ => None,
+    }).collect();
+    if parts.iter().any(|p| p.starts_with('.')) {
+        return None;
+    }
+    match parts.as_slice() {
+        // the base folder itself was removed or replaced
+        [] => Some(StorageChange::ClassList),
+        // files directly in the base folder are not classes
+        [_] if path.is_file() => None,
+        [_] => Some(StorageChange::ClassList),
+        [class_name, file_name] if file_name.to_ascii_lowercase().ends_with(".json") => {
+            let class_name = class_name.to_string();
+            let path = path.to_path_buf();
+            // the event kind is platform dependent (renames in particular), so check the file itself
+            if path.exists() {
+                Some(StorageChange::SetWritten { class_name, path })
+            } else {
+                Some(StorageChange::SetRemoved { class_name, path })
+            }
+        }
+        _ => None,
+    }
+}
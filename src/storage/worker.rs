@@ -0,0 +1,159 @@
+// src/storage/worker.rs
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::mpsc::{channel, Receiver, Sender, TryRecvError};
+use std::thread;
+
+type Job<T> = Box<dyn FnOnce() -> T + Send>;
+/// A job's result, or the message of the panic that ended it.
+type JobResult<T> = Result<T, String>;
+
+/// Runs storage jobs on a single background thread so the UI never waits on the disk.
+///
+/// Jobs run one at a time in submission order, so a save queued before a load of the
+/// same class is on disk before the load reads it. Each job produces a `T` that the UI
+/// thread collects with `poll` (the GUI uses a closure that applies the result to the app).
+/// A job that panics is reported by `take_failures`; the thread keeps running the others.
+pub struct StorageWorker<T: Send + 'static> {
+    jobs: Sender<Job<T>>,
+    results: Receiver<JobResult<T>>,
+    // labels of submitted jobs that have not been collected yet, oldest first
+    pending: Vec<String>,
+    // "label: panic message" of jobs that panicked since the last `take_failures`
+    failures: Vec<String>,
+}
+
+/// Start the worker thread; it ends when the job sender is dropped.
+fn spawn_worker<T: Send + 'static>() -> (Sender<Job<T>>, Receiver<JobResult<T>>) {
+    let (job_tx, job_rx) = channel::<Job<T>>();
+    let (result_tx, result_rx) = channel();
+    thread::Builder::new()
+        .name("storage-worker".to_string())
+        .spawn(move || {
+            for job in job_rx {
+                let result = catch_unwind(AssertUnwindSafe(job)).map_err(|payload| {
+                    payload.downcast_ref::<&str>().map(|s| s.to_string())
+                        .or_else(|| payload.downcast_ref::<String>().cloned())
+                        .unwrap_or_else(|| "unknown panic".to_string())
+                });
+                if result_tx.send(result).is_err() {
+                    break;
+                }
+            }
+        })
+        .expect("failed to spawn storage worker thread");
+    (job_tx, result_rx)
+}
+
+impl<T: Send + 'static> StorageWorker<T> {
+    pub fn new() -> Self {
+        let (jobs, results) = spawn_worker();
+        StorageWorker { jobs, results, pending: Vec::new(), failures: Vec::new() }
+    }
+
+    /// Queue a job. `label` describes it in progress indicators ("Saving 'Biology'").
+    /// If the thread is gone, a new one is started for this and later jobs.
+    pub fn submit(&mut self, label: impl Into<String>, job: impl FnOnce() -> T + Send + 'static) {
+        let label = label.into();
+        log::debug!("Queued storage job: {}", label);
+        let job: Job<T> = match self.jobs.send(Box::new(job)) {
+            Ok(()) => {
+                self.pending.push(label);
+                return;
+            }
+            Err(e) => e.0,
+        };
+        log::error!("Storage worker is gone; starting a new one for '{}'", label);
+        // jobs queued on the old thread will never finish
+        for lost in self.pending.drain(..) {
+            self.failures.push(format!("{}: the storage thread stopped", lost));
+        }
+        (self.jobs, self.results) = spawn_worker();
+        match self.jobs.send(job) {
+            Ok(()) => self.pending.push(label),
+            Err(_) => self.failures.push(format!("{}: the storage thread could not be started", label)),
+        }
+    }
+
+    /// Jobs that panicked (or were lost) since the last call, as "label: reason".
+    pub fn take_failures(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.failures)
+    }
+
+    /// Results of finished jobs, in submission order. Never blocks.
+    pub fn poll(&mut self) -> Vec<T> {
+        let mut done = Vec::new();
+        loop {
+            match self.results.try_recv() {
+                Ok(result) => {
+                    let label = if self.pending.is_empty() { String::new() } else { self.pending.remove(0) };
+                    match result {
+                        Ok(result) => done.push(result),
+                        Err(panic) => {
+                            log::error!("Storage job '{}' panicked: {}", label, panic);
+                            self.failures.push(format!("{}: {}", label, panic));
+                        }
+                    }
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    // the thread is gone; nothing else will finish, `submit` starts a new one
+                    if !self.pending.is_empty() {
+                        log::error!("Storage worker stopped with {} job(s) pending", self.pending.len());
+                        for lost in self.pending.drain(..) {
+                            self.failures.push(format!("{}: the storage thread stopped", lost));
+                        }
+                    }
+                    break;
+                }
+            }
+        }
+        done
+    }
+
+    pub fn is_busy(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Label of the job currently running (the oldest one not yet collected).
+    pub fn current_job(&self) -> Option<&str> {
+        self.pending.first().map(String::as_str)
+    }
+
+    pub fn pending_count(&self) -> usize {
+        self.pending.len()
+    }
+}
+
+impl<T: Send + 'static> Default for StorageWorker<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    fn wait_for(worker: &mut StorageWorker<u32>) -> Vec<u32> {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let mut done = Vec::new();
+        while worker.is_busy() && Instant::now() < deadline {
+            done.extend(worker.poll());
+            thread::sleep(Duration::from_millis(5));
+        }
+        done
+    }
+
+    #[test]
+    fn panicking_job_is_reported_and_later_jobs_still_run() {
+        let mut worker = StorageWorker::new();
+        worker.submit("first", || 1);
+        worker.submit("broken", || panic!("disk on fire"));
+        worker.submit("third", || 3);
+        assert_eq!(wait_for(&mut worker), vec![1, 3]);
+        assert_eq!(worker.take_failures(), vec!["broken: disk on fire".to_string()]);
+        assert!(worker.take_failures().is_empty());
+    }
+}
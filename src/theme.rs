@@ -0,0 +1,160 @@
+// src/theme.rs
+
+/*
+Appearance settings: colour theme, fonts, text size and zoom. `Appearance` is
+stored in the config; `visuals` turns the theme into egui colours each frame
+and `load_fonts` reads the chosen font files (on the storage worker, since it
+touches the disk). A font file is checked before it is handed to egui, which
+would otherwise panic on a file it cannot parse.
+*/
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use ab_glyph::Font;
+use eframe::egui::{self, Color32, FontData, FontDefinitions, FontFamily, Stroke, Visuals};
+use serde::{Serialize, Deserialize};
+
+/// Body text size at 100% zoom; headings and buttons are sized relative to it.
+pub const DEFAULT_FONT_SIZE: f32 = 16.0;
+pub const FONT_SIZE_RANGE: std::ops::RangeInclusive<f32> = 10.0..=32.0;
+pub const ZOOM_RANGE: std::ops::RangeInclusive<f32> = 0.5..=3.0;
+
+/// Colour theme of the whole window.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Theme {
+    /// Follow the operating system's light or dark setting.
+    #[default]
+    System,
+    Light,
+    Dark,
+    HighContrast,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 4] = [Theme::System, Theme::Light, Theme::Dark, Theme::HighContrast];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Theme::System => "Same as system",
+            Theme::Light => "Light",
+            Theme::Dark => "Dark",
+            Theme::HighContrast => "High contrast",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Appearance {
+    pub theme: Theme,
+    /// TTF/OTF file used for all text instead of the built-in font; empty for the built-in font.
+    pub font_path: String,
+    /// Font used for characters the main font lacks, such as Chinese, Japanese or Korean.
+    pub cjk_font_path: String,
+    /// Body text size in points at 100% zoom.
+    pub font_size: f32,
+    /// Fixed UI zoom; `None` scales the UI with the window width.
+    pub zoom: Option<f32>,
+}
+
+impl Default for Appearance {
+    fn default() -> Self {
+        Appearance { theme: Theme::System, font_path: String::new(), cjk_font_path: String::new(), font_size: DEFAULT_FONT_SIZE, zoom: None }
+    }
+}
+
+impl Appearance {
+    /// Colours for the theme; `system` is the OS preference, if known (dark otherwise).
+    pub fn visuals(&self, system: Option<egui::Theme>) -> Visuals {
+        match self.theme {
+            Theme::System if system == Some(egui::Theme::Light) => Visuals::light(),
+            Theme::System | Theme::Dark => Visuals::dark(),
+            Theme::Light => Visuals::light(),
+            Theme::HighContrast => high_contrast(),
+        }
+    }
+
+    /// The font files to load, as (main font, CJK fallback); empty paths are skipped.
+    pub fn font_files(&self) -> (String, String) {
+        (self.font_path.trim().to_string(), self.cjk_font_path.trim().to_string())
+    }
+}
+
+/// White on black with bold outlines and a yellow focus colour.
+fn high_contrast() -> Visuals {
+    let mut v = Visuals::dark();
+    v.override_text_color = Some(Color32::WHITE);
+    v.panel_fill = Color32::BLACK;
+    v.window_fill = Color32::BLACK;
+    v.extreme_bg_color = Color32::BLACK;
+    v.faint_bg_color = Color32::from_gray(30);
+    v.window_stroke = Stroke::new(2.0, Color32::WHITE);
+    v.hyperlink_color = Color32::YELLOW;
+    v.warn_fg_color = Color32::YELLOW;
+    v.error_fg_color = Color32::from_rgb(255, 110, 110);
+    v.selection.bg_fill = Color32::from_rgb(0, 70, 200);
+    v.selection.stroke = Stroke::new(2.0, Color32::YELLOW);
+    for w in [&mut v.widgets.noninteractive, &mut v.widgets.inactive, &mut v.widgets.hovered, &mut v.widgets.active, &mut v.widgets.open] {
+        w.fg_stroke = Stroke::new(1.5, Color32::WHITE);
+        w.bg_stroke = Stroke::new(1.5, Color32::WHITE);
+    }
+    v.widgets.noninteractive.bg_fill = Color32::BLACK;
+    v.widgets.inactive.bg_fill = Color32::BLACK;
+    v.widgets.inactive.weak_bg_fill = Color32::BLACK;
+    v.widgets.hovered.bg_stroke = Stroke::new(2.5, Color32::YELLOW);
+    v.widgets.active.bg_stroke = Stroke::new(2.5, Color32::YELLOW);
+    v
+}
+
+/// Colour for "correct" feedback that is readable on the theme's background.
+pub fn success_color(visuals: &Visuals) -> Color32 {
+    if visuals.dark_mode { Color32::LIGHT_GREEN } else { Color32::DARK_GREEN }
+}
+
+/// Read a font file and check that egui will be able to use it.
+fn read_font(path: &str) -> io::Result<Vec<u8>> {
+    let bytes = std::fs::read(path).map_err(|e| io::Error::new(e.kind(), format!("Cannot read font '{}': {}", path, e)))?;
+    let font = ab_glyph::FontRef::try_from_slice(&bytes)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, format!("'{}' is not a TTF or OTF font", path)))?;
+    // egui only accepts fonts in this range
+    if !font.units_per_em().is_some_and(|u| (16.0..=16384.0).contains(&u)) {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, format!("'{}' has an unsupported font size unit", path)));
+    }
+    Ok(bytes)
+}
+
+/// Built-in fonts plus the configured main font (used first) and CJK font (used
+/// for any character the others lack).
+pub fn load_fonts(font_path: &str, cjk_font_path: &str) -> io::Result<FontDefinitions> {
+    let mut fonts = FontDefinitions::default();
+    if !font_path.is_empty() {
+        fonts.font_data.insert("user".to_string(), Arc::new(FontData::from_owned(read_font(font_path)?)));
+        fonts.families.entry(FontFamily::Proportional).or_default().insert(0, "user".to_string());
+    }
+    if !cjk_font_path.is_empty() {
+        fonts.font_data.insert("user_cjk".to_string(), Arc::new(FontData::from_owned(read_font(cjk_font_path)?)));
+        for family in [FontFamily::Proportional, FontFamily::Monospace] {
+            fonts.families.entry(family).or_default().push("user_cjk".to_string());
+        }
+    }
+    Ok(fonts)
+}
+
+/// Well-known places of fonts covering Chinese, Japanese and Korean.
+const CJK_FONT_CANDIDATES: &[&str] = &[
+    "/usr/share/fonts/opentype/noto/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/noto-cjk/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/google-noto-cjk/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/truetype/wqy/wqy-microhei.ttc",
+    "/System/Library/Fonts/PingFang.ttc",
+    "/System/Library/Fonts/Hiragino Sans GB.ttc",
+    "/Library/Fonts/Arial Unicode.ttf",
+    "C:\\Windows\\Fonts\\msyh.ttc",
+    "C:\\Windows\\Fonts\\YuGothM.ttc",
+    "C:\\Windows\\Fonts\\malgun.ttf",
+];
+
+/// First installed CJK font from a list of common locations.
+pub fn find_cjk_font() -> Option<PathBuf> {
+    CJK_FONT_CANDIDATES.iter().map(Path::new).find(|p| p.is_file()).map(Path::to_path_buf)
+}